@@ -0,0 +1,32 @@
+//! Benchmarks of both parts of every day on its real input.
+//!
+//! Run a single day with e.g. `cargo bench -p aoc -- day17/`.
+use common::input::InputSource;
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::time::Duration;
+
+fn bench_days(c: &mut Criterion) {
+    for day in aoc::days() {
+        let input = match day.load_input(&InputSource::Default) {
+            Ok(input) => input,
+            Err(err) => {
+                eprintln!("skipping day {}: {:#}", day.number, err);
+                continue;
+            }
+        };
+
+        let mut group = c.benchmark_group(format!("day{}", day.number));
+        // The slowest days take seconds per iteration, so keep the sample count low
+        group.sample_size(10);
+        group.warm_up_time(Duration::from_millis(500));
+        for part in day.parts() {
+            group.bench_function(format!("part{}", part), |b| {
+                b.iter(|| day.run(part, &input).expect("the day's own input is valid"))
+            });
+        }
+        group.finish();
+    }
+}
+
+criterion_group!(benches, bench_days);
+criterion_main!(benches);
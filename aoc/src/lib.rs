@@ -0,0 +1,55 @@
+//! The solutions of all days, shared by the `aoc` runner and the benchmarks.
+use common::Day;
+
+pub fn days() -> Vec<Day> {
+    vec![
+        day1::day(),
+        day2::day(),
+        day3::day(),
+        day4::day(),
+        day5::day(),
+        day6::day(),
+        day7::day(),
+        day8::day(),
+        day9::day(),
+        day10::day(),
+        day11::day(),
+        day12::day(),
+        day13::day(),
+        day14::day(),
+        day15::day(),
+        day16::day(),
+        day17::day(),
+        day18::day(),
+        day19::day(),
+        day20::day(),
+        day21::day(),
+        day22::day(),
+        day23::day(),
+        day24::day(),
+        day25::day(),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parsers_never_panic() {
+        for day in days() {
+            day.fuzz(100, 0).unwrap();
+        }
+    }
+
+    #[test]
+    fn params_belong_to_their_day() {
+        for day in days() {
+            for (i, param) in day.params.iter().enumerate() {
+                assert_eq!(param.day, day.number, "{}", param.name);
+                assert!(param.default_value().is_ok(), "{}", param.name);
+                assert!(day.params[..i].iter().all(|p| p.name != param.name));
+            }
+        }
+    }
+}
@@ -0,0 +1,385 @@
+use anyhow::{anyhow, bail, Result};
+use aoc::days;
+use clap::Parser;
+use common::answers::{input_hash, Answers, Entry, Verdict};
+use common::batch::{self, BatchArgs};
+use common::budget::Budgets;
+use common::config::{self, ConfigArgs};
+use common::input::{InputSource, DEFAULT_SIZE};
+use common::instrument;
+use common::parallel;
+use common::report::{Format, Report};
+use common::{Day, Outcome};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Runs the Advent of Code 2023 solutions and prints a summary of answers and timings.
+#[derive(Parser)]
+#[command(name = "aoc")]
+struct Args {
+    /// Day to run; all days are run in sequence when omitted
+    day: Option<u8>,
+
+    /// Only run this part
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+
+    /// Input file, or `-` to read from stdin [default: the day's bundled input.txt]
+    #[arg(long, requires = "day")]
+    input: Option<PathBuf>,
+
+    /// Use the bundled examples instead: example.txt, or exampleN.txt when N is given
+    #[arg(long, value_name = "N", num_args = 0..=1, conflicts_with = "input")]
+    example: Option<Option<u8>>,
+
+    /// Generate random inputs from this seed instead
+    #[arg(long, conflicts_with_all = ["input", "example"])]
+    seed: Option<u64>,
+
+    #[command(flatten)]
+    batch: BatchArgs,
+
+    #[command(flatten)]
+    config: ConfigArgs,
+
+    /// Size of the generated inputs, roughly their number of lines
+    #[arg(long, value_name = "N", default_value_t = DEFAULT_SIZE, requires = "seed")]
+    size: usize,
+
+    /// Print more diagnostics: -v for info, -vv for debug, -vvv for trace
+    #[arg(short, long, action = clap::ArgAction::Count)]
+    verbose: u8,
+
+    #[arg(long, value_enum, default_value_t)]
+    format: Format,
+
+    /// Compare the answers with the answers database and report PASS, FAIL or UNKNOWN
+    #[arg(long, conflicts_with = "batch")]
+    verify: bool,
+
+    /// Add the answers for inputs that are not in the answers database yet; implies
+    /// --verify. Not with changed puzzle parameters, whose answers are not the puzzle's.
+    #[arg(long, conflicts_with_all = ["batch", "config", "set"])]
+    record: bool,
+
+    /// Answers database [default: answers.toml at the root of the workspace]
+    #[arg(long)]
+    answers: Option<PathBuf>,
+
+    /// Check the time of every day against its budget [default: budgets.toml at the root
+    /// of the workspace]
+    #[arg(long, value_name = "PATH", num_args = 0..=1, conflicts_with = "batch")]
+    budgets: Option<Option<PathBuf>>,
+
+    /// Draw the puzzle of the day into this file instead of solving it, as an SVG, PNG or
+    /// PPM depending on its extension
+    #[arg(long, value_name = "PATH", requires = "day")]
+    render: Option<PathBuf>,
+
+    /// Write the graph of the puzzle of the day in the DOT language of Graphviz into this
+    /// file, or to stdout for `-`, instead of solving it
+    #[arg(long, value_name = "PATH", requires = "day", conflicts_with = "render")]
+    dot: Option<PathBuf>,
+
+    /// Highlight the solution in the graph, such as the cut edges or the longest path
+    #[arg(long, requires = "dot")]
+    highlight: bool,
+
+    /// Run the days, and the work within them that splits up, on this many threads. The
+    /// answers stay the same, but the times of days that run side by side get longer.
+    #[arg(long, value_name = "N", default_value_t = 1)]
+    threads: usize,
+
+    /// Make the long searches give up after this many seconds each, with the best answer
+    /// they found so far and their statistics
+    #[arg(long, value_name = "SECONDS")]
+    time_limit: Option<f64>,
+
+    /// Fuzz the parsers of the days for this many runs each instead of solving, starting
+    /// from their bundled inputs, and report the inputs that make them panic. --seed
+    /// picks other random inputs.
+    #[arg(long, value_name = "RUNS", conflicts_with_all = ["render", "dot", "verify", "record"])]
+    fuzz: Option<usize>,
+}
+
+fn default_answers_path() -> PathBuf {
+    PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/../answers.toml"))
+}
+
+fn default_budgets_path() -> PathBuf {
+    PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/../budgets.toml"))
+}
+
+/// `path` relative to the directory of the answers database, when it is inside it
+fn relative_to(path: &Path, answers_path: &Path) -> String {
+    let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    let base = answers_path
+        .parent()
+        .and_then(|dir| dir.canonicalize().ok());
+    let relative = base
+        .as_deref()
+        .and_then(|base| path.strip_prefix(base).ok());
+    relative.unwrap_or(&path).display().to_string()
+}
+
+fn format_duration(duration: Duration) -> String {
+    format!("{:.3} ms", duration.as_secs_f64() * 1000.0)
+}
+
+/// The input of a day, or why it could not be loaded, and the results of the parts
+struct DayRun<'a> {
+    day: &'a Day,
+    parts: Vec<u8>,
+    input: Result<String>,
+    results: Vec<Result<Outcome>>,
+}
+
+fn run<'a>(day: &'a Day, part: Option<u8>, source: &InputSource) -> DayRun<'a> {
+    let parts: Vec<u8> = day
+        .parts()
+        .into_iter()
+        .filter(|&p| part.is_none_or(|part| p == part))
+        .collect();
+    let input = day.load_input(source);
+    let results = match &input {
+        Ok(input) => parts.iter().map(|&part| day.run(part, input)).collect(),
+        Err(_) => Vec::new(),
+    };
+    DayRun {
+        day,
+        parts,
+        input,
+        results,
+    }
+}
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+    common::init_logging(args.verbose);
+    let source = match args.seed {
+        Some(seed) => InputSource::Generated {
+            seed,
+            size: args.size,
+        },
+        None => InputSource::new(args.input.clone(), args.example),
+    };
+    let days = days();
+
+    let selected: Vec<&Day> = match args.day {
+        Some(number) => {
+            let day = days
+                .iter()
+                .find(|day| day.number == number)
+                .ok_or_else(|| anyhow!("there is no day {}", number))?;
+            if let Some(part) = args.part {
+                if day.solver(part).is_none() {
+                    bail!("day {} has no part {}", number, part);
+                }
+            }
+            vec![day]
+        }
+        None => days.iter().collect(),
+    };
+    let all: Vec<&Day> = days.iter().collect();
+    args.config.load(&all, args.day)?.install()?;
+    if let Some(seconds) = args.time_limit {
+        instrument::set_time_limit(seconds)?;
+    }
+    if args.config.print_config {
+        print!("{}", config::to_toml(&selected)?);
+        return Ok(());
+    }
+
+    if args.batch.batch.is_some() {
+        let [day] = selected.as_slice() else {
+            bail!("--batch needs a day to run");
+        };
+        let parts: Vec<u8> = day
+            .parts()
+            .into_iter()
+            .filter(|&p| args.part.is_none_or(|part| p == part))
+            .collect();
+        return batch::run_args(day, &args.batch, &parts, args.format, args.threads);
+    }
+    if let Some(path) = &args.render {
+        let day = selected[0];
+        return day.draw(&day.load_input(&source)?, path);
+    }
+    if let Some(path) = &args.dot {
+        let day = selected[0];
+        return day.export_dot(&day.load_input(&source)?, path, args.highlight);
+    }
+    if let Some(runs) = args.fuzz {
+        let mut num_panicking = 0;
+        for day in &selected {
+            match day.fuzz(runs, args.seed.unwrap_or(0)) {
+                Ok(()) => println!("day {}: no panics in {} runs", day.number, runs),
+                Err(err) => {
+                    println!("{:#}", err);
+                    num_panicking += 1;
+                }
+            }
+        }
+        if num_panicking > 0 {
+            bail!("{} of {} days panic", num_panicking, selected.len());
+        }
+        return Ok(());
+    }
+
+    let verify = args.verify || args.record;
+    let answers_path = args.answers.clone().unwrap_or_else(default_answers_path);
+    let mut answers = match verify {
+        true => Answers::load(&answers_path)?,
+        false => Answers::default(),
+    };
+    let mut num_recorded = 0;
+
+    let budgets = match &args.budgets {
+        Some(path) => Some(Budgets::load(
+            &path.clone().unwrap_or_else(default_budgets_path),
+        )?),
+        None => None,
+    };
+
+    let text = args.format == Format::Text;
+    if text {
+        print!(
+            "{:>3}  {:>4}  {:>20}  {:>12}",
+            "day", "part", "answer", "time"
+        );
+        println!("{}", if verify { "  status" } else { "" });
+    }
+    let mut total = Duration::ZERO;
+    let mut num_failed = 0;
+    let mut num_over_budget = 0;
+    // On a single thread every day is reported as soon as it is done
+    let runs: Box<dyn Iterator<Item = DayRun>> = match args.threads {
+        0 | 1 => Box::new(selected.iter().map(|day| run(day, args.part, &source))),
+        threads => Box::new(
+            parallel::with_threads(threads, || {
+                parallel::map(&selected, |day| run(day, args.part, &source))
+            })?
+            .into_iter(),
+        ),
+    };
+    for DayRun {
+        day,
+        parts,
+        input,
+        results,
+    } in runs
+    {
+        let input = match input {
+            Ok(input) => input,
+            Err(err) => {
+                if text {
+                    println!("{:>3}  {:>4}  {}", day.number, "-", err);
+                } else {
+                    for &part in &parts {
+                        let result = Err(anyhow!("{:#}", err));
+                        println!("{}", Report::new(day, part, &source, &result).to_json());
+                    }
+                }
+                num_failed += 1;
+                continue;
+            }
+        };
+
+        let hash = input_hash(&input);
+        let mut day_total = Duration::ZERO;
+        for (part, result) in parts.into_iter().zip(results) {
+            let status = match (&result, verify) {
+                (Ok(outcome), true) => {
+                    let verdict = answers.verify(day.number, part, &hash, &outcome.answer);
+                    let recorded = verdict == Verdict::Unknown
+                        && args.record
+                        && answers.record(Entry {
+                            day: day.number,
+                            part,
+                            input: hash.clone(),
+                            answer: outcome.answer.clone(),
+                            input_path: day
+                                .input_path(&source)
+                                .map(|path| relative_to(&path, &answers_path)),
+                        });
+                    if verdict == Verdict::Fail {
+                        num_failed += 1;
+                    }
+                    if recorded {
+                        num_recorded += 1;
+                        Some("RECORDED".to_string())
+                    } else {
+                        Some(verdict.to_string())
+                    }
+                }
+                _ => None,
+            };
+            if !text {
+                let mut report = Report::new(day, part, &source, &result);
+                report.status = status.clone();
+                println!("{}", report.to_json());
+            }
+            match result {
+                Ok(outcome) => {
+                    day_total += outcome.elapsed;
+                    if text {
+                        print!(
+                            "{:>3}  {:>4}  {:>20}  {:>12}",
+                            day.number,
+                            part,
+                            outcome.answer,
+                            format_duration(outcome.elapsed)
+                        );
+                        match status {
+                            Some(status) => println!("  {}", status),
+                            None => println!(),
+                        }
+                    }
+                }
+                Err(err) => {
+                    if text {
+                        println!("{:>3}  {:>4}  error: {:#}", day.number, part, err);
+                    }
+                    num_failed += 1;
+                }
+            }
+        }
+        total += day_total;
+
+        let budget = budgets
+            .as_ref()
+            .and_then(|budgets| budgets.for_day(day.number));
+        if let Some(budget) = budget.filter(|&budget| day_total > budget) {
+            let message = format!(
+                "day {} took {}, over its budget of {}",
+                day.number,
+                format_duration(day_total),
+                format_duration(budget)
+            );
+            match text {
+                true => println!("{:>3}  {:>4}  {}", day.number, "-", message),
+                false => log::warn!("{}", message),
+            }
+            num_over_budget += 1;
+        }
+    }
+    if text {
+        println!("total time: {}", format_duration(total));
+    }
+    if num_recorded > 0 {
+        answers.save(&answers_path)?;
+        log::info!(
+            "recorded {} answer(s) in {}",
+            num_recorded,
+            answers_path.display()
+        );
+    }
+
+    if num_failed > 0 {
+        bail!("{} part(s) failed", num_failed);
+    }
+    if num_over_budget > 0 {
+        bail!("{} day(s) over budget", num_over_budget);
+    }
+    Ok(())
+}
@@ -0,0 +1,143 @@
+//! The database of confirmed answers, used to catch regressions.
+//!
+//! Answers are stored in a TOML file, keyed by day, part and a hash of the input, so
+//! that the answer to an example is never mistaken for the answer to the real input.
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::Path;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub day: u8,
+    pub part: u8,
+    /// [`input_hash`] of the input, in hexadecimal
+    pub input: String,
+    pub answer: String,
+    /// Where the input was read from when the answer was recorded, for reference only
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input_path: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    #[serde(default, rename = "answer")]
+    pub entries: Vec<Entry>,
+}
+
+/// How an answer compares to the database.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail,
+    /// There is no confirmed answer for this input yet
+    Unknown,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Verdict::Pass => "PASS",
+            Verdict::Fail => "FAIL",
+            Verdict::Unknown => "UNKNOWN",
+        };
+        f.pad(name)
+    }
+}
+
+/// A stable 64-bit FNV-1a hash of the input. Unlike the hashers in `std` it does not
+/// change between Rust versions, so it can be stored.
+pub fn input_hash(input: &str) -> String {
+    let hash = input.bytes().fold(0xcbf29ce484222325u64, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    });
+    format!("{:016x}", hash)
+}
+
+impl Answers {
+    /// Reads the database, which is empty if the file doesn't exist yet.
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("could not read {}", path.display()))?;
+        toml::from_str(&text).with_context(|| format!("could not parse {}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let text = toml::to_string(self)?;
+        std::fs::write(path, text).with_context(|| format!("could not write {}", path.display()))
+    }
+
+    pub fn get(&self, day: u8, part: u8, input_hash: &str) -> Option<&Entry> {
+        self.entries
+            .iter()
+            .find(|entry| entry.day == day && entry.part == part && entry.input == input_hash)
+    }
+
+    pub fn verify(&self, day: u8, part: u8, input_hash: &str, answer: &str) -> Verdict {
+        match self.get(day, part, input_hash) {
+            Some(entry) if entry.answer == answer => Verdict::Pass,
+            Some(_) => Verdict::Fail,
+            None => Verdict::Unknown,
+        }
+    }
+
+    /// Adds an answer for an input without one. Returns false, and leaves the database
+    /// alone, if there already is an answer: a confirmed answer is only changed by hand.
+    pub fn record(&mut self, entry: Entry) -> bool {
+        if self.get(entry.day, entry.part, &entry.input).is_some() {
+            return false;
+        }
+        self.entries.push(entry);
+        self.entries.sort_by_key(|entry| (entry.day, entry.part));
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(answer: &str) -> Entry {
+        Entry {
+            day: 4,
+            part: 1,
+            input: input_hash("input"),
+            answer: answer.to_string(),
+            input_path: None,
+        }
+    }
+
+    #[test]
+    fn hash_is_stable() {
+        assert_eq!(input_hash(""), "cbf29ce484222325");
+        assert_eq!(input_hash("a"), "af63dc4c8601ec8c");
+    }
+
+    #[test]
+    fn verdicts() {
+        let mut answers = Answers::default();
+        let hash = input_hash("input");
+        assert_eq!(answers.verify(4, 1, &hash, "13"), Verdict::Unknown);
+        assert!(answers.record(entry("13")));
+        assert!(!answers.record(entry("14")));
+        assert_eq!(answers.verify(4, 1, &hash, "13"), Verdict::Pass);
+        assert_eq!(answers.verify(4, 1, &hash, "14"), Verdict::Fail);
+        assert_eq!(
+            answers.verify(4, 1, &input_hash("example"), "13"),
+            Verdict::Unknown
+        );
+    }
+
+    #[test]
+    fn toml_round_trip() {
+        let mut answers = Answers::default();
+        answers.record(entry("13"));
+        let text = toml::to_string(&answers).unwrap();
+        assert!(text.starts_with("[[answer]]\nday = 4\n"));
+        assert_eq!(toml::from_str::<Answers>(&text).unwrap(), answers);
+        assert_eq!(toml::from_str::<Answers>("").unwrap(), Answers::default());
+    }
+}
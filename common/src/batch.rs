@@ -0,0 +1,330 @@
+//! Batch runs: solving a day on every input in a directory, or matching a glob, with one
+//! row per input and part, checked against expected answers when there are any.
+//!
+//! Expected answers live in a TOML file with a table per input file name, by default the
+//! `expected.toml` in the directory of the inputs:
+//!
+//! ```toml
+//! ["alice.txt"]
+//! part1 = "54450"
+//! part2 = "54265"
+//! ```
+use crate::answers::Verdict;
+use crate::input::InputSource;
+use crate::report::{Format, Report};
+use crate::{parallel, Day, Outcome};
+use anyhow::{anyhow, bail, Context, Result};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Name of the expected answers file that is picked up next to the inputs
+pub const EXPECTED_FILE: &str = "expected.toml";
+
+/// Command line arguments of batch runs, shared by the `aoc` runner and the days.
+#[derive(clap::Args, Debug, Default)]
+pub struct BatchArgs {
+    /// Solve every file in this directory, or matching this glob, such as
+    /// `inputs/*.txt`, instead of a single input. `*` and `?` are only allowed in the
+    /// file name.
+    #[arg(
+        long,
+        value_name = "PATH",
+        conflicts_with_all = ["input", "example", "seed", "render", "dot", "fuzz"]
+    )]
+    pub batch: Option<String>,
+
+    /// Expected answers of the batch [default: expected.toml next to the inputs, if any]
+    #[arg(long, value_name = "PATH", requires = "batch")]
+    pub expected: Option<PathBuf>,
+}
+
+#[derive(Deserialize, Debug, Default, Clone, PartialEq, Eq)]
+struct ExpectedAnswers {
+    part1: Option<String>,
+    part2: Option<String>,
+}
+
+/// Expected answers by input file name
+#[derive(Deserialize, Debug, Default, Clone, PartialEq, Eq)]
+#[serde(transparent)]
+pub struct Expected(HashMap<String, ExpectedAnswers>);
+
+impl Expected {
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("could not read {}", path.display()))?;
+        toml::from_str(&text).with_context(|| format!("could not parse {}", path.display()))
+    }
+
+    pub fn get(&self, file: &Path, part: u8) -> Option<&str> {
+        let name = file.file_name()?.to_str()?;
+        let answers = self.0.get(name)?;
+        match part {
+            1 => answers.part1.as_deref(),
+            2 => answers.part2.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+/// Whether `name` matches `pattern`, where `*` matches any run of characters and `?`
+/// any single one
+fn matches(pattern: &[char], name: &[char]) -> bool {
+    match (pattern.first(), name.first()) {
+        (None, _) => name.is_empty(),
+        (Some('*'), _) => {
+            matches(&pattern[1..], name) || (!name.is_empty() && matches(pattern, &name[1..]))
+        }
+        (Some('?'), Some(_)) => matches(&pattern[1..], &name[1..]),
+        (Some(p), Some(n)) => p == n && matches(&pattern[1..], &name[1..]),
+        (Some(_), None) => false,
+    }
+}
+
+/// The input files a batch pattern stands for, in order of their names: every file in a
+/// directory but hidden ones and the expected answers, the files matching a glob, or a
+/// single file
+pub fn input_files(pattern: &str) -> Result<Vec<PathBuf>> {
+    let path = Path::new(pattern);
+    let (dir, name) = match path.file_name().and_then(|name| name.to_str()) {
+        _ if path.is_dir() => (path, "*"),
+        Some(name) if name.contains(['*', '?']) => (path.parent().unwrap_or(Path::new("")), name),
+        _ => return Ok(vec![path.to_path_buf()]),
+    };
+    let dir = match dir.as_os_str().is_empty() {
+        true => Path::new("."),
+        false => dir,
+    };
+    if dir.to_string_lossy().contains(['*', '?']) {
+        bail!("{}: only file names can have wildcards", pattern);
+    }
+
+    let pattern: Vec<char> = name.chars().collect();
+    let mut files = Vec::new();
+    let entries =
+        std::fs::read_dir(dir).with_context(|| format!("could not list {}", dir.display()))?;
+    for entry in entries {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let name_chars: Vec<char> = name.chars().collect();
+        if entry.path().is_file()
+            && !name.starts_with('.')
+            && name != EXPECTED_FILE
+            && matches(&pattern, &name_chars)
+        {
+            files.push(entry.path());
+        }
+    }
+    files.sort();
+    if files.is_empty() {
+        bail!("no input files match {}", dir.join(name).display());
+    }
+    Ok(files)
+}
+
+/// The answer to one part of one input of a batch
+pub struct Row {
+    pub file: PathBuf,
+    pub part: u8,
+    pub result: Result<Outcome>,
+    pub expected: Option<String>,
+}
+
+impl Row {
+    /// PASS or FAIL against the expected answer, if there is one
+    pub fn verdict(&self) -> Option<Verdict> {
+        let expected = self.expected.as_ref()?;
+        match &self.result {
+            Ok(outcome) if &outcome.answer == expected => Some(Verdict::Pass),
+            _ => Some(Verdict::Fail),
+        }
+    }
+}
+
+/// Solves the `parts` of `day` for every file, on `threads` threads
+pub fn run(
+    day: &Day,
+    files: &[PathBuf],
+    parts: &[u8],
+    expected: &Expected,
+    threads: usize,
+) -> Result<Vec<Row>> {
+    let solve = |file: &PathBuf| -> Vec<Row> {
+        let input = day.read_input(file);
+        parts
+            .iter()
+            .map(|&part| Row {
+                file: file.clone(),
+                part,
+                result: match &input {
+                    Ok(input) => day.run(part, input),
+                    Err(err) => Err(anyhow!("{:#}", err)),
+                },
+                expected: expected.get(file, part).map(str::to_string),
+            })
+            .collect()
+    };
+    let rows = parallel::with_threads(threads, || parallel::map(files, solve))?;
+    Ok(rows.into_iter().flatten().collect())
+}
+
+/// Prints the rows as a table or as JSON reports, and fails if any part failed or gave
+/// another answer than expected
+pub fn print(day: &Day, rows: &[Row], format: Format) -> Result<()> {
+    let with_expected = rows.iter().any(|row| row.expected.is_some());
+    let width = rows
+        .iter()
+        .map(|row| row.file.display().to_string().len())
+        .max()
+        .unwrap_or(0)
+        .max(4);
+    if format == Format::Text {
+        print!(
+            "{:<width$}  {:>4}  {:>20}  {:>12}",
+            "file", "part", "answer", "time"
+        );
+        match with_expected {
+            true => println!("  {:>20}  status", "expected"),
+            false => println!(),
+        }
+    }
+
+    let mut num_failed = 0;
+    for row in rows {
+        let verdict = row.verdict();
+        if row.result.is_err() || verdict == Some(Verdict::Fail) {
+            num_failed += 1;
+        }
+        if format == Format::Json {
+            let mut report = Report::new(day, row.part, &InputSource::Stdin, &row.result);
+            report.input_path = Some(row.file.display().to_string());
+            report.expected = row.expected.clone();
+            report.status = verdict.map(|verdict| verdict.to_string());
+            println!("{}", report.to_json());
+            continue;
+        }
+        print!("{:<width$}  {:>4}  ", row.file.display(), row.part);
+        match &row.result {
+            Ok(outcome) => print!(
+                "{:>20}  {:>12}",
+                outcome.answer,
+                format!("{:.3} ms", outcome.elapsed.as_secs_f64() * 1000.0)
+            ),
+            Err(err) => print!("error: {:#}", err),
+        }
+        match (&row.expected, verdict) {
+            (Some(expected), Some(verdict)) => println!("  {:>20}  {}", expected, verdict),
+            _ => println!(),
+        }
+    }
+
+    if num_failed > 0 {
+        bail!("{} of {} part(s) failed", num_failed, rows.len());
+    }
+    Ok(())
+}
+
+/// Runs a batch from the command line: finds the files and the expected answers, solves
+/// the `parts` and prints the rows
+pub fn run_args(
+    day: &Day,
+    args: &BatchArgs,
+    parts: &[u8],
+    format: Format,
+    threads: usize,
+) -> Result<()> {
+    let pattern = args
+        .batch
+        .as_deref()
+        .ok_or_else(|| anyhow!("no batch to run"))?;
+    let files = input_files(pattern)?;
+    let default_expected = files
+        .first()
+        .and_then(|file| file.parent())
+        .map(|dir| dir.join(EXPECTED_FILE))
+        .filter(|path| path.exists());
+    let expected = match args.expected.clone().or(default_expected) {
+        Some(path) => Expected::load(&path)?,
+        None => Expected::default(),
+    };
+    let rows = run(day, &files, parts, &expected, threads)?;
+    print(day, &rows, format)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn glob(pattern: &str, name: &str) -> bool {
+        let chars = |s: &str| s.chars().collect::<Vec<_>>();
+        matches(&chars(pattern), &chars(name))
+    }
+
+    #[test]
+    fn globs() {
+        assert!(glob("*", "input.txt"));
+        assert!(glob("*.txt", "input.txt"));
+        assert!(glob("example?.txt", "example2.txt"));
+        assert!(glob("*ex*2*", "example2.txt"));
+        assert!(!glob("example?.txt", "example.txt"));
+        assert!(!glob("*.txt", "input.toml"));
+        assert!(glob("", ""));
+    }
+
+    #[test]
+    fn expected_answers() {
+        let expected: Expected =
+            toml::from_str("[\"a.txt\"]\npart1 = \"12\"\n\n[\"b.txt\"]\npart2 = \"3\"\n").unwrap();
+        assert_eq!(expected.get(Path::new("dir/a.txt"), 1), Some("12"));
+        assert_eq!(expected.get(Path::new("a.txt"), 2), None);
+        assert_eq!(expected.get(Path::new("b.txt"), 2), Some("3"));
+        assert_eq!(expected.get(Path::new("c.txt"), 1), None);
+    }
+
+    #[test]
+    fn rows_and_verdicts() {
+        let dir = std::env::temp_dir().join(format!("aoc-batch-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("a.txt"), "12").unwrap();
+        std::fs::write(dir.join("b.txt"), "").unwrap();
+        std::fs::write(dir.join(EXPECTED_FILE), "[\"a.txt\"]\npart1 = \"12\"\n").unwrap();
+        let day = Day {
+            number: 0,
+            dir: "",
+            part1: Some(|input| match input {
+                "" => Err(anyhow!("empty input")),
+                input => Ok(input.to_string()),
+            }),
+            part2: None,
+            generate: None,
+            render: None,
+            dot: None,
+            parse: None,
+            params: &[],
+        };
+
+        let files = input_files(dir.to_str().unwrap()).unwrap();
+        let expected = Expected::load(&dir.join(EXPECTED_FILE)).unwrap();
+        let rows = run(&day, &files, &[1], &expected, 1).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+        let verdicts: Vec<_> = rows.iter().map(Row::verdict).collect();
+        assert_eq!(verdicts, [Some(Verdict::Pass), None]);
+        assert!(rows[1].result.is_err());
+        assert!(print(&day, &rows, Format::Json).is_err());
+    }
+
+    #[test]
+    fn files_in_a_directory_and_by_glob() {
+        let src = concat!(env!("CARGO_MANIFEST_DIR"), "/src");
+        let all = input_files(src).unwrap();
+        assert!(all.iter().any(|file| file.ends_with("batch.rs")));
+        let some = input_files(&format!("{}/b*.rs", src)).unwrap();
+        assert!(some.iter().all(|file| {
+            let name = file.file_name().unwrap().to_string_lossy();
+            name.starts_with('b') && name.ends_with(".rs")
+        }));
+        assert!(some.len() < all.len());
+        assert!(input_files(&format!("{}/*.nothing", src)).is_err());
+    }
+}
@@ -0,0 +1,73 @@
+//! Time budgets per day, so that the runner can flag performance regressions.
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::Duration;
+
+/// Budgets in milliseconds for both parts of a day together, read from a TOML file:
+///
+/// ```toml
+/// default = 100
+///
+/// [day]
+/// 23 = 15000
+/// ```
+#[derive(Deserialize, Debug, Default, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Budgets {
+    /// Budget of the days without their own entry; unlimited when missing
+    default: Option<f64>,
+    /// Keyed by day number; TOML keys are always strings
+    #[serde(default)]
+    day: HashMap<String, f64>,
+}
+
+impl Budgets {
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("could not read {}", path.display()))?;
+        Self::parse(&text).with_context(|| format!("could not parse {}", path.display()))
+    }
+
+    pub fn parse(text: &str) -> Result<Self> {
+        let budgets: Self = toml::from_str(text)?;
+        if let Some(key) = budgets.day.keys().find(|key| key.parse::<u8>().is_err()) {
+            bail!("{:?} is not a day number", key);
+        }
+        // Negative, infinite and NaN budgets, and those too long for a `Duration`
+        let budget = |ms: f64| Duration::try_from_secs_f64(ms / 1000.0).is_ok();
+        if let Some(ms) = budgets.default.filter(|&ms| !budget(ms)) {
+            bail!("default = {}: not a budget in milliseconds", ms);
+        }
+        if let Some((key, ms)) = budgets.day.iter().find(|(_, &ms)| !budget(ms)) {
+            bail!("day {} = {}: not a budget in milliseconds", key, ms);
+        }
+        Ok(budgets)
+    }
+
+    pub fn for_day(&self, day: u8) -> Option<Duration> {
+        let ms = self.day.get(&day.to_string()).copied().or(self.default)?;
+        Some(Duration::from_secs_f64(ms / 1000.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn days_override_the_default() {
+        let budgets = Budgets::parse("default = 100\n[day]\n23 = 1500\n").unwrap();
+        assert_eq!(budgets.for_day(1), Some(Duration::from_millis(100)));
+        assert_eq!(budgets.for_day(23), Some(Duration::from_millis(1500)));
+        assert_eq!(Budgets::parse("").unwrap().for_day(1), None);
+        assert!(Budgets::parse("defualt = 100").is_err());
+        assert!(Budgets::parse("[day]\nday23 = 100").is_err());
+        assert!(Budgets::parse("default = -5.0").is_err());
+        assert!(Budgets::parse("default = nan").is_err());
+        assert!(Budgets::parse("[day]\n23 = -inf").is_err());
+        assert!(Budgets::parse("[day]\n23 = 1e300").is_err());
+        assert!(Budgets::parse("default = 0").is_ok());
+    }
+}
@@ -0,0 +1,324 @@
+//! Puzzle parameters, such as how far the empty space of day 11 expands or how many steps
+//! the elf of day 21 takes, so that variants of the puzzles and their examples run without
+//! recompiling. Every day declares its [`Param`]s with their defaults, which a TOML file
+//! with a table per day overrides:
+//!
+//! ```toml
+//! [day.11]
+//! expansion = 100
+//!
+//! [day.21]
+//! steps = 6
+//! ```
+//!
+//! and `--set` overrides single values on top of that. The binaries install the config
+//! once at startup; without one every parameter keeps its default.
+use crate::Day;
+use anyhow::{anyhow, bail, Context, Result};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Command line arguments of the config, shared by the `aoc` runner and the days.
+#[derive(clap::Args, Debug, Default)]
+pub struct ConfigArgs {
+    /// Read the puzzle parameters from this TOML file, with a `[day.N]` table per day
+    #[arg(long, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+
+    /// Set a puzzle parameter, overriding the config file: NAME=VALUE, or DAY.NAME=VALUE
+    /// for days other than the one being run
+    #[arg(long, value_name = "NAME=VALUE")]
+    pub set: Vec<String>,
+
+    /// Print the puzzle parameters with their values, as a config file, instead of solving
+    #[arg(long, conflicts_with_all = ["batch", "render", "dot", "fuzz"])]
+    pub print_config: bool,
+}
+
+impl ConfigArgs {
+    /// The config of the file and the `--set` values, checked against the parameters of
+    /// `days`. `day` is the day that `--set` refers to when it names none.
+    pub fn load(&self, days: &[&Day], day: Option<u8>) -> Result<Config> {
+        let mut config = match &self.config {
+            Some(path) => Config::load(path)?,
+            None => Config::default(),
+        };
+        for assignment in &self.set {
+            config.set(day, assignment)?;
+        }
+        config.check(days)?;
+        Ok(config)
+    }
+}
+
+/// A parameter of a day, with its default as a TOML value such as `5` or `"hb"`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Param {
+    pub day: u8,
+    pub name: &'static str,
+    pub default: &'static str,
+    pub help: &'static str,
+}
+
+impl Param {
+    pub const fn new(
+        day: u8,
+        name: &'static str,
+        default: &'static str,
+        help: &'static str,
+    ) -> Self {
+        Self {
+            day,
+            name,
+            default,
+            help,
+        }
+    }
+
+    pub fn default_value(&self) -> Result<toml::Value> {
+        parse_value(self.default)
+            .with_context(|| format!("day {}: bad default for {}", self.day, self.name))
+    }
+
+    /// The value in the installed config, or the default
+    pub fn value(&self) -> Result<toml::Value> {
+        match CONFIG
+            .get()
+            .and_then(|config| config.get(self.day, self.name))
+        {
+            Some(value) => Ok(value.clone()),
+            None => self.default_value(),
+        }
+    }
+
+    /// The value as a `T`, which fails for values out of its range, such as a negative
+    /// count
+    pub fn get<T: DeserializeOwned>(&self) -> Result<T> {
+        let value = self.value()?;
+        value.clone().try_into().map_err(|err| {
+            let err = err.to_string();
+            anyhow!(
+                "day {}: {} = {}: {}",
+                self.day,
+                self.name,
+                value,
+                err.trim_end().replace('\n', " ")
+            )
+        })
+    }
+}
+
+/// A TOML value, such as `12`, `1e-3` or `"hb"`
+fn parse_value(text: &str) -> Result<toml::Value> {
+    let mut table: toml::Table = toml::from_str(&format!("value = {}", text))?;
+    table
+        .remove("value")
+        .ok_or_else(|| anyhow!("{:?} is not a value", text))
+}
+
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigFile {
+    /// Keyed by day number; TOML keys are always strings
+    #[serde(default)]
+    day: HashMap<String, toml::Table>,
+}
+
+/// Values of parameters by day and name
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Config {
+    values: BTreeMap<(u8, String), toml::Value>,
+}
+
+impl Config {
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("could not read {}", path.display()))?;
+        Self::parse(&text).with_context(|| format!("could not parse {}", path.display()))
+    }
+
+    pub fn parse(text: &str) -> Result<Self> {
+        let file: ConfigFile = toml::from_str(text)?;
+        let mut values = BTreeMap::new();
+        for (key, table) in file.day {
+            let Ok(day) = key.parse::<u8>() else {
+                bail!("{:?} is not a day number", key);
+            };
+            for (name, value) in table {
+                values.insert((day, name), value);
+            }
+        }
+        Ok(Self { values })
+    }
+
+    /// Sets a value from `NAME=VALUE` for `day`, or from `DAY.NAME=VALUE`. Values that
+    /// are not TOML are taken as strings, so that `name=hb` needs no quotes.
+    pub fn set(&mut self, day: Option<u8>, assignment: &str) -> Result<()> {
+        let Some((name, text)) = assignment.split_once('=') else {
+            bail!("{:?} is not NAME=VALUE", assignment);
+        };
+        let (day, name) = match name.trim().split_once('.') {
+            Some((number, name)) => match number.parse::<u8>() {
+                Ok(number) => (number, name),
+                Err(_) => bail!("{:?} is not a day number", number),
+            },
+            None => match day {
+                Some(day) => (day, name.trim()),
+                None => bail!("{:?} needs a day: DAY.NAME=VALUE", assignment),
+            },
+        };
+        let text = text.trim();
+        let value = parse_value(text).unwrap_or_else(|_| toml::Value::String(text.to_string()));
+        self.values.insert((day, name.to_string()), value);
+        Ok(())
+    }
+
+    pub fn get(&self, day: u8, name: &str) -> Option<&toml::Value> {
+        self.values.get(&(day, name.to_string()))
+    }
+
+    /// Fails on parameters that `days` do not have and on values of another type than
+    /// their default. Parameters of days that are not among `days` are left alone, as
+    /// long as the day exists.
+    pub fn check(&self, days: &[&Day]) -> Result<()> {
+        for ((number, name), value) in &self.values {
+            if !(1..=25).contains(number) {
+                bail!("there is no day {}", number);
+            }
+            let Some(day) = days.iter().find(|day| day.number == *number) else {
+                continue;
+            };
+            let Some(param) = day.params.iter().find(|param| param.name == name) else {
+                bail!("day {} has no parameter {}", number, name);
+            };
+            let default = param.default_value()?;
+            // Integers are fine where a float is expected
+            let same_type = default.same_type(value) || (default.is_float() && value.is_integer());
+            if !same_type {
+                let kind = default.type_str();
+                let article = match kind.starts_with(['a', 'e', 'i', 'o', 'u']) {
+                    true => "an",
+                    false => "a",
+                };
+                bail!(
+                    "day {}: {} should be {} {} like {}, not {}",
+                    number,
+                    name,
+                    article,
+                    kind,
+                    default,
+                    value
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Makes the values visible to [`Param::get`]. There is one config per process.
+    pub fn install(self) -> Result<()> {
+        CONFIG
+            .set(self)
+            .map_err(|_| anyhow!("the config is already installed"))
+    }
+}
+
+/// The parameters of `days` with their values as a config file, with their help as
+/// comments
+pub fn to_toml(days: &[&Day]) -> Result<String> {
+    let mut out = String::new();
+    for day in days.iter().filter(|day| !day.params.is_empty()) {
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out.push_str(&format!("[day.{}]\n", day.number));
+        for param in day.params {
+            out.push_str(&format!(
+                "# {}\n{} = {}\n",
+                param.help,
+                param.name,
+                param.value()?
+            ));
+        }
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PARAMS: &[Param] = &[
+        Param::new(11, "expansion", "1000000", "Rows per empty row"),
+        Param::new(11, "name", "\"hb\"", "A module"),
+        Param::new(11, "scale", "1e-3", "A factor"),
+    ];
+
+    fn day() -> Day {
+        Day {
+            number: 11,
+            dir: "",
+            part1: None,
+            part2: None,
+            generate: None,
+            render: None,
+            dot: None,
+            parse: None,
+            params: PARAMS,
+        }
+    }
+
+    #[test]
+    fn defaults() {
+        assert_eq!(PARAMS[0].get::<usize>().unwrap(), 1_000_000);
+        assert_eq!(PARAMS[1].get::<String>().unwrap(), "hb");
+        assert_eq!(PARAMS[2].get::<f64>().unwrap(), 1e-3);
+        assert!(PARAMS[0].get::<u8>().is_err());
+        assert!(PARAMS[1].get::<usize>().is_err());
+    }
+
+    #[test]
+    fn files_and_overrides() {
+        let mut config = Config::parse("[day.11]\nexpansion = 10\n\n[day.3]\nx = 1\n").unwrap();
+        config.set(Some(11), "name=rx").unwrap();
+        config.set(None, "11.scale = 2").unwrap();
+        config.set(Some(11), "expansion=100").unwrap();
+        assert_eq!(
+            config.get(11, "expansion"),
+            Some(&toml::Value::Integer(100))
+        );
+        assert_eq!(
+            config.get(11, "name"),
+            Some(&toml::Value::String("rx".into()))
+        );
+        assert_eq!(config.get(11, "scale"), Some(&toml::Value::Integer(2)));
+        // Day 3 is not being checked
+        config.check(&[&day()]).unwrap();
+
+        assert!(Config::parse("[day]\neleven = {}").is_err());
+        assert!(Config::parse("[days.11]").is_err());
+        assert!(Config::default().set(None, "expansion=10").is_err());
+        assert!(Config::default().set(Some(11), "expansion").is_err());
+    }
+
+    #[test]
+    fn unknown_parameters_and_wrong_types() {
+        let check = |text: &str| Config::parse(text).unwrap().check(&[&day()]);
+        assert!(check("[day.11]\nexpansoin = 10").is_err());
+        assert!(check("[day.11]\nexpansion = \"10\"").is_err());
+        assert!(check("[day.11]\nname = 1").is_err());
+        assert!(check("[day.26]\nx = 1").is_err());
+        assert!(check("[day.11]\nexpansion = -1").is_ok());
+    }
+
+    #[test]
+    fn printed_config_parses() {
+        let text = to_toml(&[&day()]).unwrap();
+        assert!(text.starts_with("[day.11]\n# Rows per empty row\nexpansion = 1000000\n"));
+        assert!(Config::parse(&text).unwrap().check(&[&day()]).is_ok());
+    }
+}
@@ -0,0 +1,286 @@
+//! Finding where a simulation starts repeating itself, so that it can skip ahead to any
+//! number of steps.
+use crate::math::crt;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// How to look for the cycle
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Mode {
+    /// Remembers the key of every state, so that it takes every step only once
+    Hash,
+    /// Brent's algorithm, which only keeps two states around but takes up to three times
+    /// as many steps
+    Brent,
+}
+
+/// From step `start` on, the states repeat every `period` steps
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub period: usize,
+}
+
+impl Cycle {
+    /// The first step with the same state as step `n`
+    pub fn reduce(&self, n: usize) -> usize {
+        match n.checked_sub(self.start) {
+            Some(into_cycle) => self.start + into_cycle % self.period,
+            None => n,
+        }
+    }
+}
+
+/// Finds where the states `initial`, then `initial` after one `step`, and so on start to
+/// repeat, where states with the same `key` count as the same. This never returns if
+/// they do not repeat.
+pub fn find_cycle<S: Clone, K: Hash + Eq>(
+    initial: S,
+    step: impl FnMut(&mut S),
+    key: impl FnMut(&S) -> K,
+    mode: Mode,
+) -> Cycle {
+    match mode {
+        Mode::Hash => find_cycle_hashed(initial, step, key),
+        Mode::Brent => find_cycle_brent(initial, step, key),
+    }
+}
+
+fn find_cycle_hashed<S, K: Hash + Eq>(
+    mut state: S,
+    mut step: impl FnMut(&mut S),
+    mut key: impl FnMut(&S) -> K,
+) -> Cycle {
+    let mut seen = HashMap::new();
+    for steps in 0.. {
+        if let Some(start) = seen.insert(key(&state), steps) {
+            return Cycle {
+                start,
+                period: steps - start,
+            };
+        }
+        step(&mut state);
+    }
+    unreachable!("the states ran out of steps without repeating")
+}
+
+fn find_cycle_brent<S: Clone, K: Eq>(
+    initial: S,
+    mut step: impl FnMut(&mut S),
+    mut key: impl FnMut(&S) -> K,
+) -> Cycle {
+    // The tortoise waits at powers of two for the hare to come around
+    let (mut power, mut period) = (1, 1);
+    let mut tortoise = key(&initial);
+    let mut hare = initial.clone();
+    step(&mut hare);
+    let mut hare_key = key(&hare);
+    while tortoise != hare_key {
+        if power == period {
+            tortoise = hare_key;
+            power *= 2;
+            period = 0;
+        }
+        step(&mut hare);
+        hare_key = key(&hare);
+        period += 1;
+    }
+
+    // With the hare a period ahead, they first meet where the cycle starts
+    let mut tortoise = initial.clone();
+    let mut hare = initial;
+    for _ in 0..period {
+        step(&mut hare);
+    }
+    let mut start = 0;
+    while key(&tortoise) != key(&hare) {
+        step(&mut tortoise);
+        step(&mut hare);
+        start += 1;
+    }
+    Cycle { start, period }
+}
+
+/// The state after `n` steps from `initial`, for any `n` however large, as long as the
+/// states repeat. See [`find_cycle`].
+pub fn state_after<S: Clone, K: Hash + Eq>(
+    initial: S,
+    mut step: impl FnMut(&mut S),
+    key: impl FnMut(&S) -> K,
+    n: usize,
+    mode: Mode,
+) -> S {
+    let cycle = find_cycle(initial.clone(), &mut step, key, mode);
+    let mut state = initial;
+    for _ in 0..cycle.reduce(n) {
+        step(&mut state);
+    }
+    state
+}
+
+/// The steps at which something happens in a simulation that runs into `cycle`: those in
+/// `times`, in order, which run until the end of the first period of the cycle, and those
+/// from the start of the cycle on also every period later
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Occurrences {
+    pub times: Vec<usize>,
+    pub cycle: Cycle,
+}
+
+impl Occurrences {
+    pub fn contains(&self, step: usize) -> bool {
+        self.times.binary_search(&self.cycle.reduce(step)).is_ok()
+    }
+
+    /// The steps within the cycle, as congruences `(step, period)`
+    fn congruences(&self) -> impl Iterator<Item = (i64, i64)> + '_ {
+        self.times
+            .iter()
+            .filter(|&&step| step >= self.cycle.start)
+            .map(|&step| (step as i64, self.cycle.period as i64))
+    }
+}
+
+/// The first step at which all the `occurrences` happen together, or `None` if they never
+/// do. The cycles need not start at the same step, nor have coprime periods.
+pub fn first_common(occurrences: &[Occurrences]) -> Option<usize> {
+    // Before the last cycle starts, look for the answer step by step
+    let start = occurrences
+        .iter()
+        .map(|occurrences| occurrences.cycle.start)
+        .max()
+        .unwrap_or(0);
+    if let Some(step) = (0..start).find(|&step| occurrences.iter().all(|o| o.contains(step))) {
+        return Some(step);
+    }
+
+    // Every combination of steps within the cycles gives a solution, if any
+    let mut solutions = vec![(0, 1)];
+    for occurrences in occurrences {
+        solutions = solutions
+            .iter()
+            .flat_map(|&solution| {
+                occurrences
+                    .congruences()
+                    .filter_map(move |congruence| crt([solution, congruence]))
+            })
+            .collect();
+    }
+    // The first solution that every cycle has reached
+    solutions
+        .iter()
+        .map(|&(step, period)| {
+            let behind = (start as i64 - step).max(0);
+            (step + (behind + period - 1) / period * period) as usize
+        })
+        .min()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// x -> x^2 + 1 modulo `m`, which runs into a cycle after a while
+    fn square_plus_one(m: u64) -> impl FnMut(&mut u64) {
+        move |x| *x = (*x * *x + 1) % m
+    }
+
+    /// The cycle found by simulating and remembering every state
+    fn naive_cycle(initial: u64, m: u64) -> Cycle {
+        let mut states = vec![initial];
+        let mut step = square_plus_one(m);
+        loop {
+            let mut next = *states.last().unwrap();
+            step(&mut next);
+            if let Some(start) = states.iter().position(|&state| state == next) {
+                return Cycle {
+                    start,
+                    period: states.len() - start,
+                };
+            }
+            states.push(next);
+        }
+    }
+
+    #[test]
+    fn both_modes_agree_with_simulation() {
+        for m in 1..60 {
+            for initial in 0..m {
+                let expected = naive_cycle(initial, m);
+                for mode in [Mode::Hash, Mode::Brent] {
+                    let cycle = find_cycle(initial, square_plus_one(m), |&x| x, mode);
+                    assert_eq!(cycle, expected, "{} mod {} in {:?} mode", initial, m, mode);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn states_after_many_steps() {
+        for mode in [Mode::Hash, Mode::Brent] {
+            for n in 0..100 {
+                let mut expected = 3;
+                let mut step = square_plus_one(1000);
+                for _ in 0..n {
+                    step(&mut expected);
+                }
+                let state = state_after(3, square_plus_one(1000), |&x| x, n, mode);
+                assert_eq!(state, expected, "after {} steps in {:?} mode", n, mode);
+            }
+            // 3, 10, 101, 202, 805, 26, 677, 330, 901, 802, 205, 26, ...
+            let state = state_after(3, square_plus_one(1000), |&x| x, 1_000_000_000, mode);
+            assert_eq!(
+                state,
+                [26, 677, 330, 901, 802, 205][(1_000_000_000 - 5) % 6]
+            );
+        }
+    }
+
+    #[test]
+    fn keys_can_forget_part_of_the_state() {
+        // The counter grows forever, but only its last digit counts
+        let cycle = find_cycle(7u64, |x| *x += 1, |x| x % 10, Mode::Brent);
+        assert_eq!(
+            cycle,
+            Cycle {
+                start: 0,
+                period: 10
+            }
+        );
+        assert_eq!(cycle.reduce(123), 3);
+        assert_eq!(
+            Cycle {
+                start: 4,
+                period: 3
+            }
+            .reduce(2),
+            2
+        );
+    }
+
+    #[test]
+    fn first_common_occurrences() {
+        let occurrences = |times: &[usize], start, period| Occurrences {
+            times: times.to_vec(),
+            cycle: Cycle { start, period },
+        };
+        // At 3 and then every 4 steps, and at 0 and at 5 and then every 6 steps
+        let a = occurrences(&[3], 0, 4);
+        let b = occurrences(&[0, 5], 1, 6);
+        let first = first_common(&[a.clone(), b.clone()]);
+        assert_eq!(
+            first,
+            (0..100).find(|&step| a.contains(step) && b.contains(step))
+        );
+        assert_eq!(first, Some(11));
+        // Once before either cycle starts
+        let c = occurrences(&[2, 9, 11], 8, 4);
+        let d = occurrences(&[0, 2, 20], 5, 16);
+        assert_eq!(first_common(&[c, d.clone()]), Some(2));
+        // At odd steps and at even ones
+        let odd = occurrences(&[1], 0, 2);
+        let even = occurrences(&[0], 0, 4);
+        assert_eq!(first_common(&[odd, even]), None);
+        assert_eq!(first_common(&[d]), Some(0));
+    }
+}
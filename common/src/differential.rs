@@ -0,0 +1,112 @@
+//! Differential testing: runs a solution and a naive reference solution on many small
+//! generated inputs, and reports the first input where they disagree.
+use crate::gen::{self, Generator};
+use crate::Solver;
+use std::fmt;
+use std::ops::{Range, RangeInclusive};
+
+/// An input on which the solution and the reference give different answers. Errors
+/// count as answers, so that an input only one of them rejects is reported too.
+#[derive(Debug)]
+pub struct Mismatch {
+    pub size: usize,
+    pub seed: u64,
+    pub input: String,
+    pub solution: Result<String, String>,
+    pub reference: Result<String, String>,
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let show = |result: &Result<String, String>| match result {
+            Ok(answer) => answer.clone(),
+            Err(err) => format!("error: {}", err),
+        };
+        writeln!(
+            f,
+            "size {}, seed {}: the solution gives {} but the reference gives {}, on input:",
+            self.size,
+            self.seed,
+            show(&self.solution),
+            show(&self.reference)
+        )?;
+        write!(f, "{}", self.input)
+    }
+}
+
+impl std::error::Error for Mismatch {}
+
+fn answer(solver: Solver, input: &str) -> Result<String, String> {
+    solver(input).map_err(|err| format!("{:#}", err))
+}
+
+/// Compares `solution` with `reference` on the inputs `generate` makes for every size
+/// and seed. Sizes are tried from small to large, so the reported input is among the
+/// smallest where they disagree.
+pub fn compare(
+    solution: Solver,
+    reference: Solver,
+    generate: Generator,
+    sizes: RangeInclusive<usize>,
+    seeds: Range<u64>,
+) -> Result<(), Mismatch> {
+    for size in sizes {
+        for seed in seeds.clone() {
+            let input = generate(&mut gen::rng(seed), size);
+            let (solution, reference) = (answer(solution, &input), answer(reference, &input));
+            if solution != reference {
+                return Err(Mismatch {
+                    size,
+                    seed,
+                    input,
+                    solution,
+                    reference,
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
+/// [`compare`], panicking with the mismatch if there is one. For use in tests.
+pub fn assert_agree(
+    solution: Solver,
+    reference: Solver,
+    generate: Generator,
+    sizes: RangeInclusive<usize>,
+    seeds: Range<u64>,
+) {
+    if let Err(mismatch) = compare(solution, reference, generate, sizes, seeds) {
+        panic!("{}", mismatch);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn count_lines(input: &str) -> anyhow::Result<String> {
+        Ok(input.lines().count().to_string())
+    }
+
+    /// Wrong once there are more than three lines
+    fn count_lines_wrong(input: &str) -> anyhow::Result<String> {
+        Ok(input.lines().count().min(3).to_string())
+    }
+
+    fn lines(_: &mut gen::StdRng, size: usize) -> String {
+        "line\n".repeat(size)
+    }
+
+    #[test]
+    fn reports_the_smallest_mismatch() {
+        assert!(compare(count_lines, count_lines, lines, 0..=10, 0..3).is_ok());
+        let mismatch = compare(count_lines, count_lines_wrong, lines, 0..=10, 0..3).unwrap_err();
+        assert_eq!((mismatch.size, mismatch.seed), (4, 0));
+        assert_eq!(mismatch.solution, Ok("4".to_string()));
+        assert_eq!(mismatch.reference, Ok("3".to_string()));
+        assert!(mismatch
+            .to_string()
+            .starts_with("size 4, seed 0: the solution gives 4 but the reference gives 3"));
+    }
+}
@@ -0,0 +1,174 @@
+//! Graphviz exports of the days whose puzzles are graphs, to look at their structure with
+//! `dot`, `neato` and friends.
+use anyhow::{Context, Result};
+use std::fmt;
+use std::io::Write;
+use std::path::Path;
+
+/// Exports the graph of an input, with the solution highlighted when asked to.
+pub type Exporter = fn(&str, bool) -> Result<Dot>;
+
+/// Attributes of a node or an edge, such as its label
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Attributes(Vec<(&'static str, String)>);
+
+impl Attributes {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets any attribute, replacing an earlier value
+    pub fn set(mut self, key: &'static str, value: impl ToString) -> Self {
+        self.0.retain(|(k, _)| *k != key);
+        self.0.push((key, value.to_string()));
+        self
+    }
+
+    pub fn label(self, label: impl ToString) -> Self {
+        self.set("label", label)
+    }
+
+    pub fn shape(self, shape: &str) -> Self {
+        self.set("shape", shape)
+    }
+
+    /// The colour of the outline or line, by name or as `#rrggbb`
+    pub fn color(self, color: &str) -> Self {
+        self.set("color", color)
+    }
+
+    /// Fills a node with a colour
+    pub fn fill(self, color: &str) -> Self {
+        self.set("style", "filled").set("fillcolor", color)
+    }
+
+    /// Marks part of the solution: thick and red
+    pub fn highlight(self) -> Self {
+        self.color("red").set("penwidth", 3)
+    }
+}
+
+impl fmt::Display for Attributes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0.is_empty() {
+            return Ok(());
+        }
+        let attributes: Vec<String> = self
+            .0
+            .iter()
+            .map(|(key, value)| format!("{}={}", key, quote(value)))
+            .collect();
+        write!(f, " [{}]", attributes.join(", "))
+    }
+}
+
+/// A graph in the DOT language. Nodes are named by their ids, and edges may mention
+/// nodes that were never added, which then get the default look.
+#[derive(Clone, Debug)]
+pub struct Dot {
+    name: String,
+    directed: bool,
+    nodes: Vec<(String, Attributes)>,
+    edges: Vec<(String, String, Attributes)>,
+}
+
+impl Dot {
+    pub fn directed(name: &str) -> Self {
+        Self::new(name, true)
+    }
+
+    pub fn undirected(name: &str) -> Self {
+        Self::new(name, false)
+    }
+
+    fn new(name: &str, directed: bool) -> Self {
+        Self {
+            name: name.to_string(),
+            directed,
+            nodes: Vec::new(),
+            edges: Vec::new(),
+        }
+    }
+
+    pub fn node(&mut self, id: impl ToString, attributes: Attributes) {
+        self.nodes.push((id.to_string(), attributes));
+    }
+
+    pub fn edge(&mut self, from: impl ToString, to: impl ToString, attributes: Attributes) {
+        self.edges
+            .push((from.to_string(), to.to_string(), attributes));
+    }
+
+    /// Writes the graph to `path`, or to stdout for `-`
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if path == Path::new("-") {
+            return std::io::stdout()
+                .write_all(self.to_string().as_bytes())
+                .context("could not write to stdout");
+        }
+        std::fs::write(path, self.to_string())
+            .with_context(|| format!("could not write {}", path.display()))
+    }
+}
+
+impl fmt::Display for Dot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (kind, arrow) = match self.directed {
+            true => ("digraph", "->"),
+            false => ("graph", "--"),
+        };
+        writeln!(f, "{} {} {{", kind, quote(&self.name))?;
+        for (id, attributes) in &self.nodes {
+            writeln!(f, "    {}{};", quote(id), attributes)?;
+        }
+        for (from, to, attributes) in &self.edges {
+            writeln!(
+                f,
+                "    {} {} {}{};",
+                quote(from),
+                arrow,
+                quote(to),
+                attributes
+            )?;
+        }
+        writeln!(f, "}}")
+    }
+}
+
+/// A DOT string, which can hold anything once quoted
+fn quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn directed_graph() {
+        let mut dot = Dot::directed("day0");
+        dot.node("in", Attributes::new().shape("box").label("start"));
+        dot.node("A", Attributes::new().fill("green"));
+        dot.edge("in", "A", Attributes::new().label("x<5").highlight());
+        dot.edge("in", "R", Attributes::new());
+        assert_eq!(
+            dot.to_string(),
+            "digraph \"day0\" {\n    \
+             \"in\" [shape=\"box\", label=\"start\"];\n    \
+             \"A\" [style=\"filled\", fillcolor=\"green\"];\n    \
+             \"in\" -> \"A\" [label=\"x<5\", color=\"red\", penwidth=\"3\"];\n    \
+             \"in\" -> \"R\";\n\
+             }\n"
+        );
+    }
+
+    #[test]
+    fn quoting_and_undirected_edges() {
+        let mut dot = Dot::undirected("g");
+        dot.edge("a\"b", "c\\d", Attributes::new().label(7).label(8));
+        assert_eq!(
+            dot.to_string(),
+            "graph \"g\" {\n    \"a\\\"b\" -- \"c\\\\d\" [label=\"8\"];\n}\n"
+        );
+    }
+}
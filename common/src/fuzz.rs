@@ -0,0 +1,206 @@
+//! Fuzzing of the input parsers. Malformed input should give an error rather than a panic,
+//! so the parsers are fed mangled versions of known inputs, with lines cut short, bytes
+//! swapped for punctuation, digits or huge numbers, and pieces of other inputs spliced in,
+//! until one panics. The input that made it panic is then cut down as far as it goes.
+//!
+//! `aoc --fuzz RUNS` fuzzes every day from its bundled inputs, and the tests of the `aoc`
+//! crate fuzz every day for a few runs. Days whose parts do little more than read the input
+//! have no parser of their own, and are fuzzed through their parts.
+use crate::gen;
+use anyhow::Result;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::cell::{Cell, RefCell};
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Once;
+
+/// Parses a puzzle input without solving it.
+pub type Parser = fn(&str) -> Result<()>;
+
+/// An input on which a parser panics, and the panic with its location
+#[derive(Debug)]
+pub struct Crash {
+    pub input: String,
+    pub panic: String,
+}
+
+impl fmt::Display for Crash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} on input {:?}", self.panic, self.input)
+    }
+}
+
+impl std::error::Error for Crash {}
+
+thread_local! {
+    /// Whether this thread is checking an input, in which case panics are caught silently
+    static CHECKING: Cell<bool> = const { Cell::new(false) };
+    static PANIC: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Keeps the panics of [`check`] quiet, and every other panic as loud as before
+fn install_hook() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| match CHECKING.get() {
+            true => PANIC.set(Some(info.to_string())),
+            false => previous(info),
+        }));
+    });
+}
+
+/// Parses `input`, which may fail but may not panic
+pub fn check(parse: &dyn Fn(&str) -> Result<()>, input: &str) -> Result<(), Crash> {
+    install_hook();
+    CHECKING.set(true);
+    // Nothing is used after a panic but the panic itself
+    let result = panic::catch_unwind(AssertUnwindSafe(|| parse(input)));
+    CHECKING.set(false);
+    match result {
+        Ok(_) => Ok(()),
+        Err(_) => Err(Crash {
+            input: input.to_string(),
+            panic: PANIC.take().unwrap_or_default(),
+        }),
+    }
+}
+
+/// Text that often trips parsers up
+const PIECES: [&str; 23] = [
+    "",
+    " ",
+    "\n",
+    "\n\n",
+    ":",
+    ",",
+    "-",
+    "=",
+    "#",
+    ".",
+    "|",
+    "{",
+    "}",
+    "(",
+    ")",
+    "->",
+    "0",
+    "-1",
+    "65536",
+    "4294967296",
+    "99999999999999999999999",
+    "x",
+    "\u{e9}",
+];
+
+/// `input` with a few random changes
+fn mutate(rng: &mut gen::StdRng, input: &str, seeds: &[&str]) -> String {
+    let mut bytes = input.as_bytes().to_vec();
+    for _ in 0..rng.gen_range(1..=8) {
+        let at = rng.gen_range(0..=bytes.len());
+        let piece = PIECES.choose(rng).unwrap().as_bytes();
+        match rng.gen_range(0..6) {
+            0 => bytes.truncate(at),
+            1 => {
+                let end = rng.gen_range(at..=bytes.len().min(at + 16));
+                bytes.drain(at..end);
+            }
+            2 => {
+                let end = (at + 1).min(bytes.len());
+                bytes.splice(at..end, piece.iter().copied());
+            }
+            3 => {
+                bytes.splice(at..at, piece.iter().copied());
+            }
+            4 => bytes.insert(at, rng.gen()),
+            _ => {
+                let other = seeds.choose(rng).unwrap().as_bytes();
+                let start = rng.gen_range(0..=other.len());
+                let end = rng.gen_range(start..=other.len().min(start + 64));
+                bytes.splice(at..at, other[start..end].iter().copied());
+            }
+        }
+    }
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+/// The crash on the shortest input found by leaving out ever smaller pieces of it, as
+/// long as the parser still panics
+fn minimize(parse: &dyn Fn(&str) -> Result<()>, mut crash: Crash) -> Crash {
+    let mut chars: Vec<char> = crash.input.chars().collect();
+    let mut len = chars.len() / 2;
+    while len > 0 {
+        let mut start = 0;
+        while start < chars.len() {
+            let end = (start + len).min(chars.len());
+            let shorter: String = chars[..start].iter().chain(&chars[end..]).collect();
+            match check(parse, &shorter) {
+                Err(shorter) => {
+                    crash = shorter;
+                    chars.drain(start..end);
+                }
+                Ok(()) => start += len,
+            }
+        }
+        len /= 2;
+    }
+    crash
+}
+
+/// Parses the `seeds` and then `runs` random inputs made from them, and returns the
+/// first crash, cut down. The same `rng_seed` always tries the same inputs.
+pub fn fuzz(
+    parse: &dyn Fn(&str) -> Result<()>,
+    seeds: &[&str],
+    runs: usize,
+    rng_seed: u64,
+) -> Result<(), Crash> {
+    let mut rng = gen::rng(rng_seed);
+    for seed in seeds {
+        check(parse, seed).map_err(|crash| minimize(parse, crash))?;
+    }
+    for _ in 0..runs {
+        // Now and then some bytes that look like nothing at all
+        let input = match seeds.is_empty() || rng.gen_ratio(1, 10) {
+            true => {
+                let bytes: Vec<u8> = (0..rng.gen_range(0..64)).map(|_| rng.gen()).collect();
+                String::from_utf8_lossy(&bytes).into_owned()
+            }
+            false => {
+                let seed = seeds.choose(&mut rng).unwrap();
+                mutate(&mut rng, seed, seeds)
+            }
+        };
+        check(parse, &input).map_err(|crash| minimize(parse, crash))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::bail;
+
+    fn first_three(input: &str) -> Result<()> {
+        if input.is_empty() {
+            bail!("empty input");
+        }
+        let _ = &input[..3];
+        Ok(())
+    }
+
+    #[test]
+    fn finds_and_cuts_down_panics() {
+        let crash = fuzz(&first_three, &["abcdef"], 1000, 0).unwrap_err();
+        assert!(crash.panic.contains("fuzz.rs"), "{}", crash.panic);
+        assert_eq!(crash.input.chars().count(), 1);
+        assert!(check(&first_three, &crash.input).is_err());
+    }
+
+    #[test]
+    fn errors_are_fine() {
+        assert!(check(&first_three, "").is_ok());
+        assert!(fuzz(&|_| bail!("never"), &["abc"], 100, 0).is_ok());
+    }
+}
@@ -0,0 +1,116 @@
+//! Seeded random puzzle inputs, so that solutions can be tested and benchmarked on more
+//! than the one real input.
+//!
+//! Every day has a generator taking a random number generator and a size. What the size
+//! means depends on the day, but inputs grow with it, roughly linearly in the number of
+//! lines.
+use crate::geom::{Direction, UPos};
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use std::collections::HashSet;
+
+pub use rand::rngs::StdRng;
+
+/// Generates a random puzzle input of the given size.
+pub type Generator = fn(&mut StdRng, usize) -> String;
+
+/// The random number generator for a seed. It is the same on every platform, so a seed
+/// always gives the same input.
+pub fn rng(seed: u64) -> StdRng {
+    StdRng::seed_from_u64(seed)
+}
+
+/// `count` distinct names of `len` lowercase letters
+pub fn names(rng: &mut impl Rng, count: usize, len: usize) -> Vec<String> {
+    assert!(
+        (count as f64) <= 26f64.powi(len as i32) / 2.0,
+        "too few names of length {}",
+        len
+    );
+    let mut seen = HashSet::new();
+    let mut names = Vec::with_capacity(count);
+    while names.len() < count {
+        let name: String = (0..len)
+            .map(|_| rng.gen_range(b'a'..=b'z') as char)
+            .collect();
+        if seen.insert(name.clone()) {
+            names.push(name);
+        }
+    }
+    names
+}
+
+/// A random closed loop of unit steps that never touches itself, within the points
+/// `(0..=width, 0..=height)`. Returns where the loop starts and its steps, clockwise.
+///
+/// The loop is the outline of a random shape without holes, made of columns of cells
+/// that each overlap their neighbours.
+pub fn random_loop(rng: &mut impl Rng, width: usize, height: usize) -> (UPos, Vec<Direction>) {
+    assert!(width >= 1 && height >= 1);
+    // The cells of column `x` are the rows `tops[x]..bottoms[x]`
+    let mut tops = vec![rng.gen_range(0..height)];
+    let mut bottoms = vec![rng.gen_range(tops[0] + 1..=height)];
+    for x in 1..width {
+        let (top, bottom) = (tops[x - 1] as isize, bottoms[x - 1] as isize);
+        let new_top = (top + rng.gen_range(-2..=2)).clamp(0, bottom - 1);
+        let new_bottom =
+            (bottom + rng.gen_range(-2..=2)).clamp(new_top.max(top) + 1, height as isize);
+        tops.push(new_top as usize);
+        bottoms.push(new_bottom as usize);
+    }
+
+    let mut steps = Vec::new();
+    let vertical = |steps: &mut Vec<Direction>, from: usize, to: usize| {
+        let direction = if to > from {
+            Direction::Down
+        } else {
+            Direction::Up
+        };
+        steps.extend(std::iter::repeat_n(direction, from.abs_diff(to)));
+    };
+    let mut y = tops[0];
+    for &top in &tops {
+        vertical(&mut steps, y, top);
+        steps.push(Direction::Right);
+        y = top;
+    }
+    for &bottom in bottoms.iter().rev() {
+        vertical(&mut steps, y, bottom);
+        steps.push(Direction::Left);
+        y = bottom;
+    }
+    vertical(&mut steps, y, tops[0]);
+    (UPos::new(0, tops[0]), steps)
+}
+
+/// Shuffles the lines of an input, for puzzles where their order does not matter.
+pub fn shuffled_lines(rng: &mut impl Rng, mut lines: Vec<String>) -> String {
+    lines.shuffle(rng);
+    lines.iter().map(|line| format!("{}\n", line)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn loops_are_closed_and_simple() {
+        for seed in 0..50 {
+            let mut rng = rng(seed);
+            let (start, steps) = random_loop(&mut rng, 8, 6);
+            let mut pos = start;
+            let mut seen = HashSet::new();
+            for &step in &steps {
+                assert!(seen.insert(pos), "seed {}: {:?} visited twice", seed, pos);
+                pos = pos.step_within(step, 9, 7).unwrap();
+            }
+            assert_eq!(pos, start);
+        }
+    }
+
+    #[test]
+    fn names_are_distinct() {
+        let names = names(&mut rng(1), 100, 2);
+        assert_eq!(names.iter().collect::<HashSet<_>>().len(), 100);
+    }
+}
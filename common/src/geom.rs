@@ -0,0 +1,394 @@
+//! Directions and positions in two and three dimensions.
+//!
+//! As in [`Grid`](crate::grid::Grid), `y` grows downwards: `Up` decreases `y`.
+use std::fmt;
+use std::ops::{Add, AddAssign, Index, IndexMut, Mul, Neg, Sub};
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, PartialOrd, Ord)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// All directions, clockwise starting at `Up`.
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    pub fn turn_left(self) -> Self {
+        match self {
+            Direction::Up => Direction::Left,
+            Direction::Right => Direction::Up,
+            Direction::Down => Direction::Right,
+            Direction::Left => Direction::Down,
+        }
+    }
+
+    pub fn turn_right(self) -> Self {
+        match self {
+            Direction::Up => Direction::Right,
+            Direction::Right => Direction::Down,
+            Direction::Down => Direction::Left,
+            Direction::Left => Direction::Up,
+        }
+    }
+
+    pub fn reverse(self) -> Self {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Right => Direction::Left,
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+        }
+    }
+
+    pub fn is_horizontal(self) -> bool {
+        matches!(self, Direction::Left | Direction::Right)
+    }
+
+    /// The change in `(x, y)` of a single step.
+    pub fn offset(self) -> (isize, isize) {
+        match self {
+            Direction::Up => (0, -1),
+            Direction::Right => (1, 0),
+            Direction::Down => (0, 1),
+            Direction::Left => (-1, 0),
+        }
+    }
+}
+
+/// A position on an unbounded plane.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, PartialOrd, Ord)]
+pub struct Pos {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Pos {
+    pub const fn new(x: i64, y: i64) -> Self {
+        Self { x, y }
+    }
+
+    pub fn step(self, direction: Direction) -> Self {
+        self.step_n(direction, 1)
+    }
+
+    pub fn step_n(self, direction: Direction, n: i64) -> Self {
+        let (dx, dy) = direction.offset();
+        Self {
+            x: self.x + dx as i64 * n,
+            y: self.y + dy as i64 * n,
+        }
+    }
+
+    /// The four orthogonal neighbours, in the order of [`Direction::ALL`].
+    pub fn neighbours(self) -> [Pos; 4] {
+        Direction::ALL.map(|direction| self.step(direction))
+    }
+
+    pub fn manhattan(self, other: Pos) -> i64 {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+
+    /// The z component of the cross product of the two positions as vectors.
+    pub fn cross(self, other: Pos) -> i64 {
+        self.x * other.y - self.y * other.x
+    }
+}
+
+impl fmt::Debug for Pos {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl Add for Pos {
+    type Output = Pos;
+
+    fn add(self, other: Pos) -> Pos {
+        Pos::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl Sub for Pos {
+    type Output = Pos;
+
+    fn sub(self, other: Pos) -> Pos {
+        Pos::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl Mul<i64> for Pos {
+    type Output = Pos;
+
+    fn mul(self, factor: i64) -> Pos {
+        Pos::new(self.x * factor, self.y * factor)
+    }
+}
+
+/// A position that cannot go past the top or left edge, such as a cell of a grid.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, PartialOrd, Ord)]
+pub struct UPos {
+    pub x: usize,
+    pub y: usize,
+}
+
+impl UPos {
+    pub const fn new(x: usize, y: usize) -> Self {
+        Self { x, y }
+    }
+
+    /// Steps in `direction`, unless that would go below zero.
+    pub fn step(self, direction: Direction) -> Option<Self> {
+        let (dx, dy) = direction.offset();
+        Some(Self {
+            x: self.x.checked_add_signed(dx)?,
+            y: self.y.checked_add_signed(dy)?,
+        })
+    }
+
+    /// Steps in `direction`, unless that would leave a `width` by `height` area.
+    pub fn step_within(self, direction: Direction, width: usize, height: usize) -> Option<Self> {
+        self.step(direction)
+            .filter(|pos| pos.x < width && pos.y < height)
+    }
+
+    pub fn manhattan(self, other: UPos) -> usize {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    pub fn to_signed(self) -> Pos {
+        Pos::new(self.x as i64, self.y as i64)
+    }
+}
+
+impl fmt::Debug for UPos {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl From<(usize, usize)> for UPos {
+    fn from((x, y): (usize, usize)) -> Self {
+        Self { x, y }
+    }
+}
+
+impl From<UPos> for (usize, usize) {
+    fn from(pos: UPos) -> Self {
+        (pos.x, pos.y)
+    }
+}
+
+impl TryFrom<Pos> for UPos {
+    type Error = std::num::TryFromIntError;
+
+    fn try_from(pos: Pos) -> Result<Self, Self::Error> {
+        Ok(Self {
+            x: pos.x.try_into()?,
+            y: pos.y.try_into()?,
+        })
+    }
+}
+
+/// A position or vector in space, with integer or floating point coordinates.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, PartialOrd, Ord)]
+pub struct Pos3<T = i64> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T> Pos3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Self { x, y, z }
+    }
+
+    /// Drops the z coordinate.
+    pub fn xy(self) -> (T, T) {
+        (self.x, self.y)
+    }
+
+    pub fn map<U>(self, mut f: impl FnMut(T) -> U) -> Pos3<U> {
+        Pos3::new(f(self.x), f(self.y), f(self.z))
+    }
+}
+
+impl<T: Default> Pos3<T> {
+    pub fn zero() -> Self {
+        Self::default()
+    }
+}
+
+impl<T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T>> Pos3<T> {
+    pub fn dot(&self, other: &Self) -> T {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    /// The squared length.
+    pub fn norm2(&self) -> T {
+        self.dot(self)
+    }
+
+    pub fn cross(&self, other: &Self) -> Self {
+        Self {
+            x: self.y * other.z - self.z * other.y,
+            y: self.z * other.x - self.x * other.z,
+            z: self.x * other.y - self.y * other.x,
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Pos3<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Forward the formatter so that e.g. `{:.2?}` applies to every coordinate
+        write!(f, "(")?;
+        self.x.fmt(f)?;
+        write!(f, ", ")?;
+        self.y.fmt(f)?;
+        write!(f, ", ")?;
+        self.z.fmt(f)?;
+        write!(f, ")")
+    }
+}
+
+impl<T: Add<Output = T>> Add for Pos3<T> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(self.x + other.x, self.y + other.y, self.z + other.z)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Pos3<T> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Pos3<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.x, -self.y, -self.z)
+    }
+}
+
+impl<T: Copy + Add<Output = T>> AddAssign for Pos3<T> {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl<T: Copy + Mul<Output = T>> Mul<T> for Pos3<T> {
+    type Output = Self;
+
+    fn mul(self, factor: T) -> Self {
+        Self::new(self.x * factor, self.y * factor, self.z * factor)
+    }
+}
+
+impl Mul<Pos3<f64>> for f64 {
+    type Output = Pos3<f64>;
+
+    fn mul(self, other: Pos3<f64>) -> Pos3<f64> {
+        other * self
+    }
+}
+
+impl Mul<Pos3<i64>> for i64 {
+    type Output = Pos3<i64>;
+
+    fn mul(self, other: Pos3<i64>) -> Pos3<i64> {
+        other * self
+    }
+}
+
+impl<T> Index<usize> for Pos3<T> {
+    type Output = T;
+
+    fn index(&self, i: usize) -> &T {
+        match i {
+            0 => &self.x,
+            1 => &self.y,
+            2 => &self.z,
+            _ => panic!("index {} out of range for a 3D position", i),
+        }
+    }
+}
+
+impl<T> IndexMut<usize> for Pos3<T> {
+    fn index_mut(&mut self, i: usize) -> &mut T {
+        match i {
+            0 => &mut self.x,
+            1 => &mut self.y,
+            2 => &mut self.z,
+            _ => panic!("index {} out of range for a 3D position", i),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn turning() {
+        for direction in Direction::ALL {
+            assert_eq!(direction.turn_left().turn_right(), direction);
+            assert_eq!(direction.turn_right().turn_right(), direction.reverse());
+            assert_eq!(direction.reverse().reverse(), direction);
+            assert_ne!(
+                direction.is_horizontal(),
+                direction.turn_left().is_horizontal()
+            );
+        }
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+    }
+
+    #[test]
+    fn signed_steps() {
+        let pos = Pos::new(0, 0);
+        assert_eq!(pos.step(Direction::Up), Pos::new(0, -1));
+        assert_eq!(pos.step_n(Direction::Left, 3), Pos::new(-3, 0));
+        assert_eq!(
+            pos.neighbours()
+                .iter()
+                .map(|n| n.manhattan(pos))
+                .sum::<i64>(),
+            4
+        );
+    }
+
+    #[test]
+    fn unsigned_steps_are_checked() {
+        let corner = UPos::new(0, 0);
+        assert_eq!(corner.step(Direction::Up), None);
+        assert_eq!(corner.step(Direction::Left), None);
+        assert_eq!(corner.step(Direction::Down), Some(UPos::new(0, 1)));
+        assert_eq!(UPos::new(2, 1).step_within(Direction::Right, 3, 3), None);
+        assert_eq!(UPos::try_from(Pos::new(-1, 0)).ok(), None);
+        assert_eq!(UPos::try_from(Pos::new(4, 2)).ok(), Some(UPos::new(4, 2)));
+    }
+
+    #[test]
+    fn vectors() {
+        let x = Pos3::new(1, 0, 0);
+        let y = Pos3::new(0, 1, 0);
+        assert_eq!(x.cross(&y), Pos3::new(0, 0, 1));
+        assert_eq!((x + y).norm2(), 2);
+        assert_eq!(2 * (x - y), Pos3::new(2, -2, 0));
+        assert_eq!(
+            format!("{:.1?}", Pos3::new(1.0, 2.0, 3.0)),
+            "(1.0, 2.0, 3.0)"
+        );
+    }
+}
@@ -0,0 +1,468 @@
+//! Searches over graphs given by the neighbours of every node, which are worked out as
+//! the search goes, so the days only have to say how to get from one node to the next.
+use crate::instrument::{Batch, Search, TimedOut};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use std::marker::PhantomData;
+
+pub trait Graph {
+    type Node: Clone + Eq + Hash;
+
+    /// The nodes one step away from `node`, with the cost of that step
+    fn neighbours(&self, node: &Self::Node) -> Vec<(Self::Node, usize)>;
+}
+
+/// A graph whose neighbours are given by a closure. See [`from_fn`].
+pub struct FromFn<N, F> {
+    neighbours: F,
+    node: PhantomData<fn(&N)>,
+}
+
+/// The graph in which `neighbours(node)` are the nodes one step away from `node`, with
+/// the cost of that step.
+pub fn from_fn<N, F>(neighbours: F) -> FromFn<N, F>
+where
+    N: Clone + Eq + Hash,
+    F: Fn(&N) -> Vec<(N, usize)>,
+{
+    FromFn {
+        neighbours,
+        node: PhantomData,
+    }
+}
+
+impl<N, F> Graph for FromFn<N, F>
+where
+    N: Clone + Eq + Hash,
+    F: Fn(&N) -> Vec<(N, usize)>,
+{
+    type Node = N;
+
+    fn neighbours(&self, node: &N) -> Vec<(N, usize)> {
+        (self.neighbours)(node)
+    }
+}
+
+/// The cheapest paths found by a search, to every node it reached
+#[derive(Debug)]
+pub struct Paths<N> {
+    /// The nodes in the order they were reached
+    nodes: Vec<N>,
+    index: HashMap<N, usize>,
+    costs: Vec<usize>,
+    /// The node before each one on its path, `None` for the start nodes
+    previous: Vec<Option<usize>>,
+}
+
+impl<N: Clone + Eq + Hash> Paths<N> {
+    fn new() -> Self {
+        Self {
+            nodes: Vec::new(),
+            index: HashMap::new(),
+            costs: Vec::new(),
+            previous: Vec::new(),
+        }
+    }
+
+    /// Records the path to `node` through `previous` if it is the first or the cheapest
+    /// so far, and returns the index of the node if so.
+    fn relax(&mut self, node: N, cost: usize, previous: Option<usize>) -> Option<usize> {
+        match self.index.get(&node) {
+            Some(&i) if self.costs[i] <= cost => None,
+            Some(&i) => {
+                self.costs[i] = cost;
+                self.previous[i] = previous;
+                Some(i)
+            }
+            None => {
+                let i = self.nodes.len();
+                self.index.insert(node.clone(), i);
+                self.nodes.push(node);
+                self.costs.push(cost);
+                self.previous.push(previous);
+                Some(i)
+            }
+        }
+    }
+
+    fn path_to_index(&self, mut i: usize) -> Vec<N> {
+        let mut path = vec![self.nodes[i].clone()];
+        while let Some(previous) = self.previous[i] {
+            path.push(self.nodes[previous].clone());
+            i = previous;
+        }
+        path.reverse();
+        path
+    }
+
+    /// The cost of the cheapest path to `node`, if it was reached
+    pub fn cost(&self, node: &N) -> Option<usize> {
+        self.index.get(node).map(|&i| self.costs[i])
+    }
+
+    /// The cheapest path from a start node to `node`, both included
+    pub fn path(&self, node: &N) -> Option<Vec<N>> {
+        self.index.get(node).map(|&i| self.path_to_index(i))
+    }
+
+    pub fn contains(&self, node: &N) -> bool {
+        self.index.contains_key(node)
+    }
+
+    /// The nodes that were reached, in the order they were reached
+    pub fn nodes(&self) -> &[N] {
+        &self.nodes
+    }
+
+    /// Every node that was reached, with the cost of getting there
+    pub fn iter(&self) -> impl Iterator<Item = (&N, usize)> {
+        self.nodes.iter().zip(self.costs.iter().copied())
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+}
+
+/// Breadth-first search from all `starts` at once, where every step costs one whatever
+/// the graph says.
+pub fn bfs<G: Graph>(graph: &G, starts: impl IntoIterator<Item = G::Node>) -> Paths<G::Node> {
+    let mut paths = Paths::new();
+    let mut queue: VecDeque<usize> = starts
+        .into_iter()
+        .filter_map(|start| paths.relax(start, 0, None))
+        .collect();
+    while let Some(i) = queue.pop_front() {
+        let cost = paths.costs[i] + 1;
+        for (next, _) in graph.neighbours(&paths.nodes[i]) {
+            if !paths.contains(&next) {
+                queue.extend(paths.relax(next, cost, Some(i)));
+            }
+        }
+    }
+    paths
+}
+
+/// The cheapest paths to every node that can be reached from the `starts`, which come
+/// with the cost of getting to them.
+pub fn dijkstra<G: Graph>(
+    graph: &G,
+    starts: impl IntoIterator<Item = (G::Node, usize)>,
+) -> Paths<G::Node> {
+    search(graph, starts, |_| false, |_| 0, None)
+        .expect("searches without a time limit run to the end")
+        .0
+}
+
+/// The length or cost of a path, and its nodes
+pub type WeightedPath<N> = (usize, Vec<N>);
+
+/// The cost of the cheapest path from the `starts` to a node where `is_goal` holds, and
+/// the path itself. The `heuristic` guesses the cost from a node to the nearest goal, and
+/// must never guess too high, nor drop by more than the cost of a step. The `search`
+/// counts the expanded nodes, and the lowest cost a goal can still have is its best
+/// answer so far.
+pub fn a_star<G: Graph>(
+    graph: &G,
+    starts: impl IntoIterator<Item = (G::Node, usize)>,
+    is_goal: impl Fn(&G::Node) -> bool,
+    heuristic: impl Fn(&G::Node) -> usize,
+    search: &Search,
+) -> Result<Option<WeightedPath<G::Node>>, TimedOut> {
+    let (paths, goal) = self::search(graph, starts, is_goal, heuristic, Some(search))?;
+    Ok(goal.map(|goal| (paths.costs[goal], paths.path_to_index(goal))))
+}
+
+/// Expands the nodes in order of their cost plus the heuristic, until the first goal
+fn search<G: Graph>(
+    graph: &G,
+    starts: impl IntoIterator<Item = (G::Node, usize)>,
+    is_goal: impl Fn(&G::Node) -> bool,
+    heuristic: impl Fn(&G::Node) -> usize,
+    instrument: Option<&Search>,
+) -> Result<(Paths<G::Node>, Option<usize>), TimedOut> {
+    let mut paths = Paths::new();
+    let mut queue = BinaryHeap::new();
+    for (start, cost) in starts {
+        let estimate = cost + heuristic(&start);
+        if let Some(i) = paths.relax(start, cost, None) {
+            queue.push(Reverse((estimate, i)));
+        }
+    }
+
+    let mut done = Vec::new();
+    let mut count = instrument.map(Batch::new);
+    let mut bound = 0;
+    while let Some(Reverse((estimate, i))) = queue.pop() {
+        done.resize(paths.len(), false);
+        if std::mem::replace(&mut done[i], true) {
+            continue;
+        }
+        if let Some(count) = &mut count {
+            count.expand()?;
+            count.queue_len(queue.len() + 1);
+        }
+        // No goal is closer than the estimates of the nodes still to expand
+        if let Some(search) = instrument.filter(|_| estimate > bound) {
+            bound = estimate;
+            search.improve(format!("at least {}", bound));
+        }
+        if is_goal(&paths.nodes[i]) {
+            return Ok((paths, Some(i)));
+        }
+        for (next, step) in graph.neighbours(&paths.nodes[i]) {
+            let cost = paths.costs[i] + step;
+            let estimate = cost + heuristic(&next);
+            if let Some(j) = paths.relax(next, cost, Some(i)) {
+                queue.push(Reverse((estimate, j)));
+            }
+        }
+    }
+    Ok((paths, None))
+}
+
+/// Splits the `nodes` into groups that are connected to each other, for graphs in which
+/// every edge goes both ways. Nodes reached from the `nodes` are included as well.
+pub fn connected_components<G: Graph>(
+    graph: &G,
+    nodes: impl IntoIterator<Item = G::Node>,
+) -> Vec<Vec<G::Node>> {
+    let mut seen = HashSet::new();
+    let mut components = Vec::new();
+    for node in nodes {
+        if seen.contains(&node) {
+            continue;
+        }
+        let component = bfs(graph, [node]).nodes;
+        seen.extend(component.iter().cloned());
+        components.push(component);
+    }
+    components
+}
+
+/// The longest path from `start` to `end` that never visits a node twice, with its
+/// length and its nodes, or `None` if there is no path at all. The graph has at most 64
+/// nodes, numbered from 0, and `edges[node]` lists the `(neighbour, length)` of its edges.
+///
+/// This tries every path, so it only works for small graphs, but it keeps the visited
+/// nodes in a bitmask to make that as quick as it gets. When a single node leads into
+/// `end`, paths that reach it go straight on to `end`, as they could not come back. Every
+/// path that is longer than those before it is recorded in `search`, so a search that
+/// runs out of time still tells the longest path it found.
+pub fn longest_path(
+    edges: &[Vec<(usize, usize)>],
+    start: usize,
+    end: usize,
+    search: &Search,
+) -> Result<Option<WeightedPath<usize>>, TimedOut> {
+    assert!(edges.len() <= 64, "at most 64 nodes fit in the bitmask");
+    let mut into_end =
+        (0..edges.len()).filter(|&node| edges[node].iter().any(|&(next, _)| next == end));
+    let last = match (into_end.next(), into_end.next()) {
+        (Some(last), None) => Some(last),
+        _ => None,
+    };
+    let mut walk = Walk {
+        edges,
+        end,
+        last,
+        search,
+        count: Batch::new(search),
+        path: vec![start],
+        longest: None,
+    };
+    walk.extend(start, 1 << start, 0)?;
+    Ok(walk.longest)
+}
+
+/// A depth-first walk through every path of [`longest_path`]
+struct Walk<'a> {
+    edges: &'a [Vec<(usize, usize)>],
+    end: usize,
+    /// The only node with an edge to the end, if there is just one
+    last: Option<usize>,
+    search: &'a Search,
+    count: Batch<'a>,
+    /// The nodes from the start to the current one
+    path: Vec<usize>,
+    longest: Option<(usize, Vec<usize>)>,
+}
+
+impl Walk<'_> {
+    /// Walks on from `node`, `length` from the start, avoiding the `visited` nodes
+    fn extend(&mut self, node: usize, visited: u64, length: usize) -> Result<(), TimedOut> {
+        self.count.expand()?;
+        if node == self.end {
+            if self
+                .longest
+                .as_ref()
+                .is_none_or(|&(longest, _)| length > longest)
+            {
+                self.search.improve(length);
+                self.longest = Some((length, self.path.clone()));
+            }
+            return Ok(());
+        }
+        let edges = self.edges;
+        for &(next, step) in &edges[node] {
+            // Leaving the only way into the end for anywhere else leads nowhere
+            if visited & 1 << next == 0 && (Some(node) != self.last || next == self.end) {
+                self.path.push(next);
+                self.extend(next, visited | 1 << next, length + step)?;
+                self.path.pop();
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A grid of `width` by `height` points, where stepping onto `(x, y)` costs `x + 1`
+    fn grid(width: i64, height: i64) -> impl Graph<Node = (i64, i64)> {
+        from_fn(move |&(x, y): &(i64, i64)| {
+            [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)]
+                .into_iter()
+                .filter(|&(x, y)| (0..width).contains(&x) && (0..height).contains(&y))
+                .map(|(x, y)| ((x, y), x as usize + 1))
+                .collect()
+        })
+    }
+
+    #[test]
+    fn bfs_counts_steps() {
+        let paths = bfs(&grid(4, 3), [(0, 0)]);
+        assert_eq!(paths.len(), 12);
+        assert_eq!(paths.cost(&(3, 2)), Some(5));
+        let path = paths.path(&(3, 2)).unwrap();
+        assert_eq!(path.len(), 6);
+        assert_eq!((path[0], path[5]), ((0, 0), (3, 2)));
+        assert_eq!(paths.cost(&(4, 0)), None);
+    }
+
+    #[test]
+    fn dijkstra_takes_the_cheap_column() {
+        // Going down the first column costs 1 per step, then across 2 + 3 + 4
+        let paths = dijkstra(&grid(4, 3), [((0, 0), 0)]);
+        assert_eq!(paths.cost(&(3, 2)), Some(11));
+        assert_eq!(
+            paths.path(&(3, 2)).unwrap(),
+            [(0, 0), (0, 1), (0, 2), (1, 2), (2, 2), (3, 2)]
+        );
+        assert_eq!(paths.cost(&(0, 0)), Some(0));
+    }
+
+    #[test]
+    fn a_star_agrees_with_dijkstra() {
+        let graph = grid(6, 5);
+        let paths = dijkstra(&graph, [((2, 1), 3)]);
+        for goal in [(0, 0), (5, 4), (2, 1), (5, 0)] {
+            // Every step costs at least 1
+            let distance = |&(x, y): &(i64, i64)| x.abs_diff(goal.0) + y.abs_diff(goal.1);
+            let (cost, path) = a_star(
+                &graph,
+                [((2, 1), 3)],
+                |&node| node == goal,
+                |node| distance(node) as usize,
+                &Search::with_limit("a*", None),
+            )
+            .unwrap()
+            .unwrap();
+            assert_eq!(Some(cost), paths.cost(&goal));
+            assert_eq!(path.first(), Some(&(2, 1)));
+            assert_eq!(path.last(), Some(&goal));
+        }
+        let search = Search::with_limit("a*", None);
+        let none = a_star(
+            &graph,
+            [((0, 0), 0)],
+            |&node| node == (9, 9),
+            |_| 0,
+            &search,
+        );
+        assert_eq!(none.unwrap(), None);
+        assert_eq!(search.stats().expanded, 30);
+    }
+
+    #[test]
+    fn components_of_two_rings() {
+        let graph = from_fn(|&node: &usize| {
+            let (ring, i) = (node / 5, node % 5);
+            vec![(ring * 5 + (i + 1) % 5, 1), (ring * 5 + (i + 4) % 5, 1)]
+        });
+        let mut components = connected_components(&graph, [7, 0, 3, 9]);
+        for component in &mut components {
+            component.sort();
+        }
+        assert_eq!(components, [vec![5, 6, 7, 8, 9], vec![0, 1, 2, 3, 4]]);
+    }
+
+    #[test]
+    fn longest_path_avoids_revisits() {
+        //   1
+        //  / \
+        // 0 - 2 - 3, with a long edge 0-2
+        let edges = vec![
+            vec![(1, 1), (2, 5)],
+            vec![(0, 1), (2, 1)],
+            vec![(0, 5), (1, 1), (3, 1)],
+            vec![(2, 1)],
+        ];
+        let longest = |edges: &[Vec<(usize, usize)>], start, end| {
+            longest_path(edges, start, end, &Search::with_limit("hike", None)).unwrap()
+        };
+        assert_eq!(longest(&edges, 0, 3), Some((6, vec![0, 2, 3])));
+        assert_eq!(longest(&edges, 1, 3), Some((7, vec![1, 0, 2, 3])));
+        assert_eq!(longest(&edges, 3, 3), Some((0, vec![3])));
+        assert_eq!(longest(&[vec![], vec![]], 0, 1), None);
+    }
+
+    #[test]
+    fn longest_path_goes_straight_to_the_end_from_the_only_way_in() {
+        // 0, 1 and 2 all connect, and only 3 leads on to the end, 4
+        let mut edges = vec![
+            vec![(1, 1), (2, 1)],
+            vec![(0, 1), (2, 1), (3, 1)],
+            vec![(0, 1), (1, 1), (3, 1)],
+            vec![(1, 1), (2, 1), (4, 1)],
+            vec![(3, 1)],
+        ];
+        let walk = |edges: &[Vec<(usize, usize)>]| {
+            let search = Search::with_limit("hike", None);
+            let longest = longest_path(edges, 0, 4, &search).unwrap();
+            (longest, search.stats().expanded)
+        };
+        let (longest, pruned) = walk(&edges);
+        assert_eq!(longest, Some((4, vec![0, 1, 2, 3, 4])));
+        // Node 5 leads into the end as well, without changing the paths from 0, so every
+        // path through 3 has to be tried
+        edges.push(vec![(4, 1)]);
+        let (longest_without, unpruned) = walk(&edges);
+        assert_eq!(longest_without, longest);
+        assert_eq!((pruned, unpruned), (13, 15));
+    }
+
+    #[test]
+    fn longest_path_gives_up_with_the_longest_so_far() {
+        // Every path through 20 nodes that all connect takes far too long
+        let edges: Vec<Vec<(usize, usize)>> = (0..20)
+            .map(|node| {
+                (0..20)
+                    .filter(|&next| next != node)
+                    .map(|next| (next, 1))
+                    .collect()
+            })
+            .collect();
+        let search = Search::with_limit("hike", Some(std::time::Duration::from_millis(10)));
+        let timed_out = longest_path(&edges, 0, 19, &search).unwrap_err();
+        let best: usize = timed_out.best.unwrap().parse().unwrap();
+        assert!(best >= 18);
+    }
+}
@@ -0,0 +1,326 @@
+//! A dense two-dimensional grid, as found in most of the puzzle inputs.
+//!
+//! Cells are addressed by `(x, y)`, with `x` the column and `y` the row counted from the
+//! top.
+use crate::geom::{Direction, UPos};
+use crate::parse::{self, ParseError};
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    /// Row-major storage
+    cells: Vec<T>,
+}
+
+/// Offsets of the neighbours to the north, east, south and west.
+const OFFSETS4: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+/// Offsets of the eight surrounding cells, clockwise starting at the north-west.
+const OFFSETS8: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+];
+
+impl<T> Grid<T> {
+    /// Builds a grid from its cells in row-major order.
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(
+            cells.len(),
+            width * height,
+            "a {}x{} grid needs {} cells",
+            width,
+            height,
+            width * height
+        );
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Parses one row per line, mapping every character to a cell. Fails on characters
+    /// the mapping rejects, on rows of different lengths and on empty rows.
+    pub fn parse(input: &str, mut cell: impl FnMut(char) -> Option<T>) -> Result<Self, ParseError> {
+        let mut cells = Vec::new();
+        let mut width = None;
+        let mut height = 0;
+        for line in parse::lines(input) {
+            let mut row_len = 0;
+            for (i, c) in line.text.char_indices() {
+                let value = cell(c)
+                    .ok_or_else(|| line.invalid(&line.text[i..i + c.len_utf8()], "a grid tile"))?;
+                cells.push(value);
+                row_len += 1;
+            }
+            match width {
+                None if row_len == 0 => return Err(line.missing("a grid tile")),
+                None => width = Some(row_len),
+                Some(width) if row_len < width => {
+                    return Err(line.missing(format!("a row of {} tiles", width)))
+                }
+                Some(width) if row_len > width => {
+                    let (end, _) = line.text.char_indices().nth(width).unwrap();
+                    return Err(line.invalid(&line.text[end..], "the end of the row"));
+                }
+                Some(_) => {}
+            }
+            height += 1;
+        }
+        let width = width.ok_or_else(|| ParseError::incomplete("grid"))?;
+
+        Ok(Self::new(width, height, cells))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, (x, y): (usize, usize)) -> Option<&T> {
+        self.index_of(x, y).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, (x, y): (usize, usize)) -> Option<&mut T> {
+        self.index_of(x, y).map(|i| &mut self.cells[i])
+    }
+
+    /// Swaps the contents of two cells.
+    pub fn swap(&mut self, a: (usize, usize), b: (usize, usize)) {
+        let i = self.index_of(a.0, a.1).expect("cell out of bounds");
+        let j = self.index_of(b.0, b.1).expect("cell out of bounds");
+        self.cells.swap(i, j);
+    }
+
+    /// Applies a signed offset to a position, if the result lies inside the grid.
+    pub fn offset(
+        &self,
+        (x, y): (usize, usize),
+        (dx, dy): (isize, isize),
+    ) -> Option<(usize, usize)> {
+        let x = x.checked_add_signed(dx)?;
+        let y = y.checked_add_signed(dy)?;
+        (x < self.width && y < self.height).then_some((x, y))
+    }
+
+    /// Takes one step in `direction`, if that stays inside the grid.
+    pub fn step(&self, pos: (usize, usize), direction: Direction) -> Option<(usize, usize)> {
+        self.offset(pos, direction.offset())
+    }
+
+    /// The up to four orthogonal neighbours inside the grid.
+    pub fn neighbours4(&self, pos: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        OFFSETS4
+            .iter()
+            .filter_map(move |&offset| self.offset(pos, offset))
+    }
+
+    /// The up to eight neighbours inside the grid, diagonals included.
+    pub fn neighbours8(&self, pos: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        OFFSETS8
+            .iter()
+            .filter_map(move |&offset| self.offset(pos, offset))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width)
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {} out of bounds", x);
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// All positions, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// All cells with their positions, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// Position of the first cell equal to `value`, row by row.
+    pub fn find(&self, value: &T) -> Option<(usize, usize)>
+    where
+        T: PartialEq,
+    {
+        self.find_all(value).next()
+    }
+
+    /// Positions of all cells equal to `value`, row by row.
+    pub fn find_all<'a>(&'a self, value: &'a T) -> impl Iterator<Item = (usize, usize)> + 'a
+    where
+        T: PartialEq,
+    {
+        self.iter()
+            .filter(move |(_, cell)| *cell == value)
+            .map(|(pos, _)| pos)
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid::new(self.width, self.height, self.cells.iter().map(f).collect())
+    }
+
+    fn index_of(&self, x: usize, y: usize) -> Option<usize> {
+        (x < self.width && y < self.height).then_some(y * self.width + x)
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn filled(width: usize, height: usize, value: T) -> Self {
+        Self::new(width, height, vec![value; width * height])
+    }
+
+    /// Mirrors the grid along its main diagonal, so that rows become columns.
+    pub fn transpose(&self) -> Self {
+        let cells = self.columns().flatten().cloned().collect();
+        Self::new(self.height, self.width, cells)
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        let i = self
+            .index_of(x, y)
+            .unwrap_or_else(|| panic!("({}, {}) is outside the grid", x, y));
+        &self.cells[i]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        let i = self
+            .index_of(x, y)
+            .unwrap_or_else(|| panic!("({}, {}) is outside the grid", x, y));
+        &mut self.cells[i]
+    }
+}
+
+impl<T> Index<UPos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: UPos) -> &T {
+        &self[(pos.x, pos.y)]
+    }
+}
+
+impl<T> IndexMut<UPos> for Grid<T> {
+    fn index_mut(&mut self, pos: UPos) -> &mut T {
+        &mut self[(pos.x, pos.y)]
+    }
+}
+
+/// Writes the grid back as text, one line per row.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEXT: &str = "#..\n.#.\n..#\n#.#\n";
+
+    fn grid() -> Grid<char> {
+        Grid::parse(TEXT, Some).unwrap()
+    }
+
+    #[test]
+    fn parse_and_display_round_trip() {
+        let grid = grid();
+        assert_eq!((grid.width(), grid.height()), (3, 4));
+        assert_eq!(grid[(2, 3)], '#');
+        assert_eq!(grid.to_string(), TEXT);
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert!(Grid::parse("#.\n#", Some).is_err());
+        assert!(Grid::parse("", Some).is_err());
+        assert!(Grid::parse("\n", Some).is_err());
+        let err = Grid::parse("..\n.x", |c| (c == '.').then_some(())).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 2: expected a grid tile, found \"x\""
+        );
+        let err = Grid::parse("..\n....\n.", Some).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 3: expected the end of the row, found \"..\""
+        );
+        let err = Grid::parse("..\n.", Some).unwrap_err();
+        assert!(matches!(err, ParseError::Missing { line: 2, .. }));
+    }
+
+    #[test]
+    fn neighbours_stay_in_bounds() {
+        let grid = grid();
+        assert_eq!(
+            grid.neighbours4((0, 0)).collect::<Vec<_>>(),
+            vec![(1, 0), (0, 1)]
+        );
+        assert_eq!(grid.neighbours4((1, 1)).count(), 4);
+        assert_eq!(grid.neighbours8((2, 3)).count(), 3);
+        assert_eq!(grid.neighbours8((1, 2)).count(), 8);
+    }
+
+    #[test]
+    fn rows_columns_and_transpose() {
+        let grid = grid();
+        assert_eq!(grid.row(3), &['#', '.', '#']);
+        assert_eq!(grid.column(2).collect::<String>(), "..##");
+        let transposed = grid.transpose();
+        assert_eq!(transposed.to_string(), "#..#\n.#..\n..##\n");
+        assert_eq!(transposed.transpose(), grid);
+        assert_eq!(
+            grid.columns()
+                .map(|c| c.collect::<String>())
+                .collect::<Vec<_>>(),
+            transposed
+                .rows()
+                .map(|r| r.iter().collect::<String>())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn find_cells() {
+        let grid = grid();
+        assert_eq!(grid.find(&'#'), Some((0, 0)));
+        assert_eq!(grid.find_all(&'#').count(), 5);
+        assert_eq!(grid.find(&'S'), None);
+    }
+}
@@ -0,0 +1,194 @@
+//! Where a day reads its puzzle input from.
+use crate::{gen, Day};
+use anyhow::{anyhow, Context, Result};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum InputSource {
+    /// The day's bundled `input.txt`
+    Default,
+    File(PathBuf),
+    Stdin,
+    /// A bundled example: `example.txt`, or `exampleN.txt` for a given `N`
+    Example(Option<u8>),
+    /// An input made by the day's generator, see [`crate::gen`]
+    Generated {
+        seed: u64,
+        size: usize,
+    },
+}
+
+impl InputSource {
+    /// Interprets an input path and an `--example` flag, where the path `-` means stdin.
+    pub fn new(input: Option<PathBuf>, example: Option<Option<u8>>) -> Self {
+        match (input, example) {
+            (_, Some(number)) => InputSource::Example(number),
+            (Some(path), None) if path == Path::new("-") => InputSource::Stdin,
+            (Some(path), None) => InputSource::File(path),
+            (None, None) => InputSource::Default,
+        }
+    }
+}
+
+/// Command line arguments selecting the input of a single day.
+#[derive(clap::Args, Debug, Default)]
+pub struct InputArgs {
+    /// Input file, or `-` to read from stdin [default: the day's bundled input.txt]
+    pub input: Option<PathBuf>,
+
+    /// Use the bundled example instead: example.txt, or exampleN.txt when N is given
+    #[arg(long, value_name = "N", num_args = 0..=1, conflicts_with = "input")]
+    pub example: Option<Option<u8>>,
+
+    /// Generate a random input from this seed instead
+    #[arg(long, conflicts_with_all = ["input", "example"])]
+    pub seed: Option<u64>,
+
+    /// Size of the generated input, roughly its number of lines
+    #[arg(long, value_name = "N", default_value_t = DEFAULT_SIZE, requires = "seed")]
+    pub size: usize,
+}
+
+/// Size of generated inputs when none is given
+pub const DEFAULT_SIZE: usize = 20;
+
+impl InputArgs {
+    pub fn source(&self) -> InputSource {
+        match self.seed {
+            Some(seed) => InputSource::Generated {
+                seed,
+                size: self.size,
+            },
+            None => InputSource::new(self.input.clone(), self.example),
+        }
+    }
+}
+
+impl Day {
+    /// The file `source` refers to, or `None` for stdin and generated inputs. Bundled inputs are found
+    /// relative to the day's crate, so this does not depend on the working directory.
+    pub fn input_path(&self, source: &InputSource) -> Option<PathBuf> {
+        let src = Path::new(self.dir).join("src");
+        match source {
+            InputSource::Default => Some(src.join("input.txt")),
+            InputSource::File(path) => Some(path.clone()),
+            InputSource::Stdin | InputSource::Generated { .. } => None,
+            InputSource::Example(None) => Some(src.join("example.txt")),
+            InputSource::Example(Some(n)) => {
+                let path = src.join(format!("example{}.txt", n));
+                // The first example usually has no number
+                if *n == 1 && !path.exists() {
+                    Some(src.join("example.txt"))
+                } else {
+                    Some(path)
+                }
+            }
+        }
+    }
+
+    pub fn load_input(&self, source: &InputSource) -> Result<String> {
+        if let &InputSource::Generated { seed, size } = source {
+            let generate = self
+                .generate
+                .ok_or_else(|| anyhow!("day {} has no input generator", self.number))?;
+            return Ok(generate(&mut gen::rng(seed), size));
+        }
+        match self.input_path(source) {
+            Some(path) => self.read_input(&path),
+            None => {
+                let mut input = String::new();
+                std::io::stdin()
+                    .read_to_string(&mut input)
+                    .with_context(|| format!("day {}: could not read stdin", self.number))?;
+                Ok(input)
+            }
+        }
+    }
+
+    /// Every input that comes with the day: the examples and the day's own input, in
+    /// order of their file names
+    pub fn bundled_inputs(&self) -> Result<Vec<String>> {
+        let src = Path::new(self.dir).join("src");
+        let entries = std::fs::read_dir(&src)
+            .with_context(|| format!("day {}: could not list {}", self.number, src.display()))?;
+        let mut paths = Vec::new();
+        for entry in entries {
+            let path = entry?.path();
+            if path.extension().is_some_and(|extension| extension == "txt") {
+                paths.push(path);
+            }
+        }
+        paths.sort();
+        paths.iter().map(|path| self.read_input(path)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day() -> Day {
+        Day {
+            number: 1,
+            dir: "/aoc/day1",
+            part1: None,
+            part2: None,
+            generate: None,
+            render: None,
+            dot: None,
+            parse: None,
+            params: &[],
+        }
+    }
+
+    #[test]
+    fn sources() {
+        assert_eq!(InputSource::new(None, None), InputSource::Default);
+        assert_eq!(InputSource::new(Some("-".into()), None), InputSource::Stdin);
+        assert_eq!(
+            InputSource::new(None, Some(Some(2))),
+            InputSource::Example(Some(2))
+        );
+    }
+
+    #[test]
+    fn paths_are_relative_to_the_crate() {
+        let day = day();
+        assert_eq!(
+            day.input_path(&InputSource::Default),
+            Some("/aoc/day1/src/input.txt".into())
+        );
+        assert_eq!(
+            day.input_path(&InputSource::Example(None)),
+            Some("/aoc/day1/src/example.txt".into())
+        );
+        assert_eq!(
+            day.input_path(&InputSource::Example(Some(1))),
+            Some("/aoc/day1/src/example.txt".into())
+        );
+        assert_eq!(
+            day.input_path(&InputSource::Example(Some(2))),
+            Some("/aoc/day1/src/example2.txt".into())
+        );
+        assert_eq!(
+            day.input_path(&InputSource::File("in.txt".into())),
+            Some("in.txt".into())
+        );
+        assert_eq!(day.input_path(&InputSource::Stdin), None);
+    }
+
+    #[test]
+    fn generated_inputs_need_a_generator() {
+        let mut day = day();
+        let source = InputSource::Generated { seed: 7, size: 3 };
+        assert!(day.load_input(&source).is_err());
+        day.generate = Some(|rng, size| {
+            use rand::Rng;
+            format!("{} {}\n", size, rng.gen_range(0..1000))
+        });
+        let input = day.load_input(&source).unwrap();
+        assert!(input.starts_with("3 "));
+        assert_eq!(day.load_input(&source).unwrap(), input);
+    }
+}
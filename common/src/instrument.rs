@@ -0,0 +1,317 @@
+//! Instrumentation of the long searches: how many states they expand, how often their
+//! caches hit or miss and how long their queues get. With `-v` they report their progress
+//! every second, and with a time limit they give up once it has passed, with the best
+//! answer they found so far and their statistics.
+//!
+//! The counters are atomic, so that the units of a search that runs on several threads,
+//! see [`crate::parallel`], count towards the same totals and stop together.
+use anyhow::{bail, Result};
+use log::{debug, info};
+use std::fmt;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering::Relaxed};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+/// How often the clock is read, in expanded states
+const CHECK_EVERY: u64 = 1 << 12;
+
+/// How many states a [`Batch`] counts before it adds them to the search
+const BATCH_SIZE: u64 = 1 << 8;
+
+static TIME_LIMIT: OnceLock<Duration> = OnceLock::new();
+
+/// Makes every search that starts from now on give up after `seconds`. There is one time
+/// limit per process.
+pub fn set_time_limit(seconds: f64) -> Result<()> {
+    let Ok(limit) = Duration::try_from_secs_f64(seconds) else {
+        bail!("{} is not a time limit in seconds", seconds);
+    };
+    if TIME_LIMIT.set(limit).is_err() {
+        bail!("the time limit is already set");
+    }
+    Ok(())
+}
+
+/// What a search did so far
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Stats {
+    pub expanded: u64,
+    pub cache_hits: u64,
+    pub cache_misses: u64,
+    pub queue_peak: usize,
+    pub elapsed: Duration,
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} states expanded", self.expanded)?;
+        if self.cache_hits + self.cache_misses > 0 {
+            write!(
+                f,
+                ", {} cache hits and {} misses",
+                self.cache_hits, self.cache_misses
+            )?;
+        }
+        if self.queue_peak > 0 {
+            write!(f, ", at most {} queued", self.queue_peak)?;
+        }
+        write!(f, " in {:.3} s", self.elapsed.as_secs_f64())
+    }
+}
+
+/// A search that gave up at the time limit
+#[derive(Debug)]
+pub struct TimedOut {
+    pub search: String,
+    pub limit: Duration,
+    /// The best answer so far, see [`Search::improve`]
+    pub best: Option<String>,
+    pub stats: Stats,
+}
+
+impl fmt::Display for TimedOut {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: gave up at the time limit of {:.3} s",
+            self.search,
+            self.limit.as_secs_f64()
+        )?;
+        match &self.best {
+            Some(best) => write!(f, " with {} so far", best)?,
+            None => write!(f, " without an answer")?,
+        }
+        write!(f, ", after {}", self.stats)
+    }
+}
+
+impl std::error::Error for TimedOut {}
+
+/// The counters of a running search, which it updates as it goes
+#[derive(Debug)]
+pub struct Search {
+    name: String,
+    start: Instant,
+    limit: Option<Duration>,
+    expanded: AtomicU64,
+    cache_hits: AtomicU64,
+    cache_misses: AtomicU64,
+    queue_peak: AtomicUsize,
+    best: Mutex<Option<String>>,
+    /// Set once the time limit has passed, so that every thread gives up
+    stopped: AtomicBool,
+    /// Whole seconds since the start at the last progress report
+    reported: AtomicU64,
+}
+
+impl Search {
+    /// A search that gives up at the time limit of the process, if there is one
+    pub fn new(name: impl Into<String>) -> Self {
+        Self::with_limit(name, TIME_LIMIT.get().copied())
+    }
+
+    pub fn with_limit(name: impl Into<String>, limit: Option<Duration>) -> Self {
+        Self {
+            name: name.into(),
+            start: Instant::now(),
+            limit,
+            expanded: AtomicU64::new(0),
+            cache_hits: AtomicU64::new(0),
+            cache_misses: AtomicU64::new(0),
+            queue_peak: AtomicUsize::new(0),
+            best: Mutex::new(None),
+            stopped: AtomicBool::new(false),
+            reported: AtomicU64::new(0),
+        }
+    }
+
+    /// Counts an expanded state. Every so often this reports the progress, and fails
+    /// once the time limit has passed.
+    pub fn expand(&self) -> Result<(), TimedOut> {
+        self.expand_many(1)
+    }
+
+    /// Counts `count` expanded states at once
+    pub fn expand_many(&self, count: u64) -> Result<(), TimedOut> {
+        let before = self.expanded.fetch_add(count, Relaxed);
+        if self.stopped.load(Relaxed) {
+            return Err(self.timed_out());
+        }
+        if before / CHECK_EVERY != (before + count) / CHECK_EVERY {
+            self.check()?;
+        }
+        Ok(())
+    }
+
+    /// Reports the progress, and fails once the time limit has passed. [`Search::expand`]
+    /// does this every so often by itself, but states that take long to expand should
+    /// check after each one.
+    pub fn check(&self) -> Result<(), TimedOut> {
+        let elapsed = self.start.elapsed();
+        if self.limit.is_some_and(|limit| elapsed > limit) {
+            self.stopped.store(true, Relaxed);
+            return Err(self.timed_out());
+        }
+        let seconds = elapsed.as_secs();
+        if self.reported.fetch_max(seconds, Relaxed) < seconds {
+            match self.best() {
+                Some(best) => info!("{}: {}, best so far {}", self.name, self.stats(), best),
+                None => info!("{}: {}", self.name, self.stats()),
+            }
+        }
+        Ok(())
+    }
+
+    pub fn cache_hit(&self) {
+        self.cache_hits.fetch_add(1, Relaxed);
+    }
+
+    pub fn cache_miss(&self) {
+        self.cache_misses.fetch_add(1, Relaxed);
+    }
+
+    /// Notes the length of the queue, to keep track of its peak
+    pub fn queue_len(&self, len: usize) {
+        self.queue_peak.fetch_max(len, Relaxed);
+    }
+
+    /// Records the best answer so far, which a search that times out gives
+    pub fn improve(&self, best: impl fmt::Display) {
+        let best = best.to_string();
+        debug!("{}: new best {}", self.name, best);
+        *self.best.lock().unwrap() = Some(best);
+    }
+
+    pub fn best(&self) -> Option<String> {
+        self.best.lock().unwrap().clone()
+    }
+
+    pub fn stats(&self) -> Stats {
+        Stats {
+            expanded: self.expanded.load(Relaxed),
+            cache_hits: self.cache_hits.load(Relaxed),
+            cache_misses: self.cache_misses.load(Relaxed),
+            queue_peak: self.queue_peak.load(Relaxed),
+            elapsed: self.start.elapsed(),
+        }
+    }
+
+    /// Logs the statistics of a search that ran to the end
+    pub fn finish(&self) {
+        info!("{}: {}", self.name, self.stats());
+    }
+
+    fn timed_out(&self) -> TimedOut {
+        TimedOut {
+            search: self.name.clone(),
+            limit: self.limit.unwrap_or_default(),
+            best: self.best(),
+            stats: self.stats(),
+        }
+    }
+}
+
+/// Counts on one thread and adds the counts to the [`Search`] in batches, for loops too
+/// tight to update the shared counters at every state. Whatever is left is added when
+/// the batch is dropped.
+pub struct Batch<'a> {
+    search: &'a Search,
+    expanded: u64,
+    cache_hits: u64,
+    cache_misses: u64,
+    queue_peak: usize,
+}
+
+impl<'a> Batch<'a> {
+    pub fn new(search: &'a Search) -> Self {
+        Self {
+            search,
+            expanded: 0,
+            cache_hits: 0,
+            cache_misses: 0,
+            queue_peak: 0,
+        }
+    }
+
+    /// See [`Search::expand`]
+    pub fn expand(&mut self) -> Result<(), TimedOut> {
+        self.expanded += 1;
+        match self.expanded == BATCH_SIZE {
+            true => self.flush(),
+            false => Ok(()),
+        }
+    }
+
+    pub fn cache_hit(&mut self) {
+        self.cache_hits += 1;
+    }
+
+    pub fn cache_miss(&mut self) {
+        self.cache_misses += 1;
+    }
+
+    pub fn queue_len(&mut self, len: usize) {
+        self.queue_peak = self.queue_peak.max(len);
+    }
+
+    fn flush(&mut self) -> Result<(), TimedOut> {
+        let search = self.search;
+        search.cache_hits.fetch_add(self.cache_hits, Relaxed);
+        search.cache_misses.fetch_add(self.cache_misses, Relaxed);
+        search.queue_len(self.queue_peak);
+        let expanded = self.expanded;
+        (self.expanded, self.cache_hits, self.cache_misses) = (0, 0, 0);
+        search.expand_many(expanded)
+    }
+}
+
+impl Drop for Batch<'_> {
+    fn drop(&mut self) {
+        // A search that gave up has already said so
+        let _ = self.flush();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counters() {
+        let search = Search::with_limit("test", None);
+        for _ in 0..10 {
+            search.expand().unwrap();
+        }
+        search.cache_hit();
+        search.cache_miss();
+        search.cache_miss();
+        search.queue_len(7);
+        search.queue_len(3);
+        let stats = search.stats();
+        assert_eq!(
+            (
+                stats.expanded,
+                stats.cache_hits,
+                stats.cache_misses,
+                stats.queue_peak
+            ),
+            (10, 1, 2, 7)
+        );
+        assert!(stats
+            .to_string()
+            .starts_with("10 states expanded, 1 cache hits and 2 misses, at most 7 queued in "));
+    }
+
+    #[test]
+    fn gives_up_at_the_time_limit() {
+        let search = Search::with_limit("test", Some(Duration::ZERO));
+        search.improve(12);
+        std::thread::sleep(Duration::from_millis(1));
+        let timed_out = (0..).find_map(|_| search.expand().err()).unwrap();
+        assert_eq!(timed_out.stats.expanded, CHECK_EVERY);
+        assert_eq!(timed_out.best.as_deref(), Some("12"));
+        assert!(timed_out.to_string().starts_with("test: gave up"));
+        // From then on it keeps giving up
+        assert!(search.expand().is_err());
+    }
+}
@@ -0,0 +1,358 @@
+//! Half-open ranges of integers and sets of them, for the days that push whole ranges of
+//! numbers through a puzzle instead of single numbers.
+use std::fmt;
+
+/// The integers `start..end`, which is empty unless `start < end`
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Interval {
+    pub start: i64,
+    pub end: i64,
+}
+
+impl Interval {
+    pub const fn new(start: i64, end: i64) -> Self {
+        Self { start, end }
+    }
+
+    /// The `len` integers from `start` on
+    pub const fn with_len(start: i64, len: i64) -> Self {
+        Self::new(start, start + len)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start >= self.end
+    }
+
+    /// The number of integers in the interval
+    pub fn len(&self) -> u64 {
+        self.end.saturating_sub(self.start).max(0) as u64
+    }
+
+    pub fn contains(&self, value: i64) -> bool {
+        self.start <= value && value < self.end
+    }
+
+    /// The integers in both intervals, which may be empty
+    pub fn intersection(&self, other: &Interval) -> Interval {
+        Interval::new(self.start.max(other.start), self.end.min(other.end))
+    }
+
+    /// The integers below `at`, and those from `at` on. Either may be empty.
+    pub fn split_at(&self, at: i64) -> (Interval, Interval) {
+        let at = at.clamp(self.start, self.end.max(self.start));
+        (
+            Interval::new(self.start, at),
+            Interval::new(at, self.end.max(self.start)),
+        )
+    }
+
+    /// The interval moved up by `offset`
+    pub fn shift(&self, offset: i64) -> Interval {
+        Interval::new(self.start + offset, self.end + offset)
+    }
+}
+
+impl fmt::Debug for Interval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}..{}", self.start, self.end)
+    }
+}
+
+/// A set of integers, kept as sorted intervals that neither overlap nor touch, so that
+/// equal sets have equal intervals.
+#[derive(Clone, Default, PartialEq, Eq, Hash)]
+pub struct IntervalSet {
+    intervals: Vec<Interval>,
+}
+
+impl IntervalSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sorts the intervals and merges those that overlap or touch
+    fn coalesce(mut intervals: Vec<Interval>) -> Self {
+        intervals.retain(|interval| !interval.is_empty());
+        intervals.sort_by_key(|interval| interval.start);
+        let mut merged: Vec<Interval> = Vec::with_capacity(intervals.len());
+        for interval in intervals {
+            match merged.last_mut() {
+                Some(last) if interval.start <= last.end => last.end = last.end.max(interval.end),
+                _ => merged.push(interval),
+            }
+        }
+        Self { intervals: merged }
+    }
+
+    /// The intervals of the set, in increasing order
+    pub fn intervals(&self) -> &[Interval] {
+        &self.intervals
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// The number of integers in the set
+    pub fn len(&self) -> u64 {
+        self.intervals.iter().map(Interval::len).sum()
+    }
+
+    pub fn min(&self) -> Option<i64> {
+        self.intervals.first().map(|interval| interval.start)
+    }
+
+    pub fn max(&self) -> Option<i64> {
+        self.intervals.last().map(|interval| interval.end - 1)
+    }
+
+    pub fn contains(&self, value: i64) -> bool {
+        let after = self
+            .intervals
+            .partition_point(|interval| interval.end <= value);
+        self.intervals
+            .get(after)
+            .is_some_and(|interval| interval.contains(value))
+    }
+
+    pub fn insert(&mut self, interval: Interval) {
+        let mut intervals = std::mem::take(&mut self.intervals);
+        intervals.push(interval);
+        *self = Self::coalesce(intervals);
+    }
+
+    pub fn union(&self, other: &IntervalSet) -> IntervalSet {
+        Self::coalesce(
+            self.intervals
+                .iter()
+                .chain(&other.intervals)
+                .copied()
+                .collect(),
+        )
+    }
+
+    pub fn intersection(&self, other: &IntervalSet) -> IntervalSet {
+        let (mut i, mut j) = (0, 0);
+        let mut intervals = Vec::new();
+        while let (Some(a), Some(b)) = (self.intervals.get(i), other.intervals.get(j)) {
+            let both = a.intersection(b);
+            if !both.is_empty() {
+                intervals.push(both);
+            }
+            // Whichever ends first cannot meet anything further on in the other set
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        Self { intervals }
+    }
+
+    /// The integers in this set but not in `other`
+    pub fn difference(&self, other: &IntervalSet) -> IntervalSet {
+        let mut intervals = Vec::new();
+        let mut j = 0;
+        for interval in &self.intervals {
+            while other
+                .intervals
+                .get(j)
+                .is_some_and(|b| b.end <= interval.start)
+            {
+                j += 1;
+            }
+            let mut start = interval.start;
+            for b in other.intervals[j..]
+                .iter()
+                .take_while(|b| b.start < interval.end)
+            {
+                if start < b.start {
+                    intervals.push(Interval::new(start, b.start));
+                }
+                start = start.max(b.end);
+            }
+            if start < interval.end {
+                intervals.push(Interval::new(start, interval.end));
+            }
+        }
+        Self { intervals }
+    }
+
+    /// The integers below `at`, and those from `at` on
+    pub fn split_at(&self, at: i64) -> (IntervalSet, IntervalSet) {
+        let (below, above) = self
+            .intervals
+            .iter()
+            .map(|interval| interval.split_at(at))
+            .unzip();
+        (Self::coalesce(below), Self::coalesce(above))
+    }
+
+    /// The set moved up by `offset`
+    pub fn shift(&self, offset: i64) -> IntervalSet {
+        Self {
+            intervals: self.intervals.iter().map(|i| i.shift(offset)).collect(),
+        }
+    }
+}
+
+impl From<Interval> for IntervalSet {
+    fn from(interval: Interval) -> Self {
+        Self::coalesce(vec![interval])
+    }
+}
+
+impl FromIterator<Interval> for IntervalSet {
+    fn from_iter<I: IntoIterator<Item = Interval>>(iter: I) -> Self {
+        Self::coalesce(iter.into_iter().collect())
+    }
+}
+
+impl fmt::Debug for IntervalSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(&self.intervals).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gen::{rng, StdRng};
+    use rand::Rng;
+    use std::collections::BTreeSet;
+
+    /// Sets are compared against plain sets of their integers, all within this range
+    const UNIVERSE: std::ops::Range<i64> = -5..55;
+
+    fn random_interval(rng: &mut StdRng) -> Interval {
+        let start = rng.gen_range(0..40);
+        // Now and then an empty or reversed interval
+        Interval::new(start, start + rng.gen_range(-2..10))
+    }
+
+    fn random_set(rng: &mut StdRng) -> IntervalSet {
+        (0..rng.gen_range(0..6))
+            .map(|_| random_interval(rng))
+            .collect()
+    }
+
+    fn elements(set: &IntervalSet) -> BTreeSet<i64> {
+        UNIVERSE.filter(|&x| set.contains(x)).collect()
+    }
+
+    fn assert_canonical(set: &IntervalSet) {
+        for interval in &set.intervals {
+            assert!(!interval.is_empty(), "{:?} has an empty interval", set);
+        }
+        for pair in set.intervals.windows(2) {
+            assert!(pair[0].end < pair[1].start, "{:?} is not coalesced", set);
+        }
+    }
+
+    #[test]
+    fn interval_basics() {
+        let interval = Interval::with_len(3, 4);
+        assert_eq!(interval, Interval::new(3, 7));
+        assert_eq!(interval.len(), 4);
+        assert!(interval.contains(3) && interval.contains(6) && !interval.contains(7));
+        assert_eq!(
+            interval.split_at(5),
+            (Interval::new(3, 5), Interval::new(5, 7))
+        );
+        assert!(interval.split_at(10).1.is_empty());
+        assert!(interval.intersection(&Interval::new(7, 9)).is_empty());
+        assert_eq!(Interval::new(5, 2).len(), 0);
+    }
+
+    #[test]
+    fn touching_intervals_merge() {
+        let set: IntervalSet = [
+            Interval::new(5, 8),
+            Interval::new(0, 5),
+            Interval::new(9, 9),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(set.intervals(), [Interval::new(0, 8)]);
+        assert_eq!(format!("{:?}", set), "{0..8}");
+    }
+
+    #[test]
+    fn sets_agree_with_sets_of_integers() {
+        let mut rng = rng(0);
+        for _ in 0..2000 {
+            let (a, b) = (random_set(&mut rng), random_set(&mut rng));
+            let (ea, eb) = (elements(&a), elements(&b));
+            assert_canonical(&a);
+            assert_eq!(a.len(), ea.len() as u64);
+            assert_eq!(a.min(), ea.first().copied());
+            assert_eq!(a.max(), ea.last().copied());
+
+            let union = a.union(&b);
+            assert_canonical(&union);
+            assert_eq!(elements(&union), &ea | &eb, "{:?} | {:?}", a, b);
+
+            let intersection = a.intersection(&b);
+            assert_canonical(&intersection);
+            assert_eq!(elements(&intersection), &ea & &eb, "{:?} & {:?}", a, b);
+
+            let difference = a.difference(&b);
+            assert_canonical(&difference);
+            assert_eq!(elements(&difference), &ea - &eb, "{:?} - {:?}", a, b);
+
+            let at = rng.gen_range(UNIVERSE);
+            let (below, above) = a.split_at(at);
+            assert_canonical(&below);
+            assert_canonical(&above);
+            assert!(elements(&below).iter().all(|&x| x < at));
+            assert!(elements(&above).iter().all(|&x| x >= at));
+            assert_eq!(below.union(&above), a);
+
+            let mut inserted = a.clone();
+            let interval = random_interval(&mut rng);
+            inserted.insert(interval);
+            assert_eq!(inserted, a.union(&interval.into()));
+        }
+    }
+
+    #[test]
+    fn shifting_moves_every_element() {
+        let mut rng = rng(1);
+        for _ in 0..500 {
+            let set = random_set(&mut rng);
+            let offset = rng.gen_range(-3..=3);
+            let shifted = set.shift(offset);
+            assert_canonical(&shifted);
+            let expected: BTreeSet<i64> = elements(&set).iter().map(|x| x + offset).collect();
+            let actual: BTreeSet<i64> = (UNIVERSE.start - 3..UNIVERSE.end + 3)
+                .filter(|&x| shifted.contains(x))
+                .collect();
+            assert_eq!(actual, expected);
+        }
+    }
+
+    #[test]
+    fn set_algebra_laws() {
+        let mut rng = rng(2);
+        for _ in 0..500 {
+            let (a, b, c) = (
+                random_set(&mut rng),
+                random_set(&mut rng),
+                random_set(&mut rng),
+            );
+            assert_eq!(a.union(&b), b.union(&a));
+            assert_eq!(a.intersection(&b), b.intersection(&a));
+            assert_eq!(
+                a.intersection(&b.union(&c)),
+                a.intersection(&b).union(&a.intersection(&c))
+            );
+            // Everything in `a` is either in `b` or not
+            assert_eq!(a.difference(&b).union(&a.intersection(&b)), a);
+            assert!(a.difference(&b).intersection(&b).is_empty());
+            assert_eq!(
+                a.len() + b.len(),
+                a.union(&b).len() + a.intersection(&b).len()
+            );
+        }
+    }
+}
@@ -0,0 +1,254 @@
+//! Shared plumbing for the day crates.
+//!
+//! Every day exposes its solutions through a [`Day`], so that both the per-day binaries
+//! and the `aoc` runner can drive them the same way.
+use anyhow::{anyhow, Context, Result};
+use batch::BatchArgs;
+use clap::{CommandFactory, FromArgMatches, Parser};
+use config::ConfigArgs;
+use input::InputArgs;
+use report::{Format, Report};
+
+pub mod answers;
+pub mod batch;
+pub mod budget;
+pub mod config;
+pub mod cycle;
+pub mod differential;
+pub mod dot;
+pub mod fuzz;
+pub mod gen;
+pub mod geom;
+pub mod graph;
+pub mod grid;
+pub mod input;
+pub mod instrument;
+pub mod interval;
+pub mod math;
+pub mod parallel;
+pub mod parse;
+pub mod render;
+pub mod report;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// Solves one part of a puzzle, taking the puzzle input and returning the answer.
+pub type Solver = fn(&str) -> Result<String>;
+
+pub struct Day {
+    pub number: u8,
+    /// Directory of the day's crate, used to locate the bundled inputs.
+    pub dir: &'static str,
+    pub part1: Option<Solver>,
+    pub part2: Option<Solver>,
+    /// Generates random inputs, see [`gen`]
+    pub generate: Option<gen::Generator>,
+    /// Draws the puzzle, see [`render`]
+    pub render: Option<render::Renderer>,
+    /// Exports the graph of the puzzle, see [`dot`]
+    pub dot: Option<dot::Exporter>,
+    /// Parses an input without solving it, see [`fuzz`]. Days that are quick to solve
+    /// have none, and are fuzzed through their parts.
+    pub parse: Option<fuzz::Parser>,
+    /// Puzzle parameters that a config can change, see [`config`]
+    pub params: &'static [config::Param],
+}
+
+/// The answer to one part together with the wall-clock time it took to compute it.
+pub struct Outcome {
+    pub answer: String,
+    pub elapsed: Duration,
+}
+
+impl Day {
+    pub fn solver(&self, part: u8) -> Option<Solver> {
+        match part {
+            1 => self.part1,
+            2 => self.part2,
+            _ => None,
+        }
+    }
+
+    /// The parts that have a solution, in order.
+    pub fn parts(&self) -> Vec<u8> {
+        (1..=2)
+            .filter(|&part| self.solver(part).is_some())
+            .collect()
+    }
+
+    pub fn read_input(&self, path: &Path) -> Result<String> {
+        std::fs::read_to_string(path)
+            .with_context(|| format!("day {}: could not read {}", self.number, path.display()))
+    }
+
+    pub fn run(&self, part: u8, input: &str) -> Result<Outcome> {
+        let solver = self
+            .solver(part)
+            .ok_or_else(|| anyhow!("day {} has no part {}", self.number, part))?;
+        let start = Instant::now();
+        let answer = solver(input)?;
+        Ok(Outcome {
+            answer,
+            elapsed: start.elapsed(),
+        })
+    }
+
+    /// Draws the puzzle for `input` into an image file, see [`render::Drawing::save`]
+    pub fn draw(&self, input: &str, path: &Path) -> Result<()> {
+        let render = self
+            .render
+            .ok_or_else(|| anyhow!("day {} cannot be drawn", self.number))?;
+        render(input)?.save(path)
+    }
+
+    /// Writes the graph of the puzzle for `input` in the DOT language to `path`, or to
+    /// stdout for `-`. With `highlight` set, the solution stands out.
+    pub fn export_dot(&self, input: &str, path: &Path, highlight: bool) -> Result<()> {
+        let export = self
+            .dot
+            .ok_or_else(|| anyhow!("day {} is not a graph", self.number))?;
+        export(input, highlight)?.save(path)
+    }
+
+    /// Fuzzes the parser of the day for `runs` runs, starting from its bundled inputs and
+    /// a few generated ones, and fails on the first input that makes it panic
+    pub fn fuzz(&self, runs: usize, seed: u64) -> Result<()> {
+        let parse = |input: &str| match self.parse {
+            Some(parse) => parse(input),
+            None => {
+                for part in self.parts() {
+                    let _ = self.run(part, input);
+                }
+                Ok(())
+            }
+        };
+        let mut inputs = self.bundled_inputs()?;
+        if let Some(generate) = self.generate {
+            inputs.extend((0..4).map(|seed| generate(&mut gen::rng(seed), 3)));
+        }
+        let seeds: Vec<&str> = inputs.iter().map(String::as_str).collect();
+        fuzz::fuzz(&parse, &seeds, runs, seed)
+            .with_context(|| format!("day {}: the parser panics", self.number))
+    }
+}
+
+/// Sets up logging to stderr. `RUST_LOG` takes precedence; otherwise only warnings and
+/// errors are shown, and every `verbosity` level adds one more: info, debug, trace.
+pub fn init_logging(verbosity: u8) {
+    let level = match verbosity {
+        0 => log::LevelFilter::Warn,
+        1 => log::LevelFilter::Info,
+        2 => log::LevelFilter::Debug,
+        _ => log::LevelFilter::Trace,
+    };
+    env_logger::Builder::new()
+        .filter_level(level)
+        .parse_default_env()
+        .format_timestamp(None)
+        .init();
+}
+
+#[derive(Parser)]
+struct DayArgs {
+    #[command(flatten)]
+    input: InputArgs,
+
+    #[command(flatten)]
+    batch: BatchArgs,
+
+    #[command(flatten)]
+    config: ConfigArgs,
+
+    /// Print more diagnostics: -v for info, -vv for debug, -vvv for trace
+    #[arg(short, long, action = clap::ArgAction::Count)]
+    verbose: u8,
+
+    #[arg(long, value_enum, default_value_t)]
+    format: Format,
+
+    /// Print the input instead of solving it, to save a generated input
+    #[arg(long, conflicts_with = "batch")]
+    print_input: bool,
+
+    /// Draw the puzzle into this file instead of solving it, as an SVG, PNG or PPM
+    /// depending on its extension
+    #[arg(long, value_name = "PATH", conflicts_with = "print_input")]
+    render: Option<PathBuf>,
+
+    /// Write the graph of the puzzle in the DOT language of Graphviz into this file, or
+    /// to stdout for `-`, instead of solving it
+    #[arg(long, value_name = "PATH", conflicts_with_all = ["print_input", "render"])]
+    dot: Option<PathBuf>,
+
+    /// Highlight the solution in the graph, such as the cut edges or the longest path
+    #[arg(long, requires = "dot")]
+    highlight: bool,
+
+    /// Run the work that splits up on this many threads, with the same answers
+    #[arg(long, value_name = "N", default_value_t = 1)]
+    threads: usize,
+
+    /// Make the long searches give up after this many seconds each, with the best answer
+    /// they found so far and their statistics
+    #[arg(long, value_name = "SECONDS")]
+    time_limit: Option<f64>,
+
+    /// Fuzz the parser for this many runs instead of solving, starting from the bundled
+    /// inputs, and report the first input that makes it panic
+    #[arg(long, value_name = "RUNS", conflicts_with_all = ["print_input", "render", "dot"])]
+    fuzz: Option<usize>,
+}
+
+/// Entry point of the per-day binaries: solves every part on the input given on the
+/// command line, the day's own input by default.
+pub fn run_day(day: &Day) -> Result<()> {
+    let command = DayArgs::command()
+        .name(format!("day{}", day.number))
+        .about(format!("Solves day {} of Advent of Code 2023", day.number));
+    let args = DayArgs::from_arg_matches(&command.get_matches())?;
+    init_logging(args.verbose);
+    args.config.load(&[day], Some(day.number))?.install()?;
+    if let Some(seconds) = args.time_limit {
+        instrument::set_time_limit(seconds)?;
+    }
+    if args.config.print_config {
+        print!("{}", config::to_toml(&[day])?);
+        return Ok(());
+    }
+    if args.batch.batch.is_some() {
+        return batch::run_args(day, &args.batch, &day.parts(), args.format, args.threads);
+    }
+    if let Some(runs) = args.fuzz {
+        day.fuzz(runs, 0)?;
+        println!("no panics in {} runs", runs);
+        return Ok(());
+    }
+    let source = args.input.source();
+    let input = day.load_input(&source)?;
+    if args.print_input {
+        print!("{}", input);
+        return Ok(());
+    }
+    if let Some(path) = &args.render {
+        return day.draw(&input, path);
+    }
+    if let Some(path) = &args.dot {
+        return day.export_dot(&input, path, args.highlight);
+    }
+    let run = |part| parallel::with_threads(args.threads, || day.run(part, &input))?;
+    for part in day.parts() {
+        match args.format {
+            Format::Text => {
+                let outcome = run(part)?;
+                println!("part {}: {}", part, outcome.answer);
+            }
+            Format::Json => {
+                let result = run(part);
+                println!("{}", Report::new(day, part, &source, &result).to_json());
+                result?;
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,200 @@
+//! Exact integer number theory, for the days where floating point or the assumption of
+//! coprime cycle lengths would break on larger inputs.
+use std::ops::RangeInclusive;
+
+pub fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Panics when the result does not fit in a `u64`.
+pub fn lcm(a: u64, b: u64) -> u64 {
+    if a == 0 || b == 0 {
+        return 0;
+    }
+    (a / gcd(a, b))
+        .checked_mul(b)
+        .expect("lcm does not fit in a u64")
+}
+
+/// `(g, x, y)` such that `g = gcd(a, b) = a * x + b * y`, with `g` not negative.
+pub fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// The largest integer whose square is at most `n`
+pub fn isqrt(n: u128) -> u128 {
+    if n < 2 {
+        return n;
+    }
+    // Newton's method from above, which decreases until it reaches the floor
+    let mut x = 1 << (n.ilog2() / 2 + 1);
+    loop {
+        let next = (x + n / x) / 2;
+        if next >= x {
+            return x;
+        }
+        x = next;
+    }
+}
+
+/// Solves the congruences `x = residue (mod modulus)` for pairs `(residue, modulus)`,
+/// where the moduli need not be coprime and the residues may be any integer. Returns
+/// the smallest non-negative solution and the lcm of the moduli, which every other
+/// solution differs from it by a multiple of, or `None` if the congruences conflict.
+///
+/// Panics if a modulus is not positive, or if the lcm does not fit in an `i64`.
+pub fn crt(congruences: impl IntoIterator<Item = (i64, i64)>) -> Option<(i64, i64)> {
+    let (mut x, mut m) = (0i128, 1i128);
+    for (residue, modulus) in congruences {
+        assert!(modulus > 0, "modulus {} is not positive", modulus);
+        let (residue, modulus) = (residue as i128, modulus as i128);
+        // x + m * k = residue (mod modulus), which needs gcd(m, modulus) to divide the gap
+        let (g, inverse, _) = extended_gcd(m as i64, modulus as i64);
+        let (g, inverse) = (g as i128, inverse as i128);
+        let gap = residue - x;
+        if gap % g != 0 {
+            return None;
+        }
+        let step = modulus / g;
+        let k = (gap / g % step * (inverse % step)).rem_euclid(step);
+        x += m * k;
+        m *= step;
+        assert!(
+            m <= i64::MAX as i128,
+            "lcm of the moduli does not fit in an i64"
+        );
+        x = x.rem_euclid(m);
+    }
+    Some((x as i64, m as i64))
+}
+
+/// The integers `x` with `a * x^2 + b * x + c < 0`, for a positive `a`, or `None` if
+/// there are none.
+pub fn quadratic_below_zero(a: i64, b: i64, c: i64) -> Option<RangeInclusive<i64>> {
+    assert!(a > 0, "the quadratic must open upwards");
+    let (a, b, c) = (a as i128, b as i128, c as i128);
+    let discriminant = b * b - 4 * a * c;
+    if discriminant <= 0 {
+        return None;
+    }
+    let value = |x: i128| (a * x + b) * x + c;
+    // The roots are (-b -+ sqrt(discriminant)) / 2a. Rounding the square root down
+    // moves the estimates at most one step inwards, which the loops correct.
+    let root = isqrt(discriminant as u128) as i128;
+    let mut low = (-b - root).div_euclid(2 * a);
+    let mut high = (-b + root).div_euclid(2 * a) + 1;
+    while value(low) >= 0 && low <= high {
+        low += 1;
+    }
+    while value(low - 1) < 0 {
+        low -= 1;
+    }
+    while value(high) >= 0 && high >= low {
+        high -= 1;
+    }
+    while value(high + 1) < 0 {
+        high += 1;
+    }
+    (low <= high).then_some(low as i64..=high as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gen::rng;
+    use rand::Rng;
+
+    #[test]
+    fn gcd_and_lcm() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(0, 5), 5);
+        assert_eq!(lcm(4, 6), 12);
+        assert_eq!(lcm(0, 6), 0);
+        assert_eq!(extended_gcd(240, 46), (2, -9, 47));
+        assert_eq!(extended_gcd(-4, 6), (2, 1, 1));
+    }
+
+    #[test]
+    fn isqrt_is_exact_near_squares() {
+        for root in (0..1000u128).chain([u64::MAX as u128, 1 << 63, 3_037_000_499]) {
+            assert_eq!(isqrt(root * root), root);
+            if root > 0 {
+                assert_eq!(isqrt(root * root - 1), root - 1);
+            }
+            assert_eq!(isqrt(root * root + 1), root.max(1));
+        }
+        assert_eq!(isqrt(u128::MAX), u64::MAX as u128);
+    }
+
+    #[test]
+    fn crt_with_common_factors_and_offsets() {
+        assert_eq!(crt([(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt([(2, 4), (4, 6)]), Some((10, 12)));
+        assert_eq!(crt([(1, 4), (2, 6)]), None);
+        assert_eq!(crt([(-1, 6), (11, 4)]), Some((11, 12)));
+        assert_eq!(crt([]), Some((0, 1)));
+    }
+
+    #[test]
+    fn crt_agrees_with_search() {
+        let mut rng = rng(0);
+        for _ in 0..1000 {
+            let congruences: Vec<(i64, i64)> = (0..rng.gen_range(1..4))
+                .map(|_| (rng.gen_range(-50..50), rng.gen_range(1..20)))
+                .collect();
+            let modulus = congruences.iter().fold(1, |m, &(_, n)| lcm(m, n as u64)) as i64;
+            let expected = (0..modulus)
+                .find(|x| congruences.iter().all(|&(r, n)| (x - r).rem_euclid(n) == 0))
+                .map(|x| (x, modulus));
+            assert_eq!(
+                crt(congruences.iter().copied()),
+                expected,
+                "{:?}",
+                congruences
+            );
+        }
+    }
+
+    #[test]
+    fn quadratic_agrees_with_search() {
+        let mut rng = rng(1);
+        for _ in 0..1000 {
+            let (a, b, c) = (
+                rng.gen_range(1..5),
+                rng.gen_range(-100..100),
+                rng.gen_range(-500..500),
+            );
+            let inside: Vec<i64> = (-300..300).filter(|x| a * x * x + b * x + c < 0).collect();
+            let expected = (!inside.is_empty()).then(|| inside[0]..=*inside.last().unwrap());
+            assert_eq!(quadratic_below_zero(a, b, c), expected, "{} {} {}", a, b, c);
+        }
+    }
+
+    #[test]
+    fn quadratic_beyond_f64_precision() {
+        // x^2 - t x + d < 0 with roots 10^9 and 10^9 + 1: none strictly between
+        let (t, d) = (2_000_000_001, 1_000_000_000 * 1_000_000_001);
+        assert_eq!(quadratic_below_zero(1, -t, d), None);
+        assert_eq!(quadratic_below_zero(1, -t, d + 1), None);
+        assert_eq!(
+            quadratic_below_zero(1, -t, d - 1),
+            Some(1_000_000_000..=1_000_000_001)
+        );
+    }
+}
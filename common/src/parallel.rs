@@ -0,0 +1,77 @@
+//! Opt-in parallelism for work that splits into independent units. Everything runs on
+//! the calling thread unless it runs within [`with_threads`], and the results always come
+//! back in the order of the units, so that the answers never depend on the threads.
+use anyhow::{Context, Result};
+use rayon::prelude::*;
+use std::fmt::Debug;
+
+/// Runs `f` with `threads` threads for the parallel work within it. With a single thread
+/// `f` runs as it would without this.
+pub fn with_threads<R: Send>(threads: usize, f: impl FnOnce() -> R + Send) -> Result<R> {
+    if threads <= 1 {
+        return Ok(f());
+    }
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .build()
+        .with_context(|| format!("could not start {} threads", threads))?;
+    Ok(pool.install(f))
+}
+
+/// `f` applied to every item, on all the threads of [`with_threads`] if there are any
+pub fn map<T: Sync, R: Send>(items: &[T], f: impl Fn(&T) -> R + Sync + Send) -> Vec<R> {
+    // Only the threads of a pool have an index
+    match rayon::current_thread_index() {
+        Some(_) => items.par_iter().map(f).collect(),
+        None => items.iter().map(f).collect(),
+    }
+}
+
+/// Panics unless `part` gives the same answer to `input` on four threads as on one. For
+/// use in tests of the days that split their work with [`map`].
+pub fn assert_thread_independent<R: PartialEq + Debug + Send>(
+    part: impl Fn(&str) -> Result<R> + Sync,
+    input: &str,
+) {
+    let answer = part(input).unwrap();
+    let threaded = with_threads(4, || part(input)).unwrap();
+    assert_eq!(
+        threaded.unwrap(),
+        answer,
+        "the answer depends on the threads"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn results_keep_their_order() {
+        let items: Vec<u64> = (0..1000).collect();
+        let square = |&x: &u64| x * x;
+        let sequential = map(&items, square);
+        for threads in [1, 2, 7] {
+            assert_eq!(
+                with_threads(threads, || map(&items, square)).unwrap(),
+                sequential
+            );
+        }
+    }
+
+    #[test]
+    fn only_pools_run_in_parallel() {
+        let outside = map(&[(); 4], |_| rayon::current_thread_index());
+        assert_eq!(outside, [None; 4]);
+        let inside = with_threads(3, || map(&[(); 4], |_| rayon::current_thread_index()));
+        assert!(inside.unwrap().iter().all(Option::is_some));
+    }
+
+    #[test]
+    #[should_panic(expected = "the answer depends on the threads")]
+    fn answers_that_depend_on_the_threads() {
+        let count_lines = |input: &str| Ok(map(&input.lines().collect::<Vec<_>>(), |_| 1).len());
+        assert_thread_independent(count_lines, "a\nb");
+        assert_thread_independent(|_| Ok(rayon::current_thread_index()), "");
+    }
+}
@@ -0,0 +1,181 @@
+//! The error type of the puzzle input parsers, which points at the offending text.
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// Part of a line could not be interpreted. Lines and columns count from 1.
+    Invalid {
+        line: usize,
+        column: usize,
+        text: String,
+        expected: String,
+    },
+    /// A line ended before something it should contain.
+    Missing {
+        line: usize,
+        text: String,
+        expected: String,
+    },
+    /// The input as a whole lacks something, such as a section or a start tile.
+    Incomplete { expected: String },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Invalid {
+                line,
+                column,
+                text,
+                expected,
+            } => write!(
+                f,
+                "line {}, column {}: expected {}, found {:?}",
+                line, column, expected, text
+            ),
+            ParseError::Missing {
+                line,
+                text,
+                expected,
+            } => write!(f, "line {}: expected {} in {:?}", line, expected, text),
+            ParseError::Incomplete { expected } => write!(f, "input has no {}", expected),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl ParseError {
+    pub fn incomplete(expected: impl Into<String>) -> Self {
+        ParseError::Incomplete {
+            expected: expected.into(),
+        }
+    }
+
+    /// Moves the error down by `lines`, for errors in a section that did not start on
+    /// the first line of the input.
+    pub fn shifted(self, lines: usize) -> Self {
+        match self {
+            ParseError::Invalid {
+                line,
+                column,
+                text,
+                expected,
+            } => ParseError::Invalid {
+                line: line + lines,
+                column,
+                text,
+                expected,
+            },
+            ParseError::Missing {
+                line,
+                text,
+                expected,
+            } => ParseError::Missing {
+                line: line + lines,
+                text,
+                expected,
+            },
+            ParseError::Incomplete { .. } => self,
+        }
+    }
+}
+
+/// One line of the input, which knows where it is for error reporting.
+#[derive(Clone, Copy, Debug)]
+pub struct Line<'a> {
+    /// Line number, counting from 1
+    pub number: usize,
+    pub text: &'a str,
+}
+
+/// The lines of the input, numbered from 1.
+pub fn lines(input: &str) -> impl Iterator<Item = Line<'_>> {
+    input.lines().enumerate().map(|(i, text)| Line {
+        number: i + 1,
+        text,
+    })
+}
+
+impl<'a> Line<'a> {
+    /// Column of `token` on this line, counting from 1. `token` should be a slice of
+    /// the line's text; for anything else the first column is reported.
+    pub fn column_of(&self, token: &str) -> usize {
+        let start = self.text.as_ptr() as usize;
+        let pos = token.as_ptr() as usize;
+        if pos >= start && pos + token.len() <= start + self.text.len() {
+            self.text[..pos - start].chars().count() + 1
+        } else {
+            1
+        }
+    }
+
+    pub fn invalid(&self, token: &str, expected: impl Into<String>) -> ParseError {
+        ParseError::Invalid {
+            line: self.number,
+            column: self.column_of(token),
+            text: token.to_string(),
+            expected: expected.into(),
+        }
+    }
+
+    pub fn missing(&self, expected: impl Into<String>) -> ParseError {
+        ParseError::Missing {
+            line: self.number,
+            text: self.text.to_string(),
+            expected: expected.into(),
+        }
+    }
+
+    /// Parses `token` with [`FromStr`], reporting failures at the token's position.
+    pub fn parse<T: FromStr>(&self, token: &str, expected: &str) -> Result<T, ParseError> {
+        token.parse().map_err(|_| self.invalid(token, expected))
+    }
+
+    /// Splits the line once on `delimiter`, reporting a missing delimiter as an error.
+    pub fn split_once(&self, delimiter: &str) -> Result<(&'a str, &'a str), ParseError> {
+        self.text
+            .split_once(delimiter)
+            .ok_or_else(|| self.missing(format!("{:?}", delimiter)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn errors_point_at_the_token() {
+        let line = lines("first\nabc 12x").nth(1).unwrap();
+        let token = line.text.split(' ').nth(1).unwrap();
+        let err = line.parse::<u32>(token, "a number").unwrap_err();
+        assert_eq!(
+            err,
+            ParseError::Invalid {
+                line: 2,
+                column: 5,
+                text: "12x".to_string(),
+                expected: "a number".to_string()
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 5: expected a number, found \"12x\""
+        );
+    }
+
+    #[test]
+    fn missing_delimiter() {
+        let line = lines("a=b\nab").nth(1).unwrap();
+        assert_eq!(
+            line.split_once("=").unwrap_err().to_string(),
+            "line 2: expected \"=\" in \"ab\""
+        );
+        assert_eq!(line.column_of("elsewhere"), 1);
+        assert_eq!(
+            line.split_once("=").unwrap_err().shifted(3).to_string(),
+            "line 5: expected \"=\" in \"ab\""
+        );
+    }
+}
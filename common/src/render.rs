@@ -0,0 +1,476 @@
+//! Pictures of puzzle states, for presentations and for seeing why an answer is wrong.
+//!
+//! A day describes a [`Drawing`] in its own coordinates, which is written as an SVG or
+//! drawn into pixels for a PPM or PNG, depending on the extension of the file.
+use anyhow::{bail, Context, Result};
+use std::fmt::Write as _;
+use std::path::Path;
+
+/// Draws the puzzle for an input.
+pub type Renderer = fn(&str) -> Result<Drawing>;
+
+/// Longest side of raster images, in pixels
+pub const MAX_SIZE: usize = 1024;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb(0, 0, 0);
+    pub const WHITE: Rgb = Rgb(255, 255, 255);
+    pub const GREY: Rgb = Rgb(128, 128, 128);
+    pub const RED: Rgb = Rgb(220, 40, 40);
+    pub const GREEN: Rgb = Rgb(60, 170, 60);
+    pub const BLUE: Rgb = Rgb(40, 90, 220);
+    pub const YELLOW: Rgb = Rgb(240, 200, 40);
+
+    /// Parses `#rrggbb`
+    pub fn from_hex(hex: &str) -> Option<Rgb> {
+        let digits = hex.strip_prefix('#').filter(|digits| digits.len() == 6)?;
+        let channel = |i: usize| u8::from_str_radix(digits.get(i..i + 2)?, 16).ok();
+        Some(Rgb(channel(0)?, channel(2)?, channel(4)?))
+    }
+
+    /// The colour `t` of the way from this one to `other`, with `t` between 0 and 1
+    pub fn mix(self, other: Rgb, t: f64) -> Rgb {
+        let t = t.clamp(0.0, 1.0);
+        let channel = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * t).round() as u8;
+        Rgb(
+            channel(self.0, other.0),
+            channel(self.1, other.1),
+            channel(self.2, other.2),
+        )
+    }
+
+    fn hex(self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.0, self.1, self.2)
+    }
+}
+
+enum Shape {
+    /// The unit square with its top left corner at a point
+    Cell((i64, i64), Rgb),
+    Polygon(Vec<(f64, f64)>, Rgb),
+    /// Line segments through the points, with a width in drawing units
+    Path(Vec<(f64, f64)>, Rgb, f64),
+}
+
+/// Shapes on a background, drawn in the order they were added. Coordinates grow to the
+/// right and down, as in the puzzle grids.
+pub struct Drawing {
+    min: (f64, f64),
+    max: (f64, f64),
+    background: Rgb,
+    shapes: Vec<Shape>,
+}
+
+impl Drawing {
+    /// The rectangle from `min` to `max`
+    pub fn new(min: (f64, f64), max: (f64, f64), background: Rgb) -> Self {
+        Self {
+            min,
+            max,
+            background,
+            shapes: Vec::new(),
+        }
+    }
+
+    /// Room for `width` by `height` grid cells
+    pub fn grid(width: usize, height: usize, background: Rgb) -> Self {
+        Self::new((0.0, 0.0), (width as f64, height as f64), background)
+    }
+
+    /// The centre of a grid cell, for paths through cells
+    pub fn centre((x, y): (usize, usize)) -> (f64, f64) {
+        (x as f64 + 0.5, y as f64 + 0.5)
+    }
+
+    pub fn cell(&mut self, (x, y): (usize, usize), color: Rgb) {
+        self.shapes.push(Shape::Cell((x as i64, y as i64), color));
+    }
+
+    /// Fills the polygon through `points`, where parts that it winds around twice count
+    /// as outside
+    pub fn polygon(&mut self, points: Vec<(f64, f64)>, color: Rgb) {
+        self.shapes.push(Shape::Polygon(points, color));
+    }
+
+    pub fn path(&mut self, points: Vec<(f64, f64)>, color: Rgb, width: f64) {
+        self.shapes.push(Shape::Path(points, color, width));
+    }
+
+    /// Writes the drawing to `path`, as an SVG, PNG or PPM depending on its extension
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+        let bytes = match extension.to_ascii_lowercase().as_str() {
+            "svg" => self.to_svg().into_bytes(),
+            "png" => self.rasterize(MAX_SIZE).to_png(),
+            "ppm" => self.rasterize(MAX_SIZE).to_ppm(),
+            _ => bail!(
+                "cannot tell the image format of {}: use .svg, .png or .ppm",
+                path.display()
+            ),
+        };
+        std::fs::write(path, bytes).with_context(|| format!("could not write {}", path.display()))
+    }
+
+    pub fn to_svg(&self) -> String {
+        let (width, height) = (self.max.0 - self.min.0, self.max.1 - self.min.1);
+        let mut svg = String::new();
+        // Writing to a string cannot fail
+        let _ = writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="{} {} {} {}">"#,
+            self.min.0, self.min.1, width, height
+        );
+        let _ = writeln!(
+            svg,
+            r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}"/>"#,
+            self.min.0,
+            self.min.1,
+            width,
+            height,
+            self.background.hex()
+        );
+        let points = |points: &[(f64, f64)]| {
+            points
+                .iter()
+                .map(|(x, y)| format!("{},{}", x, y))
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+        let mut shapes = self.shapes.iter().peekable();
+        while let Some(shape) = shapes.next() {
+            match shape {
+                Shape::Cell((x, y), color) => {
+                    // Runs of the same colour along a row make a single rectangle
+                    let mut len = 1;
+                    while let Some(Shape::Cell(next, next_color)) = shapes.peek() {
+                        if *next != (x + len, *y) || next_color != color {
+                            break;
+                        }
+                        shapes.next();
+                        len += 1;
+                    }
+                    let _ = writeln!(
+                        svg,
+                        r#"<rect x="{}" y="{}" width="{}" height="1" fill="{}" shape-rendering="crispEdges"/>"#,
+                        x,
+                        y,
+                        len,
+                        color.hex()
+                    );
+                }
+                Shape::Polygon(corners, color) => {
+                    let _ = writeln!(
+                        svg,
+                        r#"<polygon points="{}" fill="{}" fill-rule="evenodd"/>"#,
+                        points(corners),
+                        color.hex()
+                    );
+                }
+                Shape::Path(through, color, width) => {
+                    let _ = writeln!(
+                        svg,
+                        r#"<polyline points="{}" fill="none" stroke="{}" stroke-width="{}" stroke-linecap="round" stroke-linejoin="round"/>"#,
+                        points(through),
+                        color.hex(),
+                        width
+                    );
+                }
+            }
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    /// Draws into pixels, with the longer side at most `max_size` pixels. When the
+    /// drawing is small enough, every unit is a whole number of pixels so that grid
+    /// cells stay sharp.
+    pub fn rasterize(&self, max_size: usize) -> Image {
+        let (width, height) = (self.max.0 - self.min.0, self.max.1 - self.min.1);
+        let mut scale = max_size as f64 / width.max(height).max(f64::MIN_POSITIVE);
+        if scale >= 1.0 {
+            scale = scale.floor();
+        }
+        let mut image = Image::new(
+            ((width * scale).ceil() as usize).max(1),
+            ((height * scale).ceil() as usize).max(1),
+            self.background,
+        );
+        // Pixel coordinates of a point, where pixel (i, j) covers i..i+1 and j..j+1
+        let to_pixels = |(x, y): (f64, f64)| ((x - self.min.0) * scale, (y - self.min.1) * scale);
+
+        for shape in &self.shapes {
+            match shape {
+                Shape::Cell((x, y), color) => {
+                    let (left, top) = to_pixels((*x as f64, *y as f64));
+                    image.fill_rect(left, top, left + scale, top + scale, *color);
+                }
+                Shape::Polygon(corners, color) => {
+                    let corners: Vec<(f64, f64)> = corners.iter().map(|&p| to_pixels(p)).collect();
+                    image.fill_polygon(&corners, *color);
+                }
+                Shape::Path(through, color, width) => {
+                    let radius = (width * scale / 2.0).max(0.5);
+                    for segment in through.windows(2) {
+                        let (from, to) = (to_pixels(segment[0]), to_pixels(segment[1]));
+                        image.stroke(from, to, radius, *color);
+                    }
+                }
+            }
+        }
+        image
+    }
+}
+
+/// Pixels in rows from the top
+pub struct Image {
+    pub width: usize,
+    pub height: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl Image {
+    pub fn new(width: usize, height: usize, background: Rgb) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![background; width * height],
+        }
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Rgb {
+        self.pixels[y * self.width + x]
+    }
+
+    /// Colours the pixels whose centres are in the rectangle
+    fn fill_rect(&mut self, left: f64, top: f64, right: f64, bottom: f64, color: Rgb) {
+        let (x0, x1) = (self.column(left), self.column(right));
+        for y in self.row(top)..self.row(bottom) {
+            self.pixels[y * self.width + x0..y * self.width + x1].fill(color);
+        }
+    }
+
+    /// The first column whose centre is at `x` or beyond
+    fn column(&self, x: f64) -> usize {
+        ((x - 0.5).ceil().max(0.0) as usize).min(self.width)
+    }
+
+    fn row(&self, y: f64) -> usize {
+        ((y - 0.5).ceil().max(0.0) as usize).min(self.height)
+    }
+
+    /// Colours the pixels whose centres are inside the polygon, a row at a time
+    fn fill_polygon(&mut self, corners: &[(f64, f64)], color: Rgb) {
+        let edges: Vec<((f64, f64), (f64, f64))> = corners
+            .iter()
+            .zip(corners.iter().cycle().skip(1))
+            .map(|(&a, &b)| (a, b))
+            .collect();
+        for row in 0..self.height {
+            let y = row as f64 + 0.5;
+            let mut crossings: Vec<f64> = edges
+                .iter()
+                .filter(|((_, ay), (_, by))| (*ay <= y) != (*by <= y))
+                .map(|((ax, ay), (bx, by))| ax + (y - ay) * (bx - ax) / (by - ay))
+                .collect();
+            crossings.sort_by(f64::total_cmp);
+            for pair in crossings.chunks_exact(2) {
+                self.fill_rect(pair[0], row as f64, pair[1], row as f64 + 1.0, color);
+            }
+        }
+    }
+
+    /// Colours the pixels within `radius` of the segment
+    fn stroke(&mut self, from: (f64, f64), to: (f64, f64), radius: f64, color: Rgb) {
+        let length = (to.0 - from.0).hypot(to.1 - from.1);
+        let steps = (length * 2.0).ceil().max(1.0) as usize;
+        for step in 0..=steps {
+            let t = step as f64 / steps as f64;
+            let (x, y) = (from.0 + (to.0 - from.0) * t, from.1 + (to.1 - from.1) * t);
+            self.fill_rect(x - radius, y - radius, x + radius, y + radius, color);
+        }
+    }
+
+    /// A binary PPM, which many viewers open and every tool can convert
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let mut bytes = format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+        bytes.extend(self.pixels.iter().flat_map(|&Rgb(r, g, b)| [r, g, b]));
+        bytes
+    }
+
+    /// A PNG with the pixel data stored without compression, which keeps the encoder
+    /// small at the cost of larger files
+    pub fn to_png(&self) -> Vec<u8> {
+        let mut raw = Vec::with_capacity((self.width * 3 + 1) * self.height);
+        for row in self.pixels.chunks(self.width.max(1)) {
+            // No filter
+            raw.push(0);
+            raw.extend(row.iter().flat_map(|&Rgb(r, g, b)| [r, g, b]));
+        }
+
+        // A zlib stream of stored deflate blocks
+        let mut zlib = vec![0x78, 0x01];
+        let mut blocks = raw.chunks(u16::MAX as usize).peekable();
+        if blocks.peek().is_none() {
+            zlib.extend([1, 0, 0, 0xff, 0xff]);
+        }
+        while let Some(block) = blocks.next() {
+            let last = blocks.peek().is_none() as u8;
+            let len = block.len() as u16;
+            zlib.push(last);
+            zlib.extend(len.to_le_bytes());
+            zlib.extend((!len).to_le_bytes());
+            zlib.extend(block);
+        }
+        zlib.extend(adler32(&raw).to_be_bytes());
+
+        let mut header = Vec::new();
+        header.extend((self.width as u32).to_be_bytes());
+        header.extend((self.height as u32).to_be_bytes());
+        // 8 bits per channel, RGB, and the only compression, filter and interlace methods
+        header.extend([8, 2, 0, 0, 0]);
+
+        let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+        png_chunk(&mut png, b"IHDR", &header);
+        png_chunk(&mut png, b"IDAT", &zlib);
+        png_chunk(&mut png, b"IEND", &[]);
+        png
+    }
+}
+
+fn png_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend((data.len() as u32).to_be_bytes());
+    let start = png.len();
+    png.extend(kind);
+    png.extend(data);
+    let crc = crc32(&png[start..]);
+    png.extend(crc.to_be_bytes());
+}
+
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in bytes {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = match crc & 1 {
+                1 => crc >> 1 ^ 0xedb8_8320,
+                _ => crc >> 1,
+            };
+        }
+    }
+    !crc
+}
+
+fn adler32(bytes: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for chunk in bytes.chunks(5552) {
+        for &byte in chunk {
+            a += byte as u32;
+            b += a;
+        }
+        (a, b) = (a % 65521, b % 65521);
+    }
+    b << 16 | a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checksums() {
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+        assert_eq!(crc32(b"IEND"), 0xae42_6082);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+    }
+
+    #[test]
+    fn colours() {
+        assert_eq!(Rgb::from_hex("#70c710"), Some(Rgb(0x70, 0xc7, 0x10)));
+        assert_eq!(Rgb::from_hex("70c710"), None);
+        assert_eq!(Rgb::from_hex("#70c71"), None);
+        assert_eq!(Rgb::BLACK.mix(Rgb::WHITE, 0.5), Rgb(128, 128, 128));
+    }
+
+    #[test]
+    fn cells_fill_whole_pixels() {
+        let mut drawing = Drawing::grid(4, 2, Rgb::WHITE);
+        drawing.cell((1, 0), Rgb::RED);
+        drawing.cell((3, 1), Rgb::BLUE);
+        let image = drawing.rasterize(8);
+        assert_eq!((image.width, image.height), (8, 4));
+        assert_eq!(image.get(2, 0), Rgb::RED);
+        assert_eq!(image.get(3, 1), Rgb::RED);
+        assert_eq!(image.get(4, 1), Rgb::WHITE);
+        assert_eq!(image.get(7, 3), Rgb::BLUE);
+
+        let ppm = image.to_ppm();
+        assert!(ppm.starts_with(b"P6\n8 4\n255\n"));
+        assert_eq!(ppm.len(), 11 + 8 * 4 * 3);
+    }
+
+    #[test]
+    fn polygons_fill_their_inside() {
+        // A square with a square notch cut out of its right side
+        let mut drawing = Drawing::new((0.0, 0.0), (10.0, 10.0), Rgb::WHITE);
+        drawing.polygon(
+            vec![
+                (0.0, 0.0),
+                (10.0, 0.0),
+                (10.0, 4.0),
+                (6.0, 4.0),
+                (6.0, 6.0),
+                (10.0, 6.0),
+                (10.0, 10.0),
+                (0.0, 10.0),
+            ],
+            Rgb::GREEN,
+        );
+        let image = drawing.rasterize(10);
+        let inside: usize = (0..10)
+            .flat_map(|y| (0..10).map(move |x| (x, y)))
+            .filter(|&(x, y)| image.get(x, y) == Rgb::GREEN)
+            .count();
+        assert_eq!(inside, 100 - 8);
+        assert_eq!(image.get(7, 5), Rgb::WHITE);
+        assert_eq!(image.get(5, 5), Rgb::GREEN);
+    }
+
+    #[test]
+    fn paths_cover_their_cells() {
+        let mut drawing = Drawing::grid(5, 5, Rgb::WHITE);
+        let path = [(0, 0), (4, 0), (4, 4)].map(Drawing::centre).to_vec();
+        drawing.path(path, Rgb::BLACK, 0.2);
+        let image = drawing.rasterize(5);
+        for (x, y) in [(0, 0), (2, 0), (4, 0), (4, 2), (4, 4)] {
+            assert_eq!(image.get(x, y), Rgb::BLACK, "{} {}", x, y);
+        }
+        assert_eq!(image.get(2, 2), Rgb::WHITE);
+    }
+
+    #[test]
+    fn svg_merges_runs_of_cells() {
+        let mut drawing = Drawing::grid(3, 1, Rgb::WHITE);
+        for x in 0..3 {
+            drawing.cell((x, 0), Rgb::RED);
+        }
+        let svg = drawing.to_svg();
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.contains(r##"<rect x="0" y="0" width="3" height="1" fill="#dc2828""##));
+        assert_eq!(svg.matches("<rect").count(), 2);
+    }
+
+    #[test]
+    fn png_structure() {
+        let png = Image::new(3, 2, Rgb::RED).to_png();
+        assert!(png.starts_with(b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR\0\0\0\x03\0\0\0\x02"));
+        assert!(png.ends_with(b"\0\0\0\0IEND\xae\x42\x60\x82"));
+        // Rows of a filter byte and three pixels, in one stored block
+        let raw_len = 2 * (1 + 3 * 3);
+        let idat = 8 + 25 + 8;
+        assert_eq!(&png[idat..idat + 3], [0x78, 0x01, 1]);
+        assert_eq!(png[idat + 3..idat + 5], (raw_len as u16).to_le_bytes());
+    }
+}
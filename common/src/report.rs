@@ -0,0 +1,103 @@
+//! Machine-readable results, one JSON object per solved part.
+use crate::input::InputSource;
+use crate::{Day, Outcome};
+use serde::Serialize;
+
+/// How the binaries print their answers.
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Format {
+    /// Human-readable lines
+    #[default]
+    Text,
+    /// One JSON object per part and line
+    Json,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct Report {
+    pub day: u8,
+    pub part: u8,
+    /// `None` when the part failed, see `error`
+    pub answer: Option<String>,
+    pub elapsed_ms: f64,
+    /// `None` for stdin and generated inputs
+    pub input_path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// The expected answer in batch runs, see [`crate::batch`]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expected: Option<String>,
+    /// PASS, FAIL or UNKNOWN when verifying against the answers database
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+}
+
+impl Report {
+    pub fn new(
+        day: &Day,
+        part: u8,
+        source: &InputSource,
+        result: &anyhow::Result<Outcome>,
+    ) -> Self {
+        let (answer, elapsed_ms, error) = match result {
+            Ok(outcome) => (
+                Some(outcome.answer.clone()),
+                outcome.elapsed.as_secs_f64() * 1000.0,
+                None,
+            ),
+            Err(err) => (None, 0.0, Some(format!("{:#}", err))),
+        };
+        Self {
+            day: day.number,
+            part,
+            answer,
+            elapsed_ms,
+            input_path: day
+                .input_path(source)
+                .map(|path| path.display().to_string()),
+            error,
+            expected: None,
+            status: None,
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("reports only contain strings and numbers")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn json_fields() {
+        let day = Day {
+            number: 3,
+            dir: "/aoc/day3",
+            part1: None,
+            part2: None,
+            generate: None,
+            render: None,
+            dot: None,
+            parse: None,
+            params: &[],
+        };
+        let outcome = Outcome {
+            answer: "42".to_string(),
+            elapsed: Duration::from_micros(1500),
+        };
+        let report = Report::new(&day, 1, &InputSource::Default, &Ok(outcome));
+        assert_eq!(
+            report.to_json(),
+            r#"{"day":3,"part":1,"answer":"42","elapsed_ms":1.5,"input_path":"/aoc/day3/src/input.txt"}"#
+        );
+
+        let failed = Report::new(&day, 2, &InputSource::Stdin, &Err(anyhow::anyhow!("bad")));
+        assert_eq!(
+            failed.to_json(),
+            r#"{"day":3,"part":2,"answer":null,"elapsed_ms":0.0,"input_path":null,"error":"bad"}"#
+        );
+    }
+}
@@ -0,0 +1,41 @@
+//! Random calibration documents: lines of letters, digits and spelled out digits.
+use common::gen::StdRng;
+use rand::Rng;
+
+const WORDS: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+/// A document of `size` lines, each with at least one digit so that both parts apply.
+pub fn generate(rng: &mut StdRng, size: usize) -> String {
+    let mut out = String::new();
+    for _ in 0..size {
+        let num_tokens = rng.gen_range(1..=6);
+        let digit_at = rng.gen_range(0..num_tokens);
+        for i in 0..num_tokens {
+            match rng.gen_range(0..3) {
+                _ if i == digit_at => out.push(char::from(b'0' + rng.gen_range(1..=9))),
+                0 => out.push_str(WORDS[rng.gen_range(0..WORDS.len())]),
+                1 => out.push(char::from(b'0' + rng.gen_range(1..=9))),
+                _ => (0..rng.gen_range(1..=4)).for_each(|_| out.push(rng.gen_range('a'..='z'))),
+            }
+        }
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::gen::rng;
+
+    #[test]
+    fn generated_inputs_are_solved() {
+        for seed in 0..20 {
+            let input = generate(&mut rng(seed), 50);
+            assert!(crate::part1(&input).unwrap() <= 50 * 99);
+            assert!(crate::part2(&input).unwrap() <= 50 * 99);
+        }
+    }
+}
@@ -0,0 +1,196 @@
+pub mod gen;
+pub mod reference;
+
+use anyhow::{bail, Result};
+use common::config::Param;
+use common::parse::{self, Line, ParseError};
+use common::Day;
+use log::debug;
+use regex::Regex;
+use std::collections::HashMap;
+
+/// Spelled out digits, which a config file can swap for those of another language or for
+/// other tokens altogether, in a `[day.1.spelled_digits]` table
+const SPELLED_DIGITS: Param = Param::new(
+    1,
+    "spelled_digits",
+    "{ one = 1, two = 2, three = 3, four = 4, five = 5, six = 6, seven = 7, eight = 8, nine = 9 }",
+    "Tokens that count as digits in part 2 besides 1 to 9, with the digits they stand for",
+);
+
+/// The tokens that count as digits: the digits 1 to 9 and the `spelled` ones, which win
+/// over the digits if they have the same token
+fn create_map(spelled: HashMap<String, u32>) -> Result<HashMap<String, u32>> {
+    let mut map: HashMap<String, u32> = (1..=9).map(|digit| (digit.to_string(), digit)).collect();
+    for (token, digit) in spelled {
+        if token.is_empty() {
+            bail!("an empty token cannot stand for a digit");
+        }
+        if digit > 9 {
+            bail!("{:?} stands for {}, which is not a digit", token, digit);
+        }
+        map.insert(token, digit);
+    }
+
+    Ok(map)
+}
+
+/// An alternation of the tokens, longest first, so that of the tokens that match at the
+/// same place the longest one wins
+fn regex_from_tokens(mut tokens: Vec<String>) -> Result<Regex> {
+    tokens.sort_by(|a, b| b.len().cmp(&a.len()).then(a.cmp(b)));
+    let regex_pattern = tokens
+        .iter()
+        .map(|token| regex::escape(token))
+        .collect::<Vec<_>>()
+        .join("|");
+
+    Ok(Regex::new(&regex_pattern)?)
+}
+
+fn process_line1(line: Line) -> Result<u32, ParseError> {
+    let mut iter = line.text.chars().filter(|&c| c.is_ascii_digit());
+    let first = iter.next().ok_or_else(|| line.missing("a digit"))?;
+    let last = iter.next_back().unwrap_or(first);
+
+    let digits = format!("{}{}", first, last);
+    line.parse(&digits, "a number")
+}
+
+fn process_line2(
+    line: Line,
+    map: &HashMap<String, u32>,
+    regex: &Regex,
+    regex_reversed: &Regex,
+) -> Result<u32, ParseError> {
+    let missing = || line.missing("a digit or spelled out digit");
+    let first = regex.find(line.text).ok_or_else(missing)?.as_str();
+
+    let binding = reverse_string(line.text);
+    let last = regex_reversed.find(&binding).ok_or_else(missing)?.as_str();
+    let last = reverse_string(last);
+
+    // The patterns are built from the keys of the map, so every match is in there
+    Ok(map[first] * 10 + map[&last])
+}
+
+fn reverse_string(s: &str) -> String {
+    s.chars().rev().collect()
+}
+
+pub fn part1(input: &str) -> Result<u32> {
+    let mut sum = 0;
+    for line in parse::lines(input) {
+        sum += process_line1(line)?;
+    }
+
+    Ok(sum)
+}
+
+/// The first token in a line is the one that starts first, and the last the one that ends
+/// last. Tokens may overlap, as in `twone`, and of those that start (or end) at the same
+/// place the longest one counts, so that `IX` is a 9 rather than a 1 if both are tokens.
+fn decode(input: &str, map: &HashMap<String, u32>) -> Result<u32> {
+    let regex = regex_from_tokens(map.keys().cloned().collect())?;
+    let regex_reversed =
+        regex_from_tokens(map.keys().map(|token| reverse_string(token)).collect())?;
+
+    debug!("regex: {}", regex);
+    debug!("regex reversed: {}", regex_reversed);
+
+    let mut sum = 0;
+    for line in parse::lines(input) {
+        sum += process_line2(line, map, &regex, &regex_reversed)?;
+    }
+
+    Ok(sum)
+}
+
+pub fn part2(input: &str) -> Result<u32> {
+    let map = create_map(SPELLED_DIGITS.get()?)?;
+    decode(input, &map)
+}
+
+pub fn day() -> Day {
+    Day {
+        number: 1,
+        dir: env!("CARGO_MANIFEST_DIR"),
+        part1: Some(|input| Ok(part1(input)?.to_string())),
+        part2: Some(|input| Ok(part2(input)?.to_string())),
+        generate: Some(gen::generate),
+        render: None,
+        dot: None,
+        parse: None,
+        params: &[SPELLED_DIGITS],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("example.txt");
+    const EXAMPLE2: &str = include_str!("example2.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(EXAMPLE).unwrap(), 142);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(EXAMPLE2).unwrap(), 281);
+    }
+
+    #[test]
+    fn line_without_digits_is_an_error() {
+        let err = part1("a1b\nabc").unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<ParseError>(),
+            Some(ParseError::Missing { line: 2, .. })
+        ));
+    }
+
+    fn spelled(tokens: &[(&str, u32)]) -> HashMap<String, u32> {
+        tokens
+            .iter()
+            .map(|&(token, digit)| (token.to_string(), digit))
+            .collect()
+    }
+
+    #[test]
+    fn other_languages() {
+        let german = spelled(&[("eins", 1), ("zwei", 2), ("drei", 3), ("sieben", 7)]);
+        let map = create_map(german).unwrap();
+        assert_eq!(decode("zweins\nxdrei4siebenx\n", &map).unwrap(), 21 + 37);
+        // The English words are gone
+        assert!(decode("onetwo", &map).is_err());
+    }
+
+    #[test]
+    fn overlapping_tokens() {
+        let roman = spelled(&[
+            ("I", 1),
+            ("II", 2),
+            ("III", 3),
+            ("IV", 4),
+            ("V", 5),
+            ("IX", 9),
+        ]);
+        let map = create_map(roman).unwrap();
+        assert_eq!(decode("IX", &map).unwrap(), 99);
+        assert_eq!(decode("xIIIx", &map).unwrap(), 33);
+        assert_eq!(decode("VIV", &map).unwrap(), 54);
+        assert_eq!(decode("IIV", &map).unwrap(), 24);
+        // Tokens are not patterns
+        let map = create_map(spelled(&[("a.b", 7)])).unwrap();
+        assert_eq!(decode("axb1a.b", &map).unwrap(), 17);
+    }
+
+    #[test]
+    fn bad_tokens() {
+        assert!(create_map(spelled(&[("ten", 10)])).is_err());
+        assert!(create_map(spelled(&[("", 1)])).is_err());
+        assert_eq!(create_map(spelled(&[("1", 7)])).unwrap()["1"], 7);
+    }
+}
@@ -0,0 +1,50 @@
+//! A naive solution to part 2, to check the reversed regex against.
+use anyhow::{anyhow, Result};
+
+const DIGITS: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+/// The digit spelled out or written at the very start of `s`
+fn digit_at(s: &str) -> Option<u32> {
+    let first = s.chars().next()?;
+    if let Some(digit) = first.to_digit(10).filter(|&digit| digit > 0) {
+        return Some(digit);
+    }
+    (1..)
+        .zip(DIGITS)
+        .find(|(_, word)| s.starts_with(word))
+        .map(|(digit, _)| digit)
+}
+
+/// Tries every position of every line, from the front for the first digit and from the
+/// back for the last.
+pub fn part2(input: &str) -> Result<u32> {
+    let mut sum = 0;
+    for line in input.lines() {
+        let mut digits = (0..line.len()).filter_map(|i| digit_at(&line[i..]));
+        let first = digits
+            .next()
+            .ok_or_else(|| anyhow!("no digit in {:?}", line))?;
+        let last = digits.next_back().unwrap_or(first);
+        sum += first * 10 + last;
+    }
+    Ok(sum)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::differential::assert_agree;
+
+    #[test]
+    fn part2_agrees() {
+        assert_agree(
+            |input| Ok(crate::part2(input)?.to_string()),
+            |input| Ok(part2(input)?.to_string()),
+            crate::gen::generate,
+            1..=10,
+            0..50,
+        );
+    }
+}
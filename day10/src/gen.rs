@@ -0,0 +1,83 @@
+//! Random pipe mazes: a loop through the start tile among junk pipes.
+use common::gen::{random_loop, StdRng};
+use common::geom::Direction;
+use common::grid::Grid;
+use rand::Rng;
+use std::collections::HashSet;
+
+const PIPES: &[u8] = b"|-LJ7F";
+
+/// The pipe connecting the two directions
+fn pipe(a: Direction, b: Direction) -> char {
+    use Direction::*;
+    match (a.min(b), a.max(b)) {
+        (Up, Down) => '|',
+        (Right, Left) => '-',
+        (Up, Right) => 'L',
+        (Up, Left) => 'J',
+        (Down, Left) => '7',
+        (Right, Down) => 'F',
+        _ => unreachable!("a pipe connects two different directions"),
+    }
+}
+
+/// A maze of `size + 1` by `size + 1` tiles. See [`maze`].
+pub fn generate(rng: &mut StdRng, size: usize) -> String {
+    maze(rng, size).0
+}
+
+/// A maze and the answers to both parts. The loop is the outline of a random shape, so
+/// the tiles it encloses follow from its area by Pick's theorem.
+pub fn maze(rng: &mut StdRng, size: usize) -> (String, usize, usize) {
+    let size = size.max(2);
+    let junk = (0..(size + 1) * (size + 1))
+        .map(|_| match rng.gen_bool(0.7) {
+            true => PIPES[rng.gen_range(0..PIPES.len())] as char,
+            false => '.',
+        })
+        .collect();
+    let mut tiles = Grid::new(size + 1, size + 1, junk);
+
+    let (mut pos, steps) = random_loop(rng, size, size);
+    let mut path = Vec::new();
+    for (i, &step) in steps.iter().enumerate() {
+        let previous = steps[(i + steps.len() - 1) % steps.len()];
+        tiles[(pos.x, pos.y)] = pipe(previous.reverse(), step);
+        path.push(pos);
+        pos = pos.step(step).unwrap();
+    }
+
+    // Only the loop may connect to the start tile, or its pipe would be ambiguous. Other
+    // parts of the loop next to it lead elsewhere.
+    let on_loop: HashSet<(usize, usize)> = path.iter().map(|pos| (pos.x, pos.y)).collect();
+    let start = path[rng.gen_range(0..path.len())];
+    for next in tiles.neighbours4((start.x, start.y)).collect::<Vec<_>>() {
+        if !on_loop.contains(&next) {
+            tiles[next] = '.';
+        }
+    }
+    tiles[(start.x, start.y)] = 'S';
+
+    let twice_area: i64 = path
+        .iter()
+        .zip(path.iter().cycle().skip(1))
+        .map(|(a, b)| a.to_signed().cross(b.to_signed()))
+        .sum();
+    let enclosed = twice_area.unsigned_abs() as usize / 2 + 1 - path.len() / 2;
+    (tiles.to_string(), path.len() / 2, enclosed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::gen::rng;
+
+    #[test]
+    fn planted_answers() {
+        for seed in 0..20 {
+            let (input, part1, part2) = maze(&mut rng(seed), 15);
+            assert_eq!(crate::part1(&input).unwrap(), part1);
+            assert_eq!(crate::part2(&input).unwrap(), part2);
+        }
+    }
+}
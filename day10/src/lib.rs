@@ -0,0 +1,305 @@
+pub mod gen;
+pub mod render;
+
+use anyhow::Result;
+use common::geom::{Direction, Pos};
+use common::graph;
+use common::grid::Grid;
+use common::parse::ParseError;
+use common::Day;
+use log::{debug, trace};
+use std::collections::{HashMap, HashSet};
+
+/// The tiles that the pipe on each tile connects to
+type Pipes = HashMap<Pos, Vec<Pos>>;
+
+/// The tiles a pipe connects to, or `None` for a tile that is no pipe, ground or start
+fn pipe_neighbours(node: Pos, maze_char: char) -> Option<Vec<Pos>> {
+    let directions: &[Direction] = match maze_char {
+        '|' => &[Direction::Up, Direction::Down],
+        '-' => &[Direction::Right, Direction::Left],
+        'L' => &[Direction::Up, Direction::Right],
+        'J' => &[Direction::Up, Direction::Left],
+        '7' => &[Direction::Down, Direction::Left],
+        'F' => &[Direction::Down, Direction::Right],
+        '.' | 'S' => &[],
+        _ => return None,
+    };
+    Some(
+        directions
+            .iter()
+            .map(|&direction| node.step(direction))
+            .collect(),
+    )
+}
+
+fn is_valid(node: Pos, x_max: i64, y_max: i64) -> bool {
+    node.x >= 0 && node.x < x_max && node.y >= 0 && node.y < y_max
+}
+
+/// Works out which pipe is hidden under the start tile from the pipes around it
+fn start_pipe(tiles: &Grid<char>, pos: (usize, usize)) -> char {
+    let at = |offset| tiles.offset(pos, offset).map_or('.', |next| tiles[next]);
+    let north = matches!(at((0, -1)), '|' | '7' | 'F');
+    let south = matches!(at((0, 1)), '|' | 'L' | 'J');
+    let west = matches!(at((-1, 0)), '-' | 'L' | 'F');
+    let east = matches!(at((1, 0)), '-' | 'J' | '7');
+    match (north, south, west, east) {
+        (true, true, _, _) => '|',
+        (_, _, true, true) => '-',
+        (true, _, _, true) => 'L',
+        (true, _, true, _) => 'J',
+        (_, true, true, _) => '7',
+        (_, true, _, true) => 'F',
+        _ => 'S',
+    }
+}
+
+/// Blows up the maze by a factor two, so that the gaps between pipes become tiles that
+/// can be reached when flooding the outside. The start tile keeps its 'S' but gets the
+/// connections of the pipe under it.
+fn double_maze(tiles: &Grid<char>) -> Grid<char> {
+    let mut out = Grid::filled(tiles.width() * 2, tiles.height() * 2, '.');
+    for ((x, y), &char) in tiles.iter() {
+        out[(2 * x, 2 * y)] = char;
+
+        let pipe = match char {
+            'S' => start_pipe(tiles, (x, y)),
+            _ => char,
+        };
+        match pipe {
+            '|' => {
+                out[(2 * x, 2 * y + 1)] = '|';
+                if y > 0 {
+                    out[(2 * x, 2 * y - 1)] = '|';
+                }
+            }
+            '-' => {
+                out[(2 * x + 1, 2 * y)] = '-';
+                if x > 0 {
+                    out[(2 * x - 1, 2 * y)] = '-';
+                }
+            }
+            'L' => {
+                out[(2 * x + 1, 2 * y)] = '-';
+                if y > 0 {
+                    out[(2 * x, 2 * y - 1)] = '|';
+                }
+            }
+            'J' => {
+                if x > 0 {
+                    out[(2 * x - 1, 2 * y)] = '-';
+                }
+                if y > 0 {
+                    out[(2 * x, 2 * y - 1)] = '|';
+                }
+            }
+            '7' => {
+                out[(2 * x, 2 * y + 1)] = '|';
+                if x > 0 {
+                    out[(2 * x - 1, 2 * y)] = '-';
+                }
+            }
+            'F' => {
+                out[(2 * x + 1, 2 * y)] = '-';
+                out[(2 * x, 2 * y + 1)] = '|'
+            }
+            _ => continue,
+        }
+    }
+    out
+}
+
+/// The pipes of the doubled maze and its start. Errors point at the tile of the input
+/// that the bad tile was doubled from.
+fn extract_graph(maze: &Grid<char>) -> Result<(Pipes, Option<Pos>), ParseError> {
+    let mut start_node: Option<Pos> = None;
+    let mut neighbors: Pipes = HashMap::new();
+    for ((x, y), &char) in maze.iter() {
+        let node = Pos::new(x as i64, y as i64);
+        if char == 'S' {
+            start_node = Some(node);
+            debug!("Found start node: {:?}", start_node);
+        }
+
+        let Some(next) = pipe_neighbours(node, char) else {
+            return Err(ParseError::Invalid {
+                line: y / 2 + 1,
+                column: x / 2 + 1,
+                text: char.to_string(),
+                expected: "a pipe, '.' or 'S'".to_string(),
+            });
+        };
+        neighbors.insert(node, next);
+    }
+
+    Ok((neighbors, start_node))
+}
+
+/// Distance along the loop from the start to every tile on it. The start tile has no
+/// connections of its own, so the loop leaves it through the pipes that connect to it.
+fn find_main_loop(neighbors: &Pipes, start_node: Pos) -> HashMap<Pos, usize> {
+    let connected = |node: &Pos| neighbors.get(node).cloned().unwrap_or_default();
+    let pipes = graph::from_fn(|&node: &Pos| {
+        let next = match node == start_node {
+            true => start_node
+                .neighbours()
+                .into_iter()
+                .filter(|next| connected(next).contains(&start_node))
+                .collect(),
+            false => connected(&node),
+        };
+        next.into_iter().map(|next| (next, 1)).collect()
+    });
+
+    graph::bfs(&pipes, [start_node])
+        .iter()
+        .map(|(&node, distance)| (node, distance))
+        .collect()
+}
+
+/// Floods the doubled maze from its border. Off the loop the water flows every way, but
+/// once it reaches the loop it only follows the pipes.
+fn find_outside_nodes(
+    main_loop: &HashMap<Pos, usize>,
+    neighbors: &Pipes,
+    width: i64,
+    height: i64,
+) -> HashSet<Pos> {
+    let border = (0..width + 1)
+        .flat_map(|x| [Pos::new(x, 0), Pos::new(x, height)])
+        .chain((0..height + 1).flat_map(|y| [Pos::new(0, y), Pos::new(width, y)]))
+        .filter(|node| !main_loop.contains_key(node));
+
+    let flow = graph::from_fn(|&node: &Pos| {
+        let neighbors_to_check = match main_loop.contains_key(&node) {
+            false => node.neighbours().to_vec(),
+            true => neighbors[&node].clone(),
+        };
+        neighbors_to_check
+            .into_iter()
+            .filter(|&neighbor| is_valid(neighbor, width, height))
+            .map(|neighbor| (neighbor, 1))
+            .collect()
+    });
+    let outside = graph::bfs(&flow, border);
+    trace!("N_outside nodes: {}", outside.len());
+
+    outside.nodes().iter().copied().collect()
+}
+
+struct Maze {
+    neighbors: Pipes,
+    main_loop: HashMap<Pos, usize>,
+    width: usize,
+    height: usize,
+}
+
+/// Parses the maze into its doubled form and finds the main loop in it
+fn parse_maze(input: &str) -> Result<Maze> {
+    let tiles = Grid::parse(input, |c| "|-LJ7F.S".contains(c).then_some(c))?;
+    let (neighbors, start_node) = extract_graph(&double_maze(&tiles))?;
+    let start_node = start_node.ok_or_else(|| ParseError::incomplete("start tile"))?;
+
+    let main_loop = find_main_loop(&neighbors, start_node);
+
+    Ok(Maze {
+        neighbors,
+        main_loop,
+        width: tiles.width(),
+        height: tiles.height(),
+    })
+}
+
+/// The farthest point along the loop. Distances in the doubled maze are twice as long.
+pub fn part1(input: &str) -> Result<usize> {
+    let maze = parse_maze(input)?;
+    let max_distance = maze.main_loop.values().max().copied().unwrap_or(0);
+
+    Ok(max_distance / 2)
+}
+
+pub fn part2(input: &str) -> Result<usize> {
+    let Maze {
+        neighbors,
+        main_loop,
+        width,
+        height,
+    } = parse_maze(input)?;
+
+    debug!("width: {}, height: {}", width, height);
+    let outside_nodes = find_outside_nodes(
+        &main_loop,
+        &neighbors,
+        (2 * width - 1) as i64,
+        (2 * height - 1) as i64,
+    );
+
+    let mut num_inside = 0;
+    let mut picture = Grid::filled(2 * width, 2 * height, '.');
+    for (x, y) in picture.positions() {
+        let node = Pos::new(x as i64, y as i64);
+        if main_loop.contains_key(&node) {
+            picture[(x, y)] = ' ';
+        } else if outside_nodes.contains(&node) {
+            picture[(x, y)] = '#';
+        } else if x % 2 == 0 && y % 2 == 0 {
+            num_inside += 1;
+        }
+    }
+    debug!("picture:\n{}", picture);
+
+    Ok(num_inside)
+}
+
+pub fn day() -> Day {
+    Day {
+        number: 10,
+        dir: env!("CARGO_MANIFEST_DIR"),
+        part1: Some(|input| Ok(part1(input)?.to_string())),
+        part2: Some(|input| Ok(part2(input)?.to_string())),
+        generate: Some(gen::generate),
+        render: Some(render::render),
+        dot: None,
+        parse: Some(|input| {
+            parse_maze(input)?;
+            Ok(())
+        }),
+        params: &[],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("example.txt");
+    const EXAMPLE2: &str = include_str!("example2.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(EXAMPLE).unwrap(), 8);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(EXAMPLE2).unwrap(), 10);
+    }
+
+    #[test]
+    fn unknown_tiles_are_an_error() {
+        let err = part1("S-7\n|X|\nL-J").unwrap_err();
+        assert!(err.downcast_ref::<ParseError>().is_some());
+        let mut doubled = double_maze(&Grid::filled(3, 3, '.'));
+        doubled[(2, 4)] = 'X';
+        assert_eq!(
+            extract_graph(&doubled).unwrap_err(),
+            ParseError::Invalid {
+                line: 3,
+                column: 2,
+                text: "X".to_string(),
+                expected: "a pipe, '.' or 'S'".to_string(),
+            }
+        );
+    }
+}
@@ -0,0 +1,81 @@
+//! The main loop, with the tiles it encloses in green and those outside it in grey.
+use crate::{find_outside_nodes, parse_maze, Maze};
+use anyhow::Result;
+use common::geom::Pos;
+use common::render::{Drawing, Rgb};
+
+pub fn render(input: &str) -> Result<Drawing> {
+    let Maze {
+        neighbors,
+        main_loop,
+        width,
+        height,
+    } = parse_maze(input)?;
+    let outside = find_outside_nodes(
+        &main_loop,
+        &neighbors,
+        (2 * width - 1) as i64,
+        (2 * height - 1) as i64,
+    );
+
+    let mut drawing = Drawing::grid(width, height, Rgb::WHITE);
+    for y in 0..height {
+        for x in 0..width {
+            let node = Pos::new(2 * x as i64, 2 * y as i64);
+            if main_loop.contains_key(&node) {
+                continue;
+            }
+            let color = match outside.contains(&node) {
+                true => Rgb(220, 220, 220),
+                false => Rgb::GREEN,
+            };
+            drawing.cell((x, y), color);
+        }
+    }
+
+    // Follow the loop around from the start, which is the node at distance 0
+    let start = main_loop.iter().find(|(_, &distance)| distance == 0);
+    if let Some((&start, _)) = start {
+        let on_loop = |node: &Pos| main_loop.contains_key(node);
+        let mut path = vec![start];
+        let mut next = start
+            .neighbours()
+            .into_iter()
+            .find(|next| on_loop(next) && neighbors[next].contains(&start));
+        while let Some(node) = next.filter(|&node| node != start) {
+            let previous = path[path.len() - 1];
+            path.push(node);
+            next = neighbors[&node]
+                .iter()
+                .copied()
+                .find(|&next| next != previous && on_loop(&next));
+        }
+        path.push(start);
+        // Nodes of the doubled maze are half a tile apart
+        let points = path
+            .iter()
+            .map(|node| (node.x as f64 / 2.0 + 0.5, node.y as f64 / 2.0 + 0.5))
+            .collect();
+        drawing.path(points, Rgb::BLACK, 0.25);
+    }
+    Ok(drawing)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn green_tiles_are_enclosed() {
+        let input = include_str!("example2.txt");
+        let drawing = render(input).unwrap();
+        let width = input.lines().next().unwrap().len();
+        // One pixel per tile
+        let image = drawing.rasterize(width);
+        let num_green = (0..image.height)
+            .flat_map(|y| (0..image.width).map(move |x| (x, y)))
+            .filter(|&(x, y)| image.get(x, y) == Rgb::GREEN)
+            .count();
+        assert_eq!(num_green, crate::part2(input).unwrap());
+    }
+}
@@ -0,0 +1,34 @@
+//! Random images of galaxies, with some empty rows and columns to expand.
+use common::gen::StdRng;
+use rand::Rng;
+
+/// A square image of `size` lines. Every row and every column is empty with
+/// probability 1/5; elsewhere one in ten spots is a galaxy.
+pub fn generate(rng: &mut StdRng, size: usize) -> String {
+    let empty_rows: Vec<bool> = (0..size).map(|_| rng.gen_bool(0.2)).collect();
+    let empty_columns: Vec<bool> = (0..size).map(|_| rng.gen_bool(0.2)).collect();
+    let mut out = String::new();
+    for &empty_row in &empty_rows {
+        for &empty_column in &empty_columns {
+            let galaxy = !empty_row && !empty_column && rng.gen_bool(0.1);
+            out.push(if galaxy { '#' } else { '.' });
+        }
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::gen::rng;
+
+    #[test]
+    fn generated_inputs_are_solved() {
+        for seed in 0..20 {
+            let input = generate(&mut rng(seed), 40);
+            let part1 = crate::part1(&input).unwrap();
+            assert!(crate::part2(&input).unwrap() >= part1);
+        }
+    }
+}
@@ -0,0 +1,123 @@
+pub mod gen;
+
+use anyhow::{bail, Result};
+use common::config::Param;
+use common::grid::Grid;
+use common::Day;
+use log::{debug, trace};
+
+const EXPANSION: Param = Param::new(
+    11,
+    "expansion",
+    "1000000",
+    "Number of rows or columns that every empty one becomes in part 2",
+);
+
+fn distance(galaxy1: (usize, usize), galaxy2: (usize, usize)) -> i64 {
+    let x = (galaxy1.0 as i64 - galaxy2.0 as i64).abs();
+    let y = (galaxy1.1 as i64 - galaxy2.1 as i64).abs();
+
+    x + y
+}
+
+/// Sum of the distances between all pairs of galaxies, after every empty row and column
+/// has been replaced by `expansion_coeff` of them
+fn sum_of_distances(input: &str, expansion_coeff: usize) -> Result<i64> {
+    if expansion_coeff == 0 {
+        bail!("the expansion should be at least 1");
+    }
+    let image = Grid::parse(input, |c| match c {
+        '#' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    })?;
+    let mut galaxy_positions: Vec<(usize, usize)> = image.find_all(&true).collect();
+    let empty_cols: Vec<usize> = image
+        .columns()
+        .map(|mut col| (!col.any(|&galaxy| galaxy) as usize) * (expansion_coeff - 1))
+        .scan(0, |state, s| {
+            *state += s;
+            Some(*state)
+        })
+        .collect();
+    let empty_rows: Vec<usize> = image
+        .rows()
+        .map(|row| (!row.contains(&true) as usize) * (expansion_coeff - 1))
+        .scan(0, |state, s| {
+            *state += s;
+            Some(*state)
+        })
+        .collect();
+    debug!("Empty cols {:?}", empty_cols);
+    debug!("Empty rows {:?}", empty_rows);
+    trace!("Galaxy before moving: {:?}", galaxy_positions);
+    galaxy_positions = galaxy_positions
+        .iter_mut()
+        .map(|(x, y)| (*x + empty_cols[*x], *y + empty_rows[*y]))
+        .collect();
+    trace!("Galaxy after moving: {:?}", galaxy_positions);
+
+    let mut sum = 0;
+    for (i, &galaxy1) in galaxy_positions.iter().enumerate() {
+        for &galaxy2 in galaxy_positions.iter().skip(i + 1) {
+            sum += distance(galaxy1, galaxy2);
+        }
+    }
+
+    Ok(sum)
+}
+
+pub fn part1(input: &str) -> Result<i64> {
+    sum_of_distances(input, 2)
+}
+
+pub fn part2(input: &str) -> Result<i64> {
+    sum_of_distances(input, EXPANSION.get()?)
+}
+
+pub fn day() -> Day {
+    Day {
+        number: 11,
+        dir: env!("CARGO_MANIFEST_DIR"),
+        part1: Some(|input| Ok(part1(input)?.to_string())),
+        part2: Some(|input| Ok(part2(input)?.to_string())),
+        generate: Some(gen::generate),
+        render: None,
+        dot: None,
+        parse: None,
+        params: &[EXPANSION],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("example.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(EXAMPLE).unwrap(), 374);
+    }
+
+    #[test]
+    fn expansion_by_10() {
+        assert_eq!(sum_of_distances(EXAMPLE, 10).unwrap(), 1030);
+    }
+
+    #[test]
+    fn expansion_by_100() {
+        assert_eq!(sum_of_distances(EXAMPLE, 100).unwrap(), 8410);
+    }
+
+    #[test]
+    fn empty_image_is_an_error() {
+        assert!(part1("\n").is_err());
+    }
+
+    #[test]
+    fn no_expansion() {
+        assert!(sum_of_distances(EXAMPLE, 0).is_err());
+        assert_eq!(sum_of_distances(EXAMPLE, 1).unwrap(), 292);
+    }
+}
@@ -0,0 +1,54 @@
+//! Random condition records of springs.
+use common::gen::StdRng;
+use rand::Rng;
+
+/// `size` records of 5 to 20 springs. The group sizes are those of a random row of
+/// springs, some of which are then hidden behind a `?`, so every record has at least
+/// one arrangement.
+pub fn generate(rng: &mut StdRng, size: usize) -> String {
+    let mut out = String::new();
+    for _ in 0..size {
+        let length = rng.gen_range(5..=20);
+        let mut springs: Vec<bool> = (0..length).map(|_| rng.gen_bool(0.5)).collect();
+        springs[rng.gen_range(0..length)] = true;
+        let mut groups = Vec::new();
+        let mut run = 0;
+        for &broken in springs.iter().chain(&[false]) {
+            match broken {
+                true => run += 1,
+                false if run > 0 => {
+                    groups.push(run.to_string());
+                    run = 0;
+                }
+                false => {}
+            }
+        }
+
+        let unknown = rng.gen_range(0.2..0.8);
+        let record: String = springs
+            .iter()
+            .map(|&broken| match (rng.gen_bool(unknown), broken) {
+                (true, _) => '?',
+                (false, true) => '#',
+                (false, false) => '.',
+            })
+            .collect();
+        out.push_str(&format!("{} {}\n", record, groups.join(",")));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::gen::rng;
+
+    #[test]
+    fn generated_inputs_are_solved() {
+        for seed in 0..20 {
+            let input = generate(&mut rng(seed), 20);
+            assert!(crate::part1(&input).unwrap() >= 20);
+            assert!(crate::part2(&input).unwrap() >= 20);
+        }
+    }
+}
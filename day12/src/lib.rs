@@ -0,0 +1,292 @@
+pub mod gen;
+
+use anyhow::Result;
+use common::config::Param;
+use common::instrument::{Batch, Search, TimedOut};
+use common::parallel;
+use common::parse::{self, Line, ParseError};
+use common::Day;
+use log::{debug, trace};
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::sync::Mutex;
+
+const REPEAT: Param = Param::new(
+    12,
+    "repeat",
+    "5",
+    "Number of copies of each record that part 2 unfolds into",
+);
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+enum Condition {
+    Broken,
+    Unknown,
+}
+
+#[derive(Clone, Hash, PartialEq, Eq)]
+struct Group {
+    entries: Vec<Condition>,
+}
+
+impl Debug for Group {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut s = String::new();
+
+        for &c in self.entries.iter() {
+            s.push(match c {
+                Condition::Broken => '#',
+                Condition::Unknown => '?',
+            })
+        }
+
+        write!(f, "'{}'", s)
+    }
+}
+
+impl Group {
+    fn first_broken(&self) -> Option<usize> {
+        self.entries.iter().position(|&c| c == Condition::Broken)
+    }
+
+    fn is_wildcard(&self) -> bool {
+        self.entries.iter().all(|&c| c == Condition::Unknown)
+    }
+
+    fn consume(&self, num: usize) -> Vec<Group> {
+        let mut new_groups: Vec<Group> = Vec::new();
+        let num_entries = self.entries.len();
+        if num_entries < num {
+            return new_groups;
+        }
+
+        let mut max_start = num_entries - num;
+        if let Some(first) = self.first_broken() {
+            if first < max_start {
+                max_start = first
+            }
+        }
+        for start in 0..max_start + 1 {
+            if let Some(Condition::Broken) = self.entries.get(start + num) {
+                continue;
+            }
+            if start + num == num_entries {
+                new_groups.push(Group {
+                    entries: Vec::new(),
+                })
+            } else {
+                new_groups.push(Group {
+                    entries: self.entries[start + num + 1..].to_vec(),
+                });
+            }
+        }
+
+        new_groups
+    }
+}
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+struct Record {
+    groups: Vec<Group>,
+    numbers: Vec<usize>,
+}
+
+impl Record {
+    fn all_wildcard(&self) -> bool {
+        self.groups.iter().all(|group| group.is_wildcard())
+    }
+
+    fn consume(&mut self) -> Vec<Record> {
+        let group = self.groups.pop();
+        if group.is_none() {
+            return Vec::new();
+        }
+        let group = group.unwrap();
+        let num = self.numbers.pop().unwrap();
+        let mut new_records: Vec<Record> = Vec::new();
+
+        if group.is_wildcard() {
+            let mut new_numbers = self.numbers.clone();
+            new_numbers.push(num);
+            new_records.push(Record {
+                groups: self.groups.clone(),
+                numbers: new_numbers,
+            });
+        }
+
+        if num > group.entries.len() {
+            return new_records;
+        }
+        let new_groups = group.consume(num);
+        for group in new_groups {
+            let mut groups = self.groups.clone();
+            if !group.entries.is_empty() {
+                groups.push(group);
+            }
+            new_records.push(Record {
+                groups,
+                numbers: self.numbers.clone(),
+            })
+        }
+
+        new_records
+    }
+}
+
+fn find_num_records_recursive(
+    record: &Record,
+    cache: &mut HashMap<Record, usize>,
+    count: &mut Batch,
+) -> Result<usize, TimedOut> {
+    if let Some(&num) = cache.get(record) {
+        count.cache_hit();
+        return Ok(num);
+    }
+    count.cache_miss();
+    count.expand()?;
+
+    let mut num = 0;
+
+    let new_records = record.clone().consume();
+    for record in new_records {
+        if record.numbers.is_empty() {
+            if record.all_wildcard() {
+                num += 1
+            }
+        } else {
+            num += find_num_records_recursive(&record, cache, count)?;
+        }
+    }
+
+    cache.insert(record.clone(), num);
+
+    Ok(num)
+}
+
+/// Parses a record, unfolding it `repeat` times
+fn parse_line(line: Line, repeat: usize) -> Result<Record, ParseError> {
+    let (condition_part, numbers) = line.split_once(" ")?;
+    // The springs, with `None` for the operational ones that separate the groups
+    let springs = condition_part
+        .char_indices()
+        .map(|(i, c)| match c {
+            '#' => Ok(Some(Condition::Broken)),
+            '?' => Ok(Some(Condition::Unknown)),
+            '.' => Ok(None),
+            _ => Err(line.invalid(&condition_part[i..i + c.len_utf8()], "'#', '?' or '.'")),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let springs_unfolded = vec![springs; repeat].join(&Some(Condition::Unknown));
+    let groups = springs_unfolded
+        .split(Option::is_none)
+        .filter(|group| !group.is_empty())
+        .map(|group| Group {
+            entries: group.iter().flatten().copied().collect(),
+        })
+        .rev()
+        .collect::<Vec<Group>>();
+
+    let mut num_vector: Vec<usize> = Vec::new();
+    for num in numbers.split(',').rev() {
+        num_vector.push(line.parse(num, "a group size")?)
+    }
+    trace!("{} | {:?} | {:?}", line.text, groups, num_vector);
+
+    Ok(Record {
+        groups,
+        numbers: num_vector.repeat(repeat),
+    })
+}
+
+fn parse_records(input: &str, repeat: usize) -> Result<Vec<Record>, ParseError> {
+    parse::lines(input)
+        .map(|line| parse_line(line, repeat))
+        .collect()
+}
+
+fn num_arrangements(input: &str, repeat: usize) -> Result<usize> {
+    let records = parse_records(input, repeat)?;
+    let search = Search::new("arrangements");
+    // The records done so far and their arrangements, as the partial answer
+    let done = Mutex::new((0, 0));
+    let nums = parallel::map(&records, |record| {
+        let mut count = Batch::new(&search);
+        let num = find_num_records_recursive(record, &mut HashMap::new(), &mut count)?;
+        let mut done = done.lock().unwrap();
+        *done = (done.0 + 1, done.1 + num);
+        search.improve(format!(
+            "{} from {} of {} records",
+            done.1,
+            done.0,
+            records.len()
+        ));
+        Ok::<_, TimedOut>(num)
+    });
+    let nums = nums.into_iter().collect::<Result<Vec<_>, _>>()?;
+    search.finish();
+    debug!("nums {:?}", nums);
+
+    Ok(nums.iter().sum::<usize>())
+}
+
+pub fn part1(input: &str) -> Result<usize> {
+    num_arrangements(input, 1)
+}
+
+pub fn part2(input: &str) -> Result<usize> {
+    num_arrangements(input, REPEAT.get()?)
+}
+
+pub fn day() -> Day {
+    Day {
+        number: 12,
+        dir: env!("CARGO_MANIFEST_DIR"),
+        part1: Some(|input| Ok(part1(input)?.to_string())),
+        part2: Some(|input| Ok(part2(input)?.to_string())),
+        generate: Some(gen::generate),
+        render: None,
+        dot: None,
+        parse: Some(|input| {
+            parse_records(input, REPEAT.get()?)?;
+            Ok(())
+        }),
+        params: &[REPEAT],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("example.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(EXAMPLE).unwrap(), 21);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(EXAMPLE).unwrap(), 525152);
+    }
+
+    #[test]
+    fn threads_give_the_same_answer() {
+        let input = gen::generate(&mut common::gen::rng(0), 20);
+        parallel::assert_thread_independent(part2, &input);
+    }
+
+    #[test]
+    fn unknown_springs_are_an_error() {
+        let err = part1("???.### 1,1,3\n#?x 1").unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<ParseError>(),
+            Some(&ParseError::Invalid {
+                line: 2,
+                column: 3,
+                text: "x".to_string(),
+                expected: "'#', '?' or '.'".to_string(),
+            })
+        );
+    }
+}
@@ -0,0 +1,114 @@
+//! Random patterns of ash and rocks, each with one line of reflection and one line that
+//! reflects up to a smudge.
+use common::gen::StdRng;
+use rand::Rng;
+
+type Pattern = Vec<Vec<bool>>;
+
+fn transpose(pattern: &Pattern) -> Pattern {
+    (0..pattern[0].len())
+        .map(|x| pattern.iter().map(|row| row[x]).collect())
+        .collect()
+}
+
+/// The number of cells that differ between the lines above `at` and their reflection
+fn defects(lines: &Pattern, at: usize) -> usize {
+    let reach = at.min(lines.len() - at);
+    (0..reach)
+        .map(|i| {
+            let (a, b) = (&lines[at - 1 - i], &lines[at + i]);
+            a.iter().zip(b).filter(|(a, b)| a != b).count()
+        })
+        .sum()
+}
+
+/// The lines of reflection of the rows with `num_defects` defects, as in the summary
+fn reflections(pattern: &Pattern, num_defects: usize) -> Vec<usize> {
+    let columns =
+        (1..pattern[0].len()).filter(|&at| defects(&transpose(pattern), at) == num_defects);
+    let rows = (1..pattern.len())
+        .filter(|&at| defects(pattern, at) == num_defects)
+        .map(|at| 100 * at);
+    columns.chain(rows).collect()
+}
+
+/// A pattern with rows reflected below row `r` and columns reflected right of column
+/// `c`, except for a single smudge that only breaks the reflection of the columns.
+/// Returns the pattern and its summaries in both parts.
+fn pattern(rng: &mut StdRng) -> (Pattern, usize, usize) {
+    loop {
+        let (width, height) = (rng.gen_range(5..=15), rng.gen_range(5..=15));
+        let r = rng.gen_range(1..=(height - 1) / 2);
+        let c = rng.gen_range(1..width);
+        let reach = c.min(width - c);
+        let mut pattern: Pattern = (0..height)
+            .map(|_| {
+                let mut row: Vec<bool> = (0..width).map(|_| rng.gen_bool(0.5)).collect();
+                for i in 0..reach {
+                    row[c + i] = row[c - 1 - i];
+                }
+                row
+            })
+            .collect();
+        for i in 0..r {
+            pattern[r + i] = pattern[r - 1 - i].clone();
+        }
+        // Below the reflected rows, so the smudge does not break their reflection
+        let y = rng.gen_range(2 * r..height);
+        let x = rng.gen_range(c - reach..c + reach);
+        pattern[y][x] = !pattern[y][x];
+
+        let (mut part1, mut part2) = (100 * r, c);
+        if rng.gen_bool(0.5) {
+            pattern = transpose(&pattern);
+            (part1, part2) = (r, 100 * c);
+        }
+        if reflections(&pattern, 0) == [part1] && reflections(&pattern, 1) == [part2] {
+            return (pattern, part1, part2);
+        }
+    }
+}
+
+/// `size` patterns. See [`notes`].
+pub fn generate(rng: &mut StdRng, size: usize) -> String {
+    notes(rng, size).0
+}
+
+/// The notes and the answers to both parts.
+pub fn notes(rng: &mut StdRng, size: usize) -> (String, usize, usize) {
+    let (mut part1, mut part2) = (0, 0);
+    let blocks: Vec<String> = (0..size)
+        .map(|_| {
+            let (pattern, summary1, summary2) = pattern(rng);
+            part1 += summary1;
+            part2 += summary2;
+            pattern
+                .iter()
+                .map(|row| {
+                    let mut line: String = row
+                        .iter()
+                        .map(|&rock| if rock { '#' } else { '.' })
+                        .collect();
+                    line.push('\n');
+                    line
+                })
+                .collect()
+        })
+        .collect();
+    (blocks.join("\n"), part1, part2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::gen::rng;
+
+    #[test]
+    fn planted_answers() {
+        for seed in 0..10 {
+            let (input, part1, part2) = notes(&mut rng(seed), 20);
+            assert_eq!(crate::part1(&input).unwrap(), part1);
+            assert_eq!(crate::part2(&input).unwrap(), part2);
+        }
+    }
+}
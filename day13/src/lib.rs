@@ -0,0 +1,192 @@
+pub mod gen;
+
+use anyhow::Result;
+use common::grid::Grid;
+use common::parse;
+use common::Day;
+use log::{debug, trace};
+
+/// Most tiles in a row or column, so that it fits in a binary number
+const MAX_LEN: usize = u128::BITS as usize;
+
+/// Reads a row or column of the pattern as a binary number, least significant bit first
+fn bits_to_int<'a>(bits: impl Iterator<Item = &'a bool>) -> u128 {
+    bits.enumerate().map(|(i, &bit)| (bit as u128) << i).sum()
+}
+
+fn find_value(vec: Vec<u128>) -> Option<usize> {
+    for i in 0..vec.len() - 1 {
+        if vec[i] == vec[i + 1] {
+            let len = std::cmp::min(i + 1, vec.len() - i - 1);
+            let mut reversed = vec[i + 1..i + len + 1].to_vec();
+            reversed.reverse();
+            if reversed == vec[i + 1 - len..i + 1].to_vec() {
+                return Some(i + 1);
+            }
+        }
+    }
+
+    None
+}
+fn equal_up_to_smudge(vec1: &[u128], vec2: &[u128]) -> bool {
+    let mut smudge_found: bool = false;
+    for (x, y) in vec1.iter().zip(vec2.iter()) {
+        if x != y {
+            if smudge_found {
+                return false;
+            }
+            if differ_in_one_bit(*x, *y) {
+                smudge_found = true;
+            } else {
+                return false;
+            }
+        }
+    }
+    smudge_found
+}
+
+/// Whether the lines differ in exactly one spot. Their difference being a power of two
+/// is not enough: 4 - 3 = 1, but 100 and 011 differ everywhere.
+fn differ_in_one_bit(x: u128, y: u128) -> bool {
+    (x ^ y).count_ones() == 1
+}
+
+fn find_value_smudge(vec: Vec<u128>) -> Option<usize> {
+    for i in 0..vec.len() - 1 {
+        if vec[i] == vec[i + 1] || differ_in_one_bit(vec[i], vec[i + 1]) {
+            let len = std::cmp::min(i + 1, vec.len() - i - 1);
+            let mut reversed = vec[i + 1..i + len + 1].to_vec();
+            reversed.reverse();
+            let normal = vec[i + 1 - len..i + 1].to_vec();
+            if equal_up_to_smudge(&reversed, &normal) {
+                trace!("{} {:?} {:?}", i + 1, reversed, normal);
+                return Some(i + 1);
+            }
+        }
+    }
+
+    None
+}
+
+/// The rows or the columns of one pattern, read as binary numbers
+type Lines = Vec<u128>;
+
+/// Parses the patterns into their row and column values, reading every row and column
+/// as a binary number
+fn parse_patterns(input: &str) -> Result<(Vec<Lines>, Vec<Lines>)> {
+    let mut row_values = Vec::new();
+    let mut col_values = Vec::new();
+    let mut first_line = 0;
+    for block in input.split("\n\n") {
+        let pattern = Grid::parse(block, |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        })
+        .map_err(|err| err.shifted(first_line))?;
+        let mut lines = parse::lines(block);
+        if let Some(line) = lines.next().filter(|_| pattern.width() > MAX_LEN) {
+            let expected = format!("at most {} tiles in a row", MAX_LEN);
+            let err = line.invalid(&line.text[MAX_LEN..], expected);
+            return Err(err.shifted(first_line).into());
+        }
+        if let Some(line) = lines.nth(MAX_LEN - 1) {
+            let expected = format!("at most {} rows in a pattern", MAX_LEN);
+            return Err(line.invalid(line.text, expected).shifted(first_line).into());
+        }
+        first_line += block.lines().count() + 1;
+        trace!(
+            "array:\n{}",
+            pattern.map(|&rock| if rock { '#' } else { '.' })
+        );
+        row_values.push(pattern.rows().map(|row| bits_to_int(row.iter())).collect());
+        col_values.push(pattern.columns().map(bits_to_int).collect());
+    }
+    debug!("row_values: {:?}", row_values);
+    debug!("col_values: {:?}", col_values);
+
+    Ok((row_values, col_values))
+}
+
+fn summarize(input: &str, find_value: fn(Vec<u128>) -> Option<usize>) -> Result<usize> {
+    let (row_values, col_values) = parse_patterns(input)?;
+
+    let mut sum = 0;
+    for (row, col) in row_values.iter().zip(col_values.iter()) {
+        if let Some(num) = find_value(col.clone()) {
+            sum += num
+        }
+        if let Some(num) = find_value(row.clone()) {
+            sum += num * 100
+        }
+    }
+
+    Ok(sum)
+}
+
+pub fn part1(input: &str) -> Result<usize> {
+    summarize(input, find_value)
+}
+
+pub fn part2(input: &str) -> Result<usize> {
+    summarize(input, find_value_smudge)
+}
+
+pub fn day() -> Day {
+    Day {
+        number: 13,
+        dir: env!("CARGO_MANIFEST_DIR"),
+        part1: Some(|input| Ok(part1(input)?.to_string())),
+        part2: Some(|input| Ok(part2(input)?.to_string())),
+        generate: Some(gen::generate),
+        render: None,
+        dot: None,
+        parse: Some(|input| {
+            parse_patterns(input)?;
+            Ok(())
+        }),
+        params: &[],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("example.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(EXAMPLE).unwrap(), 405);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(EXAMPLE).unwrap(), 400);
+    }
+
+    #[test]
+    fn error_lines_count_from_the_start_of_the_input() {
+        let err = parse_patterns("#.\n.#\n\n##\n#x").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 5, column 2: expected a grid tile, found \"x\""
+        );
+    }
+
+    #[test]
+    fn wide_and_tall_patterns() {
+        let row = |rock| format!("{}{}....\n", ".".repeat(35), if rock { '#' } else { '.' });
+        let wide = [row(true), row(false), row(false), row(true)].concat();
+        assert_eq!(part1(&wide).unwrap(), 201);
+        let tall = format!("{}#.\n", ".#\n".repeat(39));
+        assert_eq!(part1(&format!("{}\n{}", tall, tall)).unwrap(), 200);
+        let err = parse_patterns(&format!("#\n\n{}", ".".repeat(130))).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 3, column 129: expected at most 128 tiles in a row, found \"..\""
+        );
+        assert!(parse_patterns(&".\n".repeat(129)).is_err());
+        assert!(parse_patterns(&".\n".repeat(128)).is_ok());
+    }
+}
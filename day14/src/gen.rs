@@ -0,0 +1,36 @@
+//! Random platforms of round and cube-shaped rocks.
+use common::gen::StdRng;
+use rand::Rng;
+
+/// A square platform of `size` lines, a fifth of it round rocks and a tenth cubes.
+pub fn generate(rng: &mut StdRng, size: usize) -> String {
+    let mut out = String::new();
+    for _ in 0..size {
+        for _ in 0..size {
+            out.push(match rng.gen_range(0..10) {
+                0 | 1 => 'O',
+                2 => '#',
+                _ => '.',
+            });
+        }
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::gen::rng;
+
+    #[test]
+    fn generated_inputs_are_solved() {
+        for seed in 0..10 {
+            let input = generate(&mut rng(seed), 30);
+            let num_round = input.matches('O').count();
+            // Every rock weighs at most the number of rows
+            assert!(crate::part1(&input).unwrap() <= 30 * num_round);
+            assert!(crate::part2(&input).unwrap() <= 30 * num_round);
+        }
+    }
+}
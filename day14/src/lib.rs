@@ -0,0 +1,188 @@
+use std::fmt::{self, Debug};
+
+pub mod gen;
+pub mod render;
+
+use anyhow::Result;
+use common::config::Param;
+use common::cycle;
+use common::grid::Grid;
+use common::Day;
+
+const CYCLES: Param = Param::new(
+    14,
+    "cycles",
+    "1000000000",
+    "Number of spin cycles of part 2",
+);
+
+#[derive(Eq, PartialEq, Clone, Copy, Hash)]
+enum Rock {
+    Round,
+    Square,
+    Empty,
+}
+
+impl fmt::Display for Rock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let c = match self {
+            Rock::Round => 'O',
+            Rock::Square => '#',
+            Rock::Empty => '.',
+        };
+        write!(f, "{}", c)
+    }
+}
+
+#[derive(Eq, PartialEq, Clone, Hash)]
+struct Table {
+    rocks: Grid<Rock>,
+}
+
+impl Table {
+    fn compute_load(&self) -> usize {
+        self.rocks
+            .find_all(&Rock::Round)
+            .map(|(_, y)| self.rocks.height() - y)
+            .sum()
+    }
+    fn move_up(&mut self) {
+        for x in 0..self.rocks.width() {
+            let mut target_y = 0;
+            for y in 0..self.rocks.height() {
+                match self.rocks[(x, y)] {
+                    Rock::Square => target_y = y + 1,
+                    Rock::Round => {
+                        self.rocks.swap((x, y), (x, target_y));
+                        target_y += 1;
+                    }
+                    Rock::Empty => {}
+                }
+            }
+        }
+    }
+    fn move_down(&mut self) {
+        for x in 0..self.rocks.width() {
+            let mut target_y = self.rocks.height() - 1;
+            for y in (0..self.rocks.height()).rev() {
+                match self.rocks[(x, y)] {
+                    Rock::Square => target_y = y.saturating_sub(1),
+                    Rock::Round => {
+                        self.rocks.swap((x, y), (x, target_y));
+                        target_y = target_y.saturating_sub(1);
+                    }
+                    Rock::Empty => {}
+                }
+            }
+        }
+    }
+    fn move_right(&mut self) {
+        for y in 0..self.rocks.height() {
+            let mut target_x = self.rocks.width() - 1;
+            for x in (0..self.rocks.width()).rev() {
+                match self.rocks[(x, y)] {
+                    Rock::Square => target_x = x.saturating_sub(1),
+                    Rock::Round => {
+                        self.rocks.swap((x, y), (target_x, y));
+                        target_x = target_x.saturating_sub(1);
+                    }
+                    Rock::Empty => {}
+                }
+            }
+        }
+    }
+    fn move_left(&mut self) {
+        for y in 0..self.rocks.height() {
+            let mut target_x = 0;
+            for x in 0..self.rocks.width() {
+                match self.rocks[(x, y)] {
+                    Rock::Square => target_x = x + 1,
+                    Rock::Round => {
+                        self.rocks.swap((x, y), (target_x, y));
+                        target_x += 1;
+                    }
+                    Rock::Empty => {}
+                }
+            }
+        }
+    }
+
+    fn cycle(&mut self) {
+        self.move_up();
+        self.move_left();
+        self.move_down();
+        self.move_right();
+    }
+}
+
+impl Debug for Table {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.rocks)
+    }
+}
+
+fn parse_input(input: &str) -> Result<Table> {
+    let rocks = Grid::parse(input, |c| match c {
+        'O' => Some(Rock::Round),
+        '#' => Some(Rock::Square),
+        '.' => Some(Rock::Empty),
+        _ => None,
+    })?;
+
+    Ok(Table { rocks })
+}
+
+pub fn part1(input: &str) -> Result<usize> {
+    let mut table = parse_input(input)?;
+    table.move_up();
+
+    Ok(table.compute_load())
+}
+
+/// The spin cycles soon repeat the same tables, so the billionth one is one of those
+pub fn part2(input: &str) -> Result<usize> {
+    let table = parse_input(input)?;
+    let table = cycle::state_after(
+        table,
+        Table::cycle,
+        Table::clone,
+        CYCLES.get()?,
+        cycle::Mode::Hash,
+    );
+
+    Ok(table.compute_load())
+}
+
+pub fn day() -> Day {
+    Day {
+        number: 14,
+        dir: env!("CARGO_MANIFEST_DIR"),
+        part1: Some(|input| Ok(part1(input)?.to_string())),
+        part2: Some(|input| Ok(part2(input)?.to_string())),
+        generate: Some(gen::generate),
+        render: Some(render::render),
+        dot: None,
+        parse: Some(|input| {
+            parse_input(input)?;
+            Ok(())
+        }),
+        params: &[CYCLES],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("example.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(EXAMPLE).unwrap(), 136);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(EXAMPLE).unwrap(), 64);
+    }
+}
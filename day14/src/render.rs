@@ -0,0 +1,34 @@
+//! Three tables side by side: as given, tilted north for part 1, and after the spin
+//! cycles of part 2. Round rocks are yellow and square rocks dark grey.
+use crate::{parse_input, Rock, Table, CYCLES};
+use anyhow::Result;
+use common::cycle;
+use common::render::{Drawing, Rgb};
+
+pub fn render(input: &str) -> Result<Drawing> {
+    let table = parse_input(input)?;
+    let mut tilted = table.clone();
+    tilted.move_up();
+    let spun = cycle::state_after(
+        table.clone(),
+        Table::cycle,
+        Table::clone,
+        CYCLES.get()?,
+        cycle::Mode::Hash,
+    );
+
+    let (width, height) = (table.rocks.width(), table.rocks.height());
+    // A column of background between the tables
+    let mut drawing = Drawing::grid(3 * width + 2, height, Rgb::WHITE);
+    for (i, table) in [table, tilted, spun].iter().enumerate() {
+        for ((x, y), rock) in table.rocks.iter() {
+            let color = match rock {
+                Rock::Round => Rgb::YELLOW,
+                Rock::Square => Rgb(70, 70, 70),
+                Rock::Empty => continue,
+            };
+            drawing.cell((i * (width + 1) + x, y), color);
+        }
+    }
+    Ok(drawing)
+}
@@ -0,0 +1,38 @@
+//! Random initialization sequences.
+use common::gen::{names, StdRng};
+use rand::Rng;
+
+/// A sequence of `size` steps on a third as many labels, of which many share a box.
+pub fn generate(rng: &mut StdRng, size: usize) -> String {
+    let labels: Vec<String> = (0..size / 3 + 1)
+        .map(|_| {
+            let len = rng.gen_range(2..=6);
+            names(rng, 1, len).remove(0)
+        })
+        .collect();
+    let steps: Vec<String> = (0..size)
+        .map(|_| {
+            let label = &labels[rng.gen_range(0..labels.len())];
+            match rng.gen_bool(0.7) {
+                true => format!("{}={}", label, rng.gen_range(1..=9)),
+                false => format!("{}-", label),
+            }
+        })
+        .collect();
+    format!("{}\n", steps.join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::gen::rng;
+
+    #[test]
+    fn generated_inputs_are_solved() {
+        for seed in 0..20 {
+            let input = generate(&mut rng(seed), 100);
+            assert!(crate::part1(&input).unwrap() < 100 * 256);
+            crate::part2(&input).unwrap();
+        }
+    }
+}
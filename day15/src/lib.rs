@@ -0,0 +1,149 @@
+pub mod gen;
+
+use anyhow::Result;
+use common::parse::{self, Line, ParseError};
+use common::Day;
+use std::collections::HashMap;
+
+fn hash(state: u8, ch: char) -> u8 {
+    state.wrapping_add(ch as u8).wrapping_mul(17)
+}
+fn hash_string(input: &str) -> usize {
+    let state = 0u8;
+    input.chars().fold(state, hash) as usize
+}
+
+#[derive(Eq, PartialEq, Hash, Debug)]
+struct Lens {
+    label: String,
+    focal_length: u8,
+}
+
+struct Boxes {
+    boxes: [Box; 256],
+}
+impl Boxes {
+    /// Applies one step of the initialization sequence, which is on a single `line`
+    fn insert(&mut self, line: Line, input: &str) -> Result<(), ParseError> {
+        if let Some((label, focal_length)) = input.split_once('=') {
+            let hash = hash_string(label);
+            let lens = Lens {
+                label: label.to_string(),
+                focal_length: line.parse(focal_length, "a focal length")?,
+            };
+            self.boxes[hash].add_lens(lens);
+        } else if let Some(label) = input.strip_suffix('-') {
+            let hash = hash_string(label);
+            self.boxes[hash].remove_lens(label)
+        } else {
+            return Err(line.invalid(input, "\"<label>=<focal length>\" or \"<label>-\""));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Eq, PartialEq, Hash, Clone, Debug)]
+struct MapEntry {
+    counter: usize,
+    focal_length: u8,
+}
+
+#[derive(Clone, Debug)]
+struct Box {
+    insertion_counter: usize,
+    lenses: HashMap<String, MapEntry>,
+}
+
+impl Box {
+    fn new() -> Box {
+        Box {
+            insertion_counter: 0,
+            lenses: HashMap::new(),
+        }
+    }
+
+    fn add_lens(&mut self, lens: Lens) {
+        let entry = self.lenses.remove(&lens.label).unwrap_or({
+            self.insertion_counter += 1;
+            MapEntry {
+                counter: self.insertion_counter,
+                focal_length: 0,
+            }
+        });
+        self.lenses.insert(
+            lens.label,
+            MapEntry {
+                counter: entry.counter,
+                focal_length: lens.focal_length,
+            },
+        );
+    }
+    fn remove_lens(&mut self, label: &str) {
+        self.lenses.remove(label);
+    }
+
+    fn focusing_power(&self) -> usize {
+        let mut entries: Vec<&MapEntry> = self.lenses.values().collect();
+        entries.sort_by_key(|entry| entry.counter);
+        entries
+            .iter()
+            .enumerate()
+            .map(|(i, entry)| (i + 1) * (entry.focal_length as usize))
+            .sum()
+    }
+}
+
+pub fn part1(input: &str) -> Result<usize> {
+    Ok(input.trim_end().split(',').map(hash_string).sum())
+}
+
+pub fn part2(input: &str) -> Result<usize> {
+    let mut boxes = Boxes {
+        boxes: std::array::from_fn(|_| Box::new()),
+    };
+    let line = parse::lines(input)
+        .next()
+        .ok_or_else(|| ParseError::incomplete("initialization sequence"))?;
+    for piece in line.text.trim_end().split(',') {
+        boxes.insert(line, piece)?;
+    }
+    let sum: usize = boxes
+        .boxes
+        .iter()
+        .enumerate()
+        .map(|(i, lens_box)| (i + 1) * lens_box.focusing_power())
+        .sum();
+
+    Ok(sum)
+}
+
+pub fn day() -> Day {
+    Day {
+        number: 15,
+        dir: env!("CARGO_MANIFEST_DIR"),
+        part1: Some(|input| Ok(part1(input)?.to_string())),
+        part2: Some(|input| Ok(part2(input)?.to_string())),
+        generate: Some(gen::generate),
+        render: None,
+        dot: None,
+        parse: None,
+        params: &[],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("example.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(EXAMPLE).unwrap(), 1320);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(EXAMPLE).unwrap(), 145);
+    }
+}
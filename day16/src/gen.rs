@@ -0,0 +1,36 @@
+//! Random contraptions of mirrors and splitters.
+use common::gen::StdRng;
+use rand::Rng;
+
+const DEVICES: &[u8] = b"/\\|-";
+
+/// A square contraption of `size` lines, with a device on one in ten tiles.
+pub fn generate(rng: &mut StdRng, size: usize) -> String {
+    let mut out = String::new();
+    for _ in 0..size {
+        for _ in 0..size {
+            out.push(match rng.gen_bool(0.1) {
+                true => DEVICES[rng.gen_range(0..DEVICES.len())] as char,
+                false => '.',
+            });
+        }
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::gen::rng;
+
+    #[test]
+    fn generated_inputs_are_solved() {
+        for seed in 0..10 {
+            let input = generate(&mut rng(seed), 30);
+            let part1 = crate::part1(&input).unwrap();
+            assert!((1..=30 * 30).contains(&part1));
+            assert!(crate::part2(&input).unwrap() >= part1);
+        }
+    }
+}
@@ -0,0 +1,233 @@
+pub mod gen;
+pub mod render;
+
+use anyhow::{anyhow, Result};
+use common::geom::Direction;
+use common::grid::Grid;
+use common::parallel;
+use common::parse::ParseError;
+use common::Day;
+use log::{debug, trace};
+use std::collections::HashSet;
+
+#[derive(Hash, PartialEq, Eq, Debug, Clone)]
+struct TileInfo {
+    x: usize,
+    y: usize,
+    direction: Direction,
+}
+
+#[derive(Hash, PartialEq, Eq, Debug)]
+enum Mirror {
+    Slash,
+    BackSlash,
+    Vertical,
+    Horizontal,
+}
+
+fn find_next_tile_pos(tile: &TileInfo, mirrors: &Mirrors) -> Option<(usize, usize)> {
+    mirrors.step((tile.x, tile.y), tile.direction)
+}
+
+fn parse_tile(tile: TileInfo, mirrors: &Mirrors, is_first: bool) -> Option<Vec<TileInfo>> {
+    let (x, y) = if is_first {
+        (tile.x, tile.y)
+    } else {
+        find_next_tile_pos(&tile, mirrors)?
+    };
+
+    let mut new_directions: Vec<Direction> = Vec::new();
+
+    if let Some(mirror) = &mirrors[(x, y)] {
+        match mirror {
+            // Splitters send the beam both ways when hit on their flat side
+            Mirror::Vertical | Mirror::Horizontal
+                if tile.direction.is_horizontal() == (*mirror == Mirror::Vertical) =>
+            {
+                new_directions.push(tile.direction.turn_left());
+                new_directions.push(tile.direction.turn_right());
+            }
+            Mirror::Vertical | Mirror::Horizontal => new_directions.push(tile.direction),
+            Mirror::Slash if tile.direction.is_horizontal() => {
+                new_directions.push(tile.direction.turn_left())
+            }
+            Mirror::Slash => new_directions.push(tile.direction.turn_right()),
+            Mirror::BackSlash if tile.direction.is_horizontal() => {
+                new_directions.push(tile.direction.turn_right())
+            }
+            Mirror::BackSlash => new_directions.push(tile.direction.turn_left()),
+        }
+    } else {
+        new_directions.push(tile.direction);
+    }
+
+    Some(
+        new_directions
+            .iter()
+            .map(|&direction| TileInfo { x, y, direction })
+            .collect(),
+    )
+}
+
+/// The tiles a beam entering at `start_tile` passes through
+fn energized(start_tile: TileInfo, mirrors: &Mirrors) -> HashSet<(usize, usize)> {
+    let mut queue: Vec<TileInfo> = vec![start_tile.clone()];
+
+    let mut found_squares: HashSet<(usize, usize)> = HashSet::new();
+    found_squares.insert((start_tile.x, start_tile.y));
+
+    let mut found_tiles: HashSet<TileInfo> = HashSet::new();
+    found_tiles.insert(start_tile.clone());
+
+    let mut is_first = true;
+    while let Some(tile) = queue.pop() {
+        if let Some(new_tiles) = parse_tile(tile, mirrors, is_first) {
+            for new_tile in new_tiles {
+                found_squares.insert((new_tile.x, new_tile.y));
+                if !found_tiles.insert(new_tile.clone()) && !is_first {
+                    continue;
+                }
+                queue.push(new_tile);
+            }
+            is_first = false;
+        }
+    }
+    trace!(
+        "({}, {}): {}",
+        start_tile.x,
+        start_tile.y,
+        found_squares.len()
+    );
+
+    found_squares
+}
+
+fn num_energized(start_tile: TileInfo, mirrors: &Mirrors) -> usize {
+    energized(start_tile, mirrors).len()
+}
+
+type Mirrors = Grid<Option<Mirror>>;
+
+fn parse_mirrors(input: &str) -> Result<Mirrors, ParseError> {
+    Grid::parse(input, |ch| match ch {
+        '/' => Some(Some(Mirror::Slash)),
+        '\\' => Some(Some(Mirror::BackSlash)),
+        '|' => Some(Some(Mirror::Vertical)),
+        '-' => Some(Some(Mirror::Horizontal)),
+        '.' => Some(None),
+        _ => None,
+    })
+}
+
+pub fn part1(input: &str) -> Result<usize> {
+    let mirrors = parse_mirrors(input)?;
+    let start_tile = TileInfo {
+        x: 0,
+        y: 0,
+        direction: Direction::Right,
+    };
+
+    Ok(num_energized(start_tile, &mirrors))
+}
+
+/// The tiles along the edges, with the beam entering from outside
+fn edge_tiles(mirrors: &Mirrors) -> Vec<TileInfo> {
+    let (size_x, size_y) = (mirrors.width(), mirrors.height());
+
+    let mut edge_tiles: Vec<TileInfo> = Vec::new();
+
+    // Left edge
+    edge_tiles.append(
+        &mut (0..size_y)
+            .map(|y| TileInfo {
+                x: 0,
+                y,
+                direction: Direction::Right,
+            })
+            .collect(),
+    );
+
+    // Right edge
+    edge_tiles.append(
+        &mut (0..size_y)
+            .map(|y| TileInfo {
+                x: size_x - 1,
+                y,
+                direction: Direction::Left,
+            })
+            .collect(),
+    );
+
+    // Top edge
+    edge_tiles.append(
+        &mut (0..size_x)
+            .map(|x| TileInfo {
+                x,
+                y: 0,
+                direction: Direction::Down,
+            })
+            .collect(),
+    );
+
+    // Bottom edge
+    edge_tiles.append(
+        &mut (0..size_x)
+            .map(|x| TileInfo {
+                x,
+                y: size_y - 1,
+                direction: Direction::Up,
+            })
+            .collect(),
+    );
+    debug!("Edge tiles: {:?}", edge_tiles);
+    edge_tiles
+}
+
+pub fn part2(input: &str) -> Result<usize> {
+    let mirrors = parse_mirrors(input)?;
+    let edge_tiles = edge_tiles(&mirrors);
+    parallel::map(&edge_tiles, |tile| num_energized(tile.clone(), &mirrors))
+        .into_iter()
+        .max()
+        .ok_or_else(|| anyhow!("empty input"))
+}
+
+pub fn day() -> Day {
+    Day {
+        number: 16,
+        dir: env!("CARGO_MANIFEST_DIR"),
+        part1: Some(|input| Ok(part1(input)?.to_string())),
+        part2: Some(|input| Ok(part2(input)?.to_string())),
+        generate: Some(gen::generate),
+        render: Some(render::render),
+        dot: None,
+        parse: Some(|input| {
+            parse_mirrors(input)?;
+            Ok(())
+        }),
+        params: &[],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("example.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(EXAMPLE).unwrap(), 46);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(EXAMPLE).unwrap(), 51);
+    }
+
+    #[test]
+    fn threads_give_the_same_answer() {
+        let input = gen::generate(&mut common::gen::rng(0), 20);
+        parallel::assert_thread_independent(part2, &input);
+    }
+}
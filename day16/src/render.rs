@@ -0,0 +1,34 @@
+//! A heatmap of how many of the beams of part 2 pass through every tile, from dark blue
+//! for none to yellow for the busiest tile, with the mirrors and splitters in grey.
+use crate::{edge_tiles, energized, parse_mirrors};
+use anyhow::Result;
+use common::grid::Grid;
+use common::parallel;
+use common::render::{Drawing, Rgb};
+
+pub fn render(input: &str) -> Result<Drawing> {
+    let mirrors = parse_mirrors(input)?;
+    let starts = edge_tiles(&mirrors);
+    let beams = parallel::map(&starts, |tile| energized(tile.clone(), &mirrors));
+    let mut heat = Grid::filled(mirrors.width(), mirrors.height(), 0);
+    for tile in beams.iter().flatten() {
+        heat[*tile] += 1;
+    }
+
+    let busiest = heat
+        .iter()
+        .map(|(_, &count)| count)
+        .max()
+        .unwrap_or(0)
+        .max(1);
+
+    let mut drawing = Drawing::grid(mirrors.width(), mirrors.height(), Rgb::BLACK);
+    for (pos, &count) in heat.iter() {
+        let color = match mirrors[pos] {
+            Some(_) => Rgb::GREY,
+            None => Rgb(20, 20, 90).mix(Rgb::YELLOW, count as f64 / busiest as f64),
+        };
+        drawing.cell(pos, color);
+    }
+    Ok(drawing)
+}
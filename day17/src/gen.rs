@@ -0,0 +1,35 @@
+//! Random city blocks of heat loss.
+use common::gen::StdRng;
+use rand::Rng;
+
+/// A square city of `size` lines, at least 5 so that the ultra crucible can get to the
+/// factory.
+pub fn generate(rng: &mut StdRng, size: usize) -> String {
+    let size = size.max(5);
+    let mut out = String::new();
+    for _ in 0..size {
+        for _ in 0..size {
+            out.push(char::from(b'0' + rng.gen_range(1..=9)));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::gen::rng;
+
+    #[test]
+    fn generated_inputs_are_solved() {
+        for seed in 0..10 {
+            let input = generate(&mut rng(seed), 20);
+            // At least one block per step, and at most nine
+            let part1 = crate::part1(&input).unwrap();
+            assert!((38..=9 * 38).contains(&part1));
+            let part2 = crate::part2(&input).unwrap();
+            assert!(part2 >= 38);
+        }
+    }
+}
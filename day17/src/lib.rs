@@ -0,0 +1,256 @@
+pub mod gen;
+pub mod render;
+
+use anyhow::{anyhow, bail, Result};
+use common::config::Param;
+use common::geom::{Direction, UPos};
+use common::graph;
+use common::grid::Grid;
+use common::instrument::Search;
+use common::Day;
+
+#[derive(Eq, PartialEq, Hash, Debug, Clone)]
+struct State {
+    position: UPos,
+    direction: Direction,
+    straight_steps: usize,
+}
+
+fn step_state(state: &State, loss_map: &Grid<usize>) -> Option<State> {
+    let position = loss_map.step(state.position.into(), state.direction)?;
+
+    Some(State {
+        position: position.into(),
+        direction: state.direction,
+        straight_steps: state.straight_steps + 1,
+    })
+}
+
+fn turn_state(state: &State) -> (State, State) {
+    let new_dirs = (state.direction.turn_left(), state.direction.turn_right());
+
+    (
+        State {
+            position: state.position,
+            direction: new_dirs.0,
+            straight_steps: 0,
+        },
+        State {
+            position: state.position,
+            direction: new_dirs.1,
+            straight_steps: 0,
+        },
+    )
+}
+
+/// The crucible has to move at least `min_straight` and at most `max_straight` blocks in
+/// a straight line before it can or must turn.
+#[derive(Clone, Copy)]
+struct Crucible {
+    min_straight: usize,
+    max_straight: usize,
+}
+
+impl Crucible {
+    fn from_params(min_straight: Param, max_straight: Param) -> Result<Crucible> {
+        let crucible = Crucible {
+            min_straight: min_straight.get()?,
+            max_straight: max_straight.get()?,
+        };
+        if crucible.min_straight > crucible.max_straight {
+            bail!("{} is more than {}", min_straight.name, max_straight.name);
+        }
+        Ok(crucible)
+    }
+}
+
+fn propose_new_entries(state: State, crucible: Crucible, loss_map: &Grid<usize>) -> Vec<State> {
+    let mut out = Vec::new();
+
+    if state.straight_steps < crucible.max_straight {
+        if let Some(new) = step_state(&state, loss_map) {
+            out.push(new);
+        }
+    }
+    if state.straight_steps >= crucible.min_straight {
+        let (turn1, turn2) = turn_state(&state);
+        if let Some(new) = step_state(&turn1, loss_map) {
+            out.push(new);
+        }
+        if let Some(new) = step_state(&turn2, loss_map) {
+            out.push(new);
+        }
+    }
+
+    out
+}
+
+/// The least heat lost on the way from every block to the bottom right corner, for a
+/// crucible that can turn whenever it likes. A crucible that cannot never loses less,
+/// which makes these a good guess for the search.
+fn shortest_possible_paths(loss_map: &Grid<usize>) -> Grid<usize> {
+    let end = (loss_map.width() - 1, loss_map.height() - 1);
+    // Backwards from the end, so stepping from a block onto `pos` costs the loss at `pos`
+    let backwards = graph::from_fn(|&pos: &(usize, usize)| {
+        loss_map
+            .neighbours4(pos)
+            .map(|previous| (previous, loss_map[pos]))
+            .collect()
+    });
+    let paths = graph::dijkstra(&backwards, [(end, 0)]);
+
+    let mut out = Grid::filled(loss_map.width(), loss_map.height(), usize::MAX);
+    for (&pos, loss) in paths.iter() {
+        out[pos] = loss;
+    }
+    out
+}
+
+fn parse_loss_map(input: &str) -> Result<Grid<usize>> {
+    Ok(Grid::parse(input, |ch| {
+        ch.to_digit(10).map(|val| val as usize)
+    })?)
+}
+
+/// The least heat lost on the way to the bottom right corner, and the blocks along the
+/// way from the top left corner
+fn best_path(loss_map: &Grid<usize>, crucible: Crucible) -> Result<(usize, Vec<UPos>)> {
+    let end = UPos::new(loss_map.width() - 1, loss_map.height() - 1);
+    let shortest_paths = shortest_possible_paths(loss_map);
+
+    let moves = graph::from_fn(|state: &State| {
+        propose_new_entries(state.clone(), crucible, loss_map)
+            .into_iter()
+            .map(|new_state| {
+                let loss = loss_map[new_state.position];
+                (new_state, loss)
+            })
+            .collect()
+    });
+    let start = UPos::new(0, 0);
+    if end == start {
+        return Ok((0, vec![start]));
+    }
+    // The crucible starts out in the top left corner, but may head either way that stays
+    // on the map
+    let starts = [Direction::Right, Direction::Down]
+        .into_iter()
+        .filter_map(|direction| {
+            let position = loss_map.step(start.into(), direction)?.into();
+            let state = State {
+                position,
+                direction,
+                straight_steps: 1,
+            };
+            Some((state, loss_map[position]))
+        });
+
+    let search = Search::new("crucible path");
+    let (loss, states) = graph::a_star(
+        &moves,
+        starts,
+        |state| state.position == end && state.straight_steps >= crucible.min_straight,
+        |state| shortest_paths[state.position],
+        &search,
+    )?
+    .ok_or_else(|| anyhow!("no path to the bottom right corner"))?;
+    search.finish();
+
+    let path = std::iter::once(start)
+        .chain(states.into_iter().map(|state| state.position))
+        .collect();
+    Ok((loss, path))
+}
+
+const MIN_STRAIGHT: Param = Param::new(
+    17,
+    "min_straight",
+    "1",
+    "Blocks the crucible of part 1 moves in a straight line before it can turn",
+);
+const MAX_STRAIGHT: Param = Param::new(
+    17,
+    "max_straight",
+    "3",
+    "Blocks the crucible of part 1 moves in a straight line before it has to turn",
+);
+const ULTRA_MIN_STRAIGHT: Param = Param::new(
+    17,
+    "ultra_min_straight",
+    "4",
+    "Blocks the ultra crucible of part 2 moves in a straight line before it can turn",
+);
+const ULTRA_MAX_STRAIGHT: Param = Param::new(
+    17,
+    "ultra_max_straight",
+    "10",
+    "Blocks the ultra crucible of part 2 moves in a straight line before it has to turn",
+);
+
+fn crucible() -> Result<Crucible> {
+    Crucible::from_params(MIN_STRAIGHT, MAX_STRAIGHT)
+}
+
+fn ultra_crucible() -> Result<Crucible> {
+    Crucible::from_params(ULTRA_MIN_STRAIGHT, ULTRA_MAX_STRAIGHT)
+}
+
+pub fn part1(input: &str) -> Result<usize> {
+    Ok(best_path(&parse_loss_map(input)?, crucible()?)?.0)
+}
+
+pub fn part2(input: &str) -> Result<usize> {
+    Ok(best_path(&parse_loss_map(input)?, ultra_crucible()?)?.0)
+}
+
+pub fn day() -> Day {
+    Day {
+        number: 17,
+        dir: env!("CARGO_MANIFEST_DIR"),
+        part1: Some(|input| Ok(part1(input)?.to_string())),
+        part2: Some(|input| Ok(part2(input)?.to_string())),
+        generate: Some(gen::generate),
+        render: Some(render::render),
+        dot: None,
+        parse: Some(|input| {
+            parse_loss_map(input)?;
+            Ok(())
+        }),
+        params: &[
+            MIN_STRAIGHT,
+            MAX_STRAIGHT,
+            ULTRA_MIN_STRAIGHT,
+            ULTRA_MAX_STRAIGHT,
+        ],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("example.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(EXAMPLE).unwrap(), 102);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(EXAMPLE).unwrap(), 94);
+    }
+
+    #[test]
+    fn maps_of_a_single_row_or_column() {
+        assert_eq!(part1("7").unwrap(), 0);
+        assert_eq!(part2("7").unwrap(), 0);
+        assert_eq!(part1("19").unwrap(), 9);
+        assert_eq!(part1("1\n9").unwrap(), 9);
+        assert_eq!(part1("1234").unwrap(), 9);
+        // Too far to go straight on, or too short for the ultra crucible
+        assert!(part1("12345").is_err());
+        assert!(part2("19").is_err());
+        assert_eq!(part2("12345").unwrap(), 14);
+    }
+}
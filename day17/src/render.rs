@@ -0,0 +1,25 @@
+//! The heat loss of every block, darker for more, with the best path of the crucible of
+//! part 1 in red over that of the ultra crucible of part 2 in blue.
+use crate::{best_path, crucible, parse_loss_map, ultra_crucible};
+use anyhow::Result;
+use common::render::{Drawing, Rgb};
+
+pub fn render(input: &str) -> Result<Drawing> {
+    let loss_map = parse_loss_map(input)?;
+    let mut drawing = Drawing::grid(loss_map.width(), loss_map.height(), Rgb::WHITE);
+    for (pos, &loss) in loss_map.iter() {
+        drawing.cell(pos, Rgb::WHITE.mix(Rgb::BLACK, loss as f64 / 12.0));
+    }
+    for (crucible, color, width) in [
+        (ultra_crucible()?, Rgb::BLUE, 0.6),
+        (crucible()?, Rgb::RED, 0.3),
+    ] {
+        let (_, path) = best_path(&loss_map, crucible)?;
+        let points = path
+            .into_iter()
+            .map(|pos| Drawing::centre(pos.into()))
+            .collect();
+        drawing.path(points, color, width);
+    }
+    Ok(drawing)
+}
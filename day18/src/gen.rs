@@ -0,0 +1,84 @@
+//! Random dig plans along the outline of a random shape.
+use common::gen::{random_loop, StdRng};
+use common::geom::Direction;
+use rand::Rng;
+
+/// The steps of the loop, merged into runs of `(direction, first step, number of steps)`
+fn runs(steps: &[Direction]) -> Vec<(Direction, usize, usize)> {
+    let mut runs: Vec<(Direction, usize, usize)> = Vec::new();
+    for (i, &step) in steps.iter().enumerate() {
+        match runs.last_mut() {
+            Some(run) if run.0 == step => run.2 += 1,
+            _ => runs.push((step, i, 1)),
+        }
+    }
+    runs
+}
+
+/// A plan of about `size` instructions.
+///
+/// Both parts dig the outline of the same shape, a random loop of unit steps, but every
+/// unit column and row is stretched by a random factor: up to 10 meters in part 1, and
+/// up to 10000 in the color codes of part 2.
+pub fn generate(rng: &mut StdRng, size: usize) -> String {
+    let extent = (size / 4).max(1);
+    let (start, steps) = random_loop(rng, extent, extent);
+    // How far every step goes, which only depends on the column or row it crosses
+    let mut widths = |max: usize| -> (Vec<usize>, Vec<usize>) {
+        let mut random = || (0..extent).map(|_| rng.gen_range(1..=max)).collect();
+        (random(), random())
+    };
+    let (columns1, rows1) = widths(10);
+    // The color codes have five hexadecimal digits for the length
+    let (columns2, rows2) = widths(10_000.min(0xfffff / extent));
+
+    let (mut x, mut y) = (start.x, start.y);
+    let mut lengths = Vec::new();
+    for &step in &steps {
+        let (dx, dy) = step.offset();
+        let (new_x, new_y) = ((x as isize + dx) as usize, (y as isize + dy) as usize);
+        lengths.push(match step.is_horizontal() {
+            true => (columns1[x.min(new_x)], columns2[x.min(new_x)]),
+            false => (rows1[y.min(new_y)], rows2[y.min(new_y)]),
+        });
+        (x, y) = (new_x, new_y);
+    }
+
+    let mut out = String::new();
+    for (direction, first, num_steps) in runs(&steps) {
+        let run = &lengths[first..first + num_steps];
+        let length1: usize = run.iter().map(|lengths| lengths.0).sum();
+        let length2: usize = run.iter().map(|lengths| lengths.1).sum();
+        let (letter, digit) = match direction {
+            Direction::Right => ('R', 0),
+            Direction::Down => ('D', 1),
+            Direction::Left => ('L', 2),
+            Direction::Up => ('U', 3),
+        };
+        out.push_str(&format!(
+            "{} {} (#{:05x}{})\n",
+            letter, length1, length2, digit
+        ));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::gen::rng;
+
+    #[test]
+    fn generated_inputs_are_solved() {
+        for seed in 0..20 {
+            let input = generate(&mut rng(seed), 40);
+            let trench: i64 = input
+                .lines()
+                .map(|line| line.split(' ').nth(1).unwrap().parse::<i64>().unwrap())
+                .sum();
+            // The lagoon holds at least its trench
+            assert!(crate::part1(&input).unwrap() >= trench);
+            assert!(crate::part2(&input).unwrap() >= 40);
+        }
+    }
+}
@@ -0,0 +1,143 @@
+pub mod gen;
+pub mod reference;
+pub mod render;
+
+use anyhow::{anyhow, Result};
+use common::geom::{Direction, Pos};
+use common::parse::{self, Line, ParseError};
+use common::Day;
+
+#[derive(Debug)]
+struct Instruction {
+    direction: Direction,
+    steps: usize,
+    // color: String,
+}
+
+impl Instruction {
+    fn from_line(line: Line) -> Result<Self, ParseError> {
+        let (direction, rest) = line.split_once(" ")?;
+        let direction = match direction {
+            "R" => Direction::Right,
+            "L" => Direction::Left,
+            "U" => Direction::Up,
+            "D" => Direction::Down,
+            _ => return Err(line.invalid(direction, "one of R, L, U or D")),
+        };
+        let (steps, _) = rest.split_once(' ').unwrap_or((rest, ""));
+        let steps = line.parse(steps, "a number of steps")?;
+
+        Ok(Self { direction, steps })
+    }
+
+    /// Reads the instruction hidden in the color code, e.g. `(#70c710)`
+    fn from_line_part2(line: Line) -> Result<Self, ParseError> {
+        let color = line.text.rsplit(' ').next().unwrap_or(line.text);
+        let expected = "a color code of the form \"(#xxxxxd)\"";
+        let hex = color
+            .strip_prefix("(#")
+            .and_then(|c| c.strip_suffix(')'))
+            .filter(|hex| hex.len() == 6 && hex.is_ascii())
+            .ok_or_else(|| line.invalid(color, expected))?;
+        let steps = usize::from_str_radix(&hex[..5], 16)
+            .map_err(|_| line.invalid(&hex[..5], "a hexadecimal number of steps"))?;
+        let direction = match &hex[5..] {
+            "0" => Direction::Right,
+            "1" => Direction::Down,
+            "2" => Direction::Left,
+            "3" => Direction::Up,
+            _ => return Err(line.invalid(&hex[5..], "a direction from 0 to 3")),
+        };
+
+        Ok(Self { direction, steps })
+    }
+
+    /// Where the instruction digs to from `pos`, unless that is too far out to measure
+    fn move_from(&self, pos: Pos) -> Option<Pos> {
+        let (dx, dy) = self.direction.offset();
+        let steps = i64::try_from(self.steps).ok()?;
+        Some(Pos::new(
+            pos.x.checked_add((dx as i64).checked_mul(steps)?)?,
+            pos.y.checked_add((dy as i64).checked_mul(steps)?)?,
+        ))
+    }
+}
+
+/// Area of the lagoon, computed with the shoelace formula. The trench itself is a
+/// meter wide, which adds half the perimeter plus one.
+fn lagoon_size(input: &str, parse: fn(Line) -> Result<Instruction, ParseError>) -> Result<i64> {
+    let instructions = parse::lines(input)
+        .map(parse)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut iter = instructions.iter();
+    let instruction = iter
+        .next()
+        .ok_or_else(|| ParseError::incomplete("dig instructions"))?;
+
+    let measure = |instruction: &Instruction, prev_coord: Pos, sum: i64| {
+        let coord = instruction.move_from(prev_coord)?;
+        let cross = (prev_coord.x as i128 * coord.y as i128)
+            .checked_sub(prev_coord.y as i128 * coord.x as i128)?;
+        let sum = (sum as i128)
+            .checked_add(cross)?
+            .checked_add(instruction.steps as i128)?
+            .try_into()
+            .ok()?;
+        Some((coord, sum))
+    };
+    let too_large = || anyhow!("the lagoon is too large to measure");
+
+    let (mut prev_coord, mut sum) =
+        measure(instruction, Pos::new(0, 0), 0).ok_or_else(too_large)?;
+    for instruction in iter {
+        (prev_coord, sum) = measure(instruction, prev_coord, sum).ok_or_else(too_large)?;
+    }
+
+    Ok(sum.checked_abs().ok_or_else(too_large)? / 2 + 1)
+}
+
+pub fn part1(input: &str) -> Result<i64> {
+    lagoon_size(input, Instruction::from_line)
+}
+
+pub fn part2(input: &str) -> Result<i64> {
+    lagoon_size(input, Instruction::from_line_part2)
+}
+
+pub fn day() -> Day {
+    Day {
+        number: 18,
+        dir: env!("CARGO_MANIFEST_DIR"),
+        part1: Some(|input| Ok(part1(input)?.to_string())),
+        part2: Some(|input| Ok(part2(input)?.to_string())),
+        generate: Some(gen::generate),
+        render: Some(render::render),
+        dot: None,
+        parse: None,
+        params: &[],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("example.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(EXAMPLE).unwrap(), 62);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(EXAMPLE).unwrap(), 952408144115);
+    }
+
+    #[test]
+    fn huge_lagoon_is_an_error() {
+        assert!(part1("R 5 \nD 3999999999999999999").is_err());
+        assert!(part1("R 9223372036854775808").is_err());
+    }
+}
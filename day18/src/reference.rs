@@ -0,0 +1,66 @@
+//! A naive solution to part 1, which digs the trench into a grid and floods the ground
+//! around it instead of using the shoelace formula.
+use anyhow::{anyhow, Result};
+use std::collections::HashSet;
+
+pub fn part1(input: &str) -> Result<usize> {
+    let mut trench = HashSet::from([(0i64, 0i64)]);
+    let (mut x, mut y) = (0, 0);
+    for line in input.lines() {
+        let mut words = line.split(' ');
+        let (dx, dy) = match words.next() {
+            Some("R") => (1, 0),
+            Some("D") => (0, 1),
+            Some("L") => (-1, 0),
+            Some("U") => (0, -1),
+            _ => return Err(anyhow!("invalid direction in {:?}", line)),
+        };
+        let length: i64 = words
+            .next()
+            .ok_or_else(|| anyhow!("missing length in {:?}", line))?
+            .parse()?;
+        for _ in 0..length {
+            (x, y) = (x + dx, y + dy);
+            trench.insert((x, y));
+        }
+    }
+
+    // Flood the ground from outside a box one meter larger than the trench
+    let (min_x, max_x) = (
+        trench.iter().map(|p| p.0).min().unwrap() - 1,
+        trench.iter().map(|p| p.0).max().unwrap() + 1,
+    );
+    let (min_y, max_y) = (
+        trench.iter().map(|p| p.1).min().unwrap() - 1,
+        trench.iter().map(|p| p.1).max().unwrap() + 1,
+    );
+    let mut outside = HashSet::from([(min_x, min_y)]);
+    let mut queue = vec![(min_x, min_y)];
+    while let Some((x, y)) = queue.pop() {
+        for next in [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)] {
+            let within = (min_x..=max_x).contains(&next.0) && (min_y..=max_y).contains(&next.1);
+            if within && !trench.contains(&next) && outside.insert(next) {
+                queue.push(next);
+            }
+        }
+    }
+    let area = (max_x - min_x + 1) * (max_y - min_y + 1);
+    Ok(area as usize - outside.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::differential::assert_agree;
+
+    #[test]
+    fn part1_agrees() {
+        assert_agree(
+            |input| Ok(crate::part1(input)?.to_string()),
+            |input| Ok(part1(input)?.to_string()),
+            crate::gen::generate,
+            4..=16,
+            0..20,
+        );
+    }
+}
@@ -0,0 +1,49 @@
+//! The lagoon of part 1: the trench along the dig plan in the colours from the plan,
+//! around the dug out inside.
+use crate::Instruction;
+use anyhow::{anyhow, Result};
+use common::geom::Pos;
+use common::parse::{self, Line, ParseError};
+use common::render::{Drawing, Rgb};
+
+/// The colour at the end of a line, e.g. `(#70c710)`
+fn parse_color(line: Line) -> Result<Rgb, ParseError> {
+    let color = line.text.rsplit(' ').next().unwrap_or(line.text);
+    color
+        .strip_prefix('(')
+        .and_then(|c| c.strip_suffix(')'))
+        .and_then(Rgb::from_hex)
+        .ok_or_else(|| line.invalid(color, "a color code of the form \"(#rrggbb)\""))
+}
+
+pub fn render(input: &str) -> Result<Drawing> {
+    let mut edges = Vec::new();
+    let mut corner = Pos::new(0, 0);
+    let mut corners = vec![corner];
+    for line in parse::lines(input) {
+        let instruction = Instruction::from_line(line)?;
+        let next = instruction
+            .move_from(corner)
+            .ok_or_else(|| anyhow!("the lagoon is too large to draw"))?;
+        edges.push((corner, next, parse_color(line)?));
+        corners.push(next);
+        corner = next;
+    }
+
+    // The trench runs through the middle of the cubes it digs
+    let point = |pos: Pos| (pos.x as f64 + 0.5, pos.y as f64 + 0.5);
+    let min_x = corners.iter().map(|pos| pos.x).min().unwrap_or(0);
+    let min_y = corners.iter().map(|pos| pos.y).min().unwrap_or(0);
+    let max_x = corners.iter().map(|pos| pos.x).max().unwrap_or(0);
+    let max_y = corners.iter().map(|pos| pos.y).max().unwrap_or(0);
+    let mut drawing = Drawing::new(
+        (min_x as f64, min_y as f64),
+        (max_x as f64 + 1.0, max_y as f64 + 1.0),
+        Rgb::WHITE,
+    );
+    drawing.polygon(corners.into_iter().map(point).collect(), Rgb(200, 170, 120));
+    for (from, to, color) in edges {
+        drawing.path(vec![point(from), point(to)], color, 1.0);
+    }
+    Ok(drawing)
+}
@@ -0,0 +1,109 @@
+//! The workflows, with an edge for every rule labelled with its condition. Highlighting
+//! follows all ratings from `in` as in part 2, and adds to every rule the number of
+//! combinations that are accepted after taking it, marking the rules that lead to `A`.
+use crate::{all_ratings, parse_workflows, Attribute, Condition, Inequality};
+use anyhow::{anyhow, Result};
+use common::dot::{Attributes, Dot};
+use common::parse;
+use std::collections::HashMap;
+
+fn describe(condition: &Condition) -> String {
+    let attribute = match condition.attribute {
+        Attribute::X => 'x',
+        Attribute::M => 'm',
+        Attribute::A => 'a',
+        Attribute::S => 's',
+    };
+    let inequality = match condition.inequality {
+        Inequality::GreaterThan => '>',
+        Inequality::LessThan => '<',
+    };
+    format!("{}{}{}", attribute, inequality, condition.value)
+}
+
+pub fn export(input: &str, highlight: bool) -> Result<Dot> {
+    let workflows = parse_workflows(&mut parse::lines(input))?;
+
+    // Accepted combinations by workflow and rule, found by following every range of
+    // ratings along with the rules it took
+    let mut accepted: HashMap<(&str, usize), i64> = HashMap::new();
+    if highlight {
+        let mut queue = vec![("in", all_ratings()?, Vec::new())];
+        while let Some((name, range, rules)) = queue.pop() {
+            let workflow = workflows
+                .get(name)
+                .ok_or_else(|| anyhow!("unknown workflow {}", name))?;
+            let mut left = Some(range);
+            for (i, entry) in workflow.entries.iter().enumerate() {
+                let Some(range) = left.take() else {
+                    break;
+                };
+                let (pass, fail) = match &entry.condition {
+                    Some(condition) => condition.split_condition(range),
+                    None => (Some(range), None),
+                };
+                left = fail;
+                let Some(pass) = pass else {
+                    continue;
+                };
+                let mut rules = rules.clone();
+                rules.push((name, i));
+                match entry.name.as_str() {
+                    "A" => {
+                        for &rule in &rules {
+                            *accepted.entry(rule).or_default() += pass.size();
+                        }
+                    }
+                    "R" => {}
+                    next => queue.push((next, pass, rules)),
+                }
+            }
+        }
+    }
+
+    let mut names: Vec<&String> = workflows.keys().collect();
+    names.sort();
+    let mut dot = Dot::directed("day19");
+    dot.node("A", Attributes::new().fill("palegreen"));
+    dot.node("R", Attributes::new().fill("salmon"));
+    for name in names {
+        let shape = match name.as_str() {
+            "in" => "doublecircle",
+            _ => "box",
+        };
+        dot.node(name, Attributes::new().shape(shape));
+        for (i, entry) in workflows[name].entries.iter().enumerate() {
+            let mut label = match &entry.condition {
+                Some(condition) => describe(condition),
+                None => "else".to_string(),
+            };
+            let attributes = match accepted.get(&(name.as_str(), i)) {
+                Some(combinations) => {
+                    label = format!("{} ({} accepted)", label, combinations);
+                    Attributes::new().highlight()
+                }
+                None => Attributes::new(),
+            };
+            dot.edge(name, &entry.name, attributes.label(label));
+        }
+    }
+    Ok(dot)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepted_combinations() {
+        let input = "in{x<2001:A,ab}\nab{m>1000:R,A}\n\n{x=1,m=1,a=1,s=1}";
+        let dot = export(input, true).unwrap().to_string();
+        let half = 4000i64.pow(3) * 2000;
+        assert!(dot.contains(&format!(
+            "\"in\" -> \"A\" [color=\"red\", penwidth=\"3\", label=\"x<2001 ({} accepted)\"];",
+            half
+        )));
+        assert!(dot.contains(&format!("label=\"else ({} accepted)\"", half / 4)));
+        assert!(dot.contains("\"ab\" -> \"R\" [label=\"m>1000\"];"));
+    }
+}
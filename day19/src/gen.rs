@@ -0,0 +1,73 @@
+//! Random workflows and parts.
+use common::gen::{names, StdRng};
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+fn condition(rng: &mut StdRng) -> String {
+    let attribute = ['x', 'm', 'a', 's'][rng.gen_range(0..4)];
+    let inequality = if rng.gen_bool(0.5) { '<' } else { '>' };
+    format!("{}{}{}", attribute, inequality, rng.gen_range(1..=4000))
+}
+
+/// `size` workflows and as many parts.
+///
+/// The workflows form a tree below `in`: every workflow but `in` is sent to by exactly
+/// one rule of an earlier workflow, so all of them are reachable and none loop.
+pub fn generate(rng: &mut StdRng, size: usize) -> String {
+    let size = size.max(1);
+    let mut names: Vec<String> = names(rng, size, 3)
+        .into_iter()
+        .filter(|name| name != "in")
+        .take(size - 1)
+        .collect();
+    names.insert(0, "in".to_string());
+    let mut children = vec![Vec::new(); size];
+    for child in 1..size {
+        children[rng.gen_range(0..child)].push(names[child].clone());
+    }
+
+    let mut workflows = Vec::new();
+    for (name, mut targets) in names.iter().zip(children) {
+        let num_targets = targets.len().max(rng.gen_range(1..=3)) + 1;
+        while targets.len() < num_targets {
+            targets.push(if rng.gen_bool(0.5) { "A" } else { "R" }.to_string());
+        }
+        targets.shuffle(rng);
+        let default = targets.pop().unwrap();
+        let rules: Vec<String> = targets
+            .iter()
+            .map(|target| format!("{}:{}", condition(rng), target))
+            .collect();
+        workflows.push(format!("{}{{{},{}}}", name, rules.join(","), default));
+    }
+    workflows.shuffle(rng);
+
+    let mut out = workflows.join("\n");
+    out.push_str("\n\n");
+    for _ in 0..size {
+        let mut rating = || rng.gen_range(1..=4000);
+        out.push_str(&format!(
+            "{{x={},m={},a={},s={}}}\n",
+            rating(),
+            rating(),
+            rating(),
+            rating()
+        ));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::gen::rng;
+
+    #[test]
+    fn generated_inputs_are_solved() {
+        for seed in 0..20 {
+            let input = generate(&mut rng(seed), 30);
+            assert!(crate::part1(&input).unwrap() <= 30 * 4 * 4000);
+            assert!(crate::part2(&input).unwrap() <= 4000i64.pow(4));
+        }
+    }
+}
@@ -0,0 +1,449 @@
+pub mod dot;
+pub mod gen;
+
+use anyhow::{anyhow, bail, Result};
+use common::config::Param;
+use common::interval::Interval;
+use common::parse::{self, Line, ParseError};
+use common::Day;
+use log::debug;
+use std::collections::HashMap;
+
+enum Attribute {
+    X,
+    M,
+    A,
+    S,
+}
+
+#[derive(Debug)]
+struct Part {
+    x: i64,
+    m: i64,
+    a: i64,
+    s: i64,
+}
+
+impl Part {
+    /// Parses a part of the form `{x=787,m=2655,a=1222,s=2876}`
+    fn from_line(line: Line) -> Result<Self, ParseError> {
+        let ratings = line
+            .text
+            .strip_prefix('{')
+            .and_then(|s| s.strip_suffix('}'))
+            .ok_or_else(|| line.invalid(line.text, "\"{<ratings>}\""))?;
+        let mut ratings = ratings.split(',');
+        let mut rating = |name: &str| -> Result<i64, ParseError> {
+            let expected = format!("\"{}=<rating>\"", name);
+            let rating = ratings.next().ok_or_else(|| line.missing(&expected))?;
+            let value = rating
+                .strip_prefix(name)
+                .and_then(|s| s.strip_prefix('='))
+                .ok_or_else(|| line.invalid(rating, &expected))?;
+            line.parse(value, "a rating")
+        };
+
+        Ok(Self {
+            x: rating("x")?,
+            m: rating("m")?,
+            a: rating("a")?,
+            s: rating("s")?,
+        })
+    }
+
+    fn score(&self) -> i64 {
+        self.x + self.m + self.a + self.s
+    }
+}
+
+#[derive(Debug, Clone)]
+struct PartRange {
+    x: Interval,
+    m: Interval,
+    a: Interval,
+    s: Interval,
+}
+
+impl PartRange {
+    fn update_range(&mut self, attribute: &Attribute, range: Interval) {
+        match attribute {
+            Attribute::X => self.x = range,
+            Attribute::M => self.m = range,
+            Attribute::A => self.a = range,
+            Attribute::S => self.s = range,
+        }
+    }
+
+    fn get_attribute_range(&self, attribute: &Attribute) -> Interval {
+        match attribute {
+            Attribute::X => self.x,
+            Attribute::M => self.m,
+            Attribute::A => self.a,
+            Attribute::S => self.s,
+        }
+    }
+
+    fn size(&self) -> i64 {
+        (self.x.len() * self.m.len() * self.a.len() * self.s.len()) as i64
+    }
+}
+
+enum Inequality {
+    GreaterThan,
+    LessThan,
+}
+
+struct Condition {
+    attribute: Attribute,
+    inequality: Inequality,
+    value: i64,
+}
+
+/// Splits the ratings into those that pass the condition and those that fail it
+fn split_range(
+    range: Interval,
+    inequality: &Inequality,
+    val: i64,
+) -> (Option<Interval>, Option<Interval>) {
+    let (pass, fail) = match inequality {
+        Inequality::GreaterThan => match val.checked_add(1) {
+            Some(above_val) => {
+                let (below, above) = range.split_at(above_val);
+                (above, below)
+            }
+            // No rating is above the largest one
+            None => (Interval::new(0, 0), range),
+        },
+        Inequality::LessThan => range.split_at(val),
+    };
+    let non_empty = |range: Interval| (!range.is_empty()).then_some(range);
+    (non_empty(pass), non_empty(fail))
+}
+
+impl Condition {
+    fn check_condition(&self, part: &Part) -> bool {
+        match self.attribute {
+            Attribute::X => match self.inequality {
+                Inequality::GreaterThan => part.x > self.value,
+                Inequality::LessThan => part.x < self.value,
+            },
+            Attribute::M => match self.inequality {
+                Inequality::GreaterThan => part.m > self.value,
+                Inequality::LessThan => part.m < self.value,
+            },
+            Attribute::A => match self.inequality {
+                Inequality::GreaterThan => part.a > self.value,
+                Inequality::LessThan => part.a < self.value,
+            },
+            Attribute::S => match self.inequality {
+                Inequality::GreaterThan => part.s > self.value,
+                Inequality::LessThan => part.s < self.value,
+            },
+        }
+    }
+
+    fn split_condition(&self, part: PartRange) -> (Option<PartRange>, Option<PartRange>) {
+        // Out is (pass, fail)
+        let val = part.get_attribute_range(&self.attribute);
+        let (range_pass, range_fail) = split_range(val, &self.inequality, self.value);
+        let part1 = range_pass.map(|range| {
+            let mut new_part = part.clone();
+            new_part.update_range(&self.attribute, range);
+            new_part
+        });
+        let part2 = range_fail.map(|range| {
+            let mut new_part = part;
+            new_part.update_range(&self.attribute, range);
+            new_part
+        });
+        (part1, part2)
+    }
+
+    fn from_string(line: Line, s: &str) -> Result<Self, ParseError> {
+        let attribute = match s.get(..1) {
+            Some("x") => Attribute::X,
+            Some("m") => Attribute::M,
+            Some("a") => Attribute::A,
+            Some("s") => Attribute::S,
+            _ => return Err(line.invalid(s, "one of the categories x, m, a or s")),
+        };
+        let inequality = match s.get(1..2) {
+            Some(">") => Inequality::GreaterThan,
+            Some("<") => Inequality::LessThan,
+            _ => return Err(line.invalid(&s[1..], "'<' or '>'")),
+        };
+        let value = line.parse(&s[2..], "a rating")?;
+
+        Ok(Self {
+            attribute,
+            inequality,
+            value,
+        })
+    }
+}
+
+struct WorkflowEntry {
+    condition: Option<Condition>,
+    name: String,
+}
+
+impl WorkflowEntry {
+    fn from_string(line: Line, s: &str) -> Result<Self, ParseError> {
+        if let Some((condition, name)) = s.split_once(':') {
+            Ok(Self {
+                condition: Some(Condition::from_string(line, condition)?),
+                name: name.to_string(),
+            })
+        } else {
+            Ok(Self {
+                condition: None,
+                name: s.to_string(),
+            })
+        }
+    }
+}
+
+struct Workflow {
+    name: String,
+    entries: Vec<WorkflowEntry>,
+}
+
+impl Workflow {
+    /// Parses a workflow of the form `px{a<2006:qkq,m>2090:A,rfg}`
+    fn from_line(line: Line) -> Result<Self, ParseError> {
+        let (name, rules) = line.split_once("{")?;
+        let rules = rules.strip_suffix('}').ok_or_else(|| line.missing("'}'"))?;
+        let entries: Vec<WorkflowEntry> = rules
+            .split(',')
+            .map(|s| WorkflowEntry::from_string(line, s))
+            .collect::<Result<_, _>>()?;
+        // Every part has to end up somewhere, so the last rule cannot have a condition
+        if let Some(last) = rules.rsplit(',').next().filter(|s| s.contains(':')) {
+            return Err(line.invalid(last, "a final rule without a condition"));
+        }
+
+        Ok(Self {
+            name: name.to_string(),
+            entries,
+        })
+    }
+
+    fn apply(&self, part: &Part) -> &str {
+        for entry in &self.entries {
+            if let Some(condition) = &entry.condition {
+                if condition.check_condition(part) {
+                    return &entry.name;
+                }
+            } else {
+                return &entry.name;
+            }
+        }
+        unreachable!("workflows end in a rule without a condition");
+    }
+
+    fn apply_range(&self, partrange: PartRange) -> Vec<(String, PartRange)> {
+        let mut out = Vec::new();
+        let mut current_range = partrange;
+        for entry in &self.entries {
+            if let Some(condition) = &entry.condition {
+                let (pass_range, fail_range) = condition.split_condition(current_range);
+                if let Some(range) = pass_range {
+                    out.push((entry.name.clone(), range));
+                }
+                match fail_range {
+                    Some(range) => current_range = range,
+                    None => break,
+                }
+            } else {
+                out.push((entry.name.clone(), current_range));
+                break;
+            }
+        }
+
+        out
+    }
+}
+
+fn check_part(part: &Part, workflows: &Workflows) -> Result<bool> {
+    let mut next_workflow_name = "in";
+    loop {
+        let workflow = workflows
+            .get(next_workflow_name)
+            .ok_or_else(|| anyhow!("unknown workflow {}", next_workflow_name))?;
+        next_workflow_name = workflow.apply(part);
+        if next_workflow_name == "A" {
+            return Ok(true);
+        }
+        if next_workflow_name == "R" {
+            return Ok(false);
+        }
+    }
+}
+
+type Workflows = HashMap<String, Workflow>;
+
+const MIN_RATING: Param = Param::new(19, "min_rating", "1", "Lowest rating of every category");
+const MAX_RATING: Param = Param::new(19, "max_rating", "4000", "Highest rating of every category");
+
+/// Every rating of every category, from 1 to 4000 by default
+fn all_ratings() -> Result<PartRange> {
+    let (min, max): (i64, i64) = (MIN_RATING.get()?, MAX_RATING.get()?);
+    if min > max {
+        bail!("the lowest rating {} is above the highest {}", min, max);
+    }
+    // The number of combinations has to fit
+    if max - min >= 50_000 {
+        bail!(
+            "the ratings from {} to {} are too many to combine",
+            min,
+            max
+        );
+    }
+    let ratings = Interval::new(min, max + 1);
+    Ok(PartRange {
+        x: ratings,
+        m: ratings,
+        a: ratings,
+        s: ratings,
+    })
+}
+
+/// Parses the workflows at the top of the input, leaving `lines` at the parts below
+fn parse_workflows<'a>(
+    lines: &mut impl Iterator<Item = Line<'a>>,
+) -> Result<Workflows, ParseError> {
+    let mut workflows: Workflows = HashMap::new();
+    for line in lines.by_ref() {
+        if line.text.is_empty() {
+            break;
+        }
+        let workflow = Workflow::from_line(line)?;
+        workflows.insert(workflow.name.clone(), workflow);
+    }
+    Ok(workflows)
+}
+
+/// Parses the workflows and the parts below them
+fn parse_system(input: &str) -> Result<(Workflows, Vec<Part>), ParseError> {
+    let mut lines = parse::lines(input);
+    let workflows = parse_workflows(&mut lines)?;
+    let parts = lines
+        .filter(|line| !line.text.is_empty())
+        .map(Part::from_line)
+        .collect::<Result<_, _>>()?;
+    Ok((workflows, parts))
+}
+
+pub fn part1(input: &str) -> Result<i64> {
+    let (workflows, parts) = parse_system(input)?;
+
+    let mut sum = 0;
+    for part in &parts {
+        if check_part(part, &workflows)? {
+            sum += part.score();
+        }
+    }
+
+    Ok(sum)
+}
+
+pub fn part2(input: &str) -> Result<i64> {
+    let workflows = parse_workflows(&mut parse::lines(input))?;
+
+    let mut queue: Vec<(String, PartRange)> = vec![("in".to_string(), all_ratings()?)];
+    let mut accepted = Vec::new();
+    let mut rejected = Vec::new();
+
+    while let Some((workflow_name, part_range)) = queue.pop() {
+        let workflow = workflows
+            .get(&workflow_name)
+            .ok_or_else(|| anyhow!("unknown workflow {}", workflow_name))?;
+        let new_ranges = workflow.apply_range(part_range);
+        for range in new_ranges.into_iter() {
+            match range.0.as_str() {
+                "A" => accepted.push(range.1),
+                "R" => rejected.push(range.1),
+                _ => queue.push((range.0, range.1)),
+            }
+        }
+    }
+
+    debug!("Accepted: {:?}", accepted);
+    let sum = accepted.iter().map(PartRange::size).sum::<i64>();
+
+    Ok(sum)
+}
+
+pub fn day() -> Day {
+    Day {
+        number: 19,
+        dir: env!("CARGO_MANIFEST_DIR"),
+        part1: Some(|input| Ok(part1(input)?.to_string())),
+        part2: Some(|input| Ok(part2(input)?.to_string())),
+        generate: Some(gen::generate),
+        render: None,
+        dot: Some(dot::export),
+        parse: Some(|input| {
+            parse_system(input)?;
+            Ok(())
+        }),
+        params: &[MIN_RATING, MAX_RATING],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("example.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(EXAMPLE).unwrap(), 19114);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(EXAMPLE).unwrap(), 167409079868000);
+    }
+
+    #[test]
+    fn conditions_split_at_the_value() {
+        let range = Interval::new(1, 11);
+        assert_eq!(
+            split_range(range, &Inequality::GreaterThan, 4),
+            (Some(Interval::new(5, 11)), Some(Interval::new(1, 5)))
+        );
+        assert_eq!(
+            split_range(range, &Inequality::LessThan, 4),
+            (Some(Interval::new(1, 4)), Some(Interval::new(4, 11)))
+        );
+        assert_eq!(
+            split_range(range, &Inequality::GreaterThan, 10),
+            (None, Some(range))
+        );
+        assert_eq!(
+            split_range(range, &Inequality::LessThan, 1),
+            (None, Some(range))
+        );
+        assert_eq!(
+            split_range(range, &Inequality::GreaterThan, i64::MAX),
+            (None, Some(range))
+        );
+        assert!(part2("in{x>9223372036854775807:R,A}\n\n{x=1,m=2,a=3,s=4}").is_ok());
+    }
+
+    #[test]
+    fn malformed_rules_are_errors() {
+        let err = part2("in{x<10:A,R}\npx{a<2006:qkq,m>2090:A}").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 15: expected a final rule without a condition, found \"m>2090:A\""
+        );
+        let err = part2("in{x<10:A,y>3:R,A}").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 1, column 11: expected one of the categories x, m, a or s, found \"y>3\""
+        );
+    }
+}
@@ -0,0 +1,40 @@
+//! Random games of drawing red, green and blue cubes from a bag.
+use common::gen::StdRng;
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+/// `size` games of one to six handfuls of up to 20 cubes per color.
+pub fn generate(rng: &mut StdRng, size: usize) -> String {
+    let mut out = String::new();
+    for game in 1..=size {
+        let handfuls: Vec<String> = (0..rng.gen_range(1..=6))
+            .map(|_| {
+                let mut colors = ["red", "green", "blue"];
+                colors.shuffle(rng);
+                let num_colors = rng.gen_range(1..=3);
+                colors[..num_colors]
+                    .iter()
+                    .map(|color| format!("{} {}", rng.gen_range(1..=20), color))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            })
+            .collect();
+        out.push_str(&format!("Game {}: {}\n", game, handfuls.join("; ")));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::gen::rng;
+
+    #[test]
+    fn generated_inputs_are_solved() {
+        for seed in 0..20 {
+            let input = generate(&mut rng(seed), 30);
+            assert!(crate::part1(&input).unwrap() <= (1..=30).sum());
+            assert!(crate::part2(&input).unwrap() > 0);
+        }
+    }
+}
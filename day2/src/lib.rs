@@ -0,0 +1,140 @@
+pub mod gen;
+
+use anyhow::Result;
+use common::parse::{self, Line, ParseError};
+use common::Day;
+use std::collections::HashMap;
+
+/// The cubes drawn in one handful, as `(number, color)`
+fn parse_handful<'a>(line: Line<'a>, handful: &'a str) -> Result<Vec<(u32, &'a str)>, ParseError> {
+    handful
+        .split(", ")
+        .map(|word| {
+            let (number, color) = word
+                .split_once(' ')
+                .ok_or_else(|| line.invalid(word, "a number of cubes and a color"))?;
+            Ok((line.parse(number, "a number of cubes")?, color))
+        })
+        .collect()
+}
+
+/// Splits a line into the game number and the handfuls of cubes
+fn parse_game<'a>(line: Line<'a>) -> Result<(u32, std::str::Split<'a, &'static str>), ParseError> {
+    let (game, rest) = line.split_once(": ")?;
+    let game_number = game
+        .strip_prefix("Game ")
+        .ok_or_else(|| line.invalid(game, "\"Game <number>\""))?;
+    let game_number = line.parse(game_number, "a game number")?;
+
+    Ok((game_number, rest.split("; ")))
+}
+
+fn create_map() -> HashMap<&'static str, u32> {
+    let mut map = HashMap::new();
+    map.insert("red", 12);
+    map.insert("green", 13);
+    map.insert("blue", 14);
+    map
+}
+
+fn process_line1(line: Line, map: &HashMap<&str, u32>) -> Result<u32, ParseError> {
+    let (game_number, handfuls) = parse_game(line)?;
+
+    for part in handfuls {
+        for (number, color) in parse_handful(line, part)? {
+            let color_max = map
+                .get(color)
+                .ok_or_else(|| line.invalid(color, "red, green or blue"))?;
+            if number > *color_max {
+                return Ok(0);
+            }
+        }
+    }
+
+    Ok(game_number)
+}
+fn process_line2(line: Line) -> Result<u32, ParseError> {
+    let (_, handfuls) = parse_game(line)?;
+
+    let mut max_red = 0;
+    let mut max_green = 0;
+    let mut max_blue = 0;
+
+    for part in handfuls {
+        for (number, color) in parse_handful(line, part)? {
+            if color == "red" && number > max_red {
+                max_red = number;
+            } else if color == "green" && number > max_green {
+                max_green = number;
+            } else if color == "blue" && number > max_blue {
+                max_blue = number;
+            }
+        }
+    }
+    Ok(max_blue * max_green * max_red)
+}
+
+pub fn part1(input: &str) -> Result<u32> {
+    let map = create_map();
+
+    let mut sum = 0;
+    for line in parse::lines(input) {
+        sum += process_line1(line, &map)?;
+    }
+
+    Ok(sum)
+}
+
+pub fn part2(input: &str) -> Result<u32> {
+    let mut sum = 0;
+    for line in parse::lines(input) {
+        sum += process_line2(line)?;
+    }
+
+    Ok(sum)
+}
+
+pub fn day() -> Day {
+    Day {
+        number: 2,
+        dir: env!("CARGO_MANIFEST_DIR"),
+        part1: Some(|input| Ok(part1(input)?.to_string())),
+        part2: Some(|input| Ok(part2(input)?.to_string())),
+        generate: Some(gen::generate),
+        render: None,
+        dot: None,
+        parse: None,
+        params: &[],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("example.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(EXAMPLE).unwrap(), 8);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(EXAMPLE).unwrap(), 2286);
+    }
+
+    #[test]
+    fn reports_malformed_line() {
+        let err = part1("Game 1: 3 blue\nGame 2: 4 purple").unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<ParseError>(),
+            Some(&ParseError::Invalid {
+                line: 2,
+                column: 11,
+                text: "purple".to_string(),
+                expected: "red, green or blue".to_string(),
+            })
+        );
+    }
+}
@@ -0,0 +1,92 @@
+//! The modules and where they send their pulses: flip-flops are boxes and conjunctions
+//! diamonds. Highlighting colours the part of the network that drives each input of `hb`,
+//! the counters whose loops part 2 lines up.
+use crate::{parse_network, CONJUNCTION};
+use anyhow::Result;
+use common::dot::{Attributes, Dot};
+use std::collections::HashMap;
+
+const COLORS: [&str; 6] = [
+    "lightblue",
+    "palegreen",
+    "khaki",
+    "plum",
+    "lightsalmon",
+    "lightgrey",
+];
+
+pub fn export(input: &str, highlight: bool) -> Result<Dot> {
+    let network = parse_network(input)?;
+
+    // The colour of every module upstream of an input of hb
+    let mut colors: HashMap<String, &str> = HashMap::new();
+    if highlight {
+        let name: String = CONJUNCTION.get()?;
+        if let Some(hb) = network.conjunctions.get(&name) {
+            let mut inputs: Vec<&String> = hb.state.keys().collect();
+            inputs.sort();
+            for (input, color) in inputs.into_iter().zip(COLORS.iter().cycle()) {
+                for module in network.upstream(input) {
+                    colors.insert(module, color);
+                }
+            }
+        }
+    }
+    let look = |name: &str, shape: &str| {
+        let attributes = Attributes::new().shape(shape);
+        match colors.get(name) {
+            Some(color) => attributes.fill(color),
+            None => attributes,
+        }
+    };
+
+    let mut modules: Vec<(&String, &Vec<String>, &str)> = network
+        .flip_flops
+        .values()
+        .map(|m| (&m.name, &m.targets, "box"))
+        .chain(
+            network
+                .conjunctions
+                .values()
+                .map(|m| (&m.name, &m.targets, "diamond")),
+        )
+        .collect();
+    modules.sort();
+
+    let mut dot = Dot::directed("day20");
+    dot.node("broadcaster", Attributes::new().shape("doublecircle"));
+    for target in &network.broadcast_targets {
+        dot.edge("broadcaster", target, Attributes::new());
+    }
+    for (name, targets, shape) in modules {
+        dot.node(name, look(name, shape));
+        for target in targets {
+            let attributes = match (colors.get(name), colors.get(target)) {
+                (Some(from), Some(to)) if from == to => {
+                    Attributes::new().color(from).set("penwidth", 2)
+                }
+                _ => Attributes::new(),
+            };
+            dot.edge(name, target, attributes);
+        }
+    }
+    Ok(dot)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counters_get_their_own_colour() {
+        let input = "broadcaster -> a, b\n%a -> ca\n%b -> cb\n&ca -> hb\n&cb -> hb\n&hb -> rx";
+        let dot = export(input, true).unwrap().to_string();
+        assert!(dot.contains("\"a\" [shape=\"box\", style=\"filled\", fillcolor=\"lightblue\"];"));
+        assert!(
+            dot.contains("\"cb\" [shape=\"diamond\", style=\"filled\", fillcolor=\"palegreen\"];")
+        );
+        assert!(dot.contains("\"hb\" [shape=\"diamond\"];"));
+        assert!(dot.contains("\"b\" -> \"cb\" [color=\"palegreen\", penwidth=\"2\"];"));
+        assert!(dot.contains("\"hb\" -> \"rx\";"));
+    }
+}
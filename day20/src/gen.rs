@@ -0,0 +1,85 @@
+//! Random module networks, shaped like the real ones: four binary counters that each
+//! send a high pulse towards `hb` once every so many button presses.
+use common::gen::{names, StdRng};
+use common::math::lcm;
+use rand::seq::{index, SliceRandom};
+
+/// A network of four counters of `size` bits, at least 4 and at most 16. See
+/// [`network`].
+pub fn generate(rng: &mut StdRng, size: usize) -> String {
+    network(rng, size).0
+}
+
+/// A network and the answer to part 2.
+///
+/// Every counter is a chain of flip-flops, one per bit, and a conjunction that counts to
+/// a period `n`: flip-flops for the bits of `n` that are set send to the conjunction, and
+/// the conjunction sends to the others and to the first, which resets the counter once
+/// all bits of `n` are set. Then an inverter sends a high pulse to `hb`, so that `rx`
+/// first gets a low pulse after the lcm of the periods `n` of the counters. The periods
+/// are odd, distinct and have their highest bit set, but need not be coprime.
+pub fn network(rng: &mut StdRng, size: usize) -> (String, usize) {
+    let bits = size.clamp(4, 16);
+    // Odd numbers with the highest bit set
+    let periods: Vec<usize> = index::sample(rng, 1 << (bits - 2), 4)
+        .into_iter()
+        .map(|i| (1 << (bits - 1)) + 2 * i + 1)
+        .collect();
+
+    let names: Vec<String> = names(rng, 4 * (bits + 2) + 2, 2)
+        .into_iter()
+        .filter(|name| name != "hb" && name != "rx")
+        .collect();
+    let mut names = names.into_iter();
+    let mut lines = Vec::new();
+    let mut starts = Vec::new();
+    for &period in &periods {
+        let chain: Vec<String> = (0..bits).map(|_| names.next().unwrap()).collect();
+        let hub = names.next().unwrap();
+        let inverter = names.next().unwrap();
+
+        let mut hub_targets = vec![chain[0].clone()];
+        for (bit, flip_flop) in chain.iter().enumerate() {
+            let mut targets = Vec::new();
+            if let Some(next) = chain.get(bit + 1) {
+                targets.push(next.clone());
+            }
+            match period >> bit & 1 {
+                1 => targets.push(hub.clone()),
+                _ => hub_targets.push(flip_flop.clone()),
+            }
+            targets.shuffle(rng);
+            lines.push(format!("%{} -> {}", flip_flop, targets.join(", ")));
+        }
+        hub_targets.push(inverter.clone());
+        hub_targets.shuffle(rng);
+        lines.push(format!("&{} -> {}", hub, hub_targets.join(", ")));
+        lines.push(format!("&{} -> hb", inverter));
+        starts.push(chain[0].clone());
+    }
+    lines.push(format!("broadcaster -> {}", starts.join(", ")));
+    lines.push("&hb -> rx".to_string());
+    lines.shuffle(rng);
+
+    let mut out = lines.join("\n");
+    out.push('\n');
+    let part2 = periods
+        .iter()
+        .fold(1, |acc, &period| lcm(acc, period as u64));
+    (out, part2 as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::gen::rng;
+
+    #[test]
+    fn planted_answer() {
+        for seed in 0..10 {
+            let (input, part2) = network(&mut rng(seed), 8);
+            crate::part1(&input).unwrap();
+            assert_eq!(crate::part2(&input).unwrap(), part2);
+        }
+    }
+}
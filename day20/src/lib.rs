@@ -0,0 +1,446 @@
+pub mod dot;
+pub mod gen;
+
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    fmt::Debug,
+};
+
+use anyhow::{anyhow, bail, Result};
+use common::config::Param;
+use common::cycle;
+use common::graph;
+use common::parse::{self, Line, ParseError};
+use common::Day;
+use log::debug;
+
+const CONJUNCTION: Param = Param::new(
+    20,
+    "conjunction",
+    "\"hb\"",
+    "The conjunction module that sends the pulses to rx in part 2",
+);
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+enum PulseWidth {
+    Low,
+    High,
+}
+
+impl Debug for PulseWidth {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Low => write!(f, "low"),
+            Self::High => write!(f, "high"),
+        }
+    }
+}
+
+// impl PulseWidth {
+//     fn flip(&self) -> Self {
+//         match self {
+//             Self::Low => Self::High,
+//             Self::High => Self::Low,
+//         }
+//     }
+// }
+
+struct Pulse {
+    width: PulseWidth,
+    target: String,
+    source: String,
+}
+
+impl Debug for Pulse {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} -{:?}-> {}", self.source, self.width, self.target)
+    }
+}
+
+#[derive(Clone)]
+enum FlipFlipState {
+    On,
+    Off,
+}
+
+impl FlipFlipState {
+    fn flip(&self) -> Self {
+        match self {
+            Self::On => Self::Off,
+            Self::Off => Self::On,
+        }
+    }
+}
+
+#[derive(Clone)]
+struct FlipFlopModule {
+    name: String,
+    targets: Vec<String>,
+    state: FlipFlipState,
+}
+
+impl FlipFlopModule {
+    fn new(name: &str, targets: Vec<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            targets,
+            state: FlipFlipState::Off,
+        }
+    }
+
+    fn handle_pulse(&mut self, pulse: PulseWidth) -> Vec<Pulse> {
+        match pulse {
+            PulseWidth::High => Vec::new(),
+            PulseWidth::Low => {
+                let output = match self.state {
+                    FlipFlipState::Off => PulseWidth::High,
+                    FlipFlipState::On => PulseWidth::Low,
+                };
+
+                self.state = self.state.flip();
+                self.targets
+                    .iter()
+                    .map(|t| Pulse {
+                        width: output,
+                        source: self.name.clone(),
+                        target: t.clone(),
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+#[derive(Clone)]
+struct ConjunctionModule {
+    name: String,
+    targets: Vec<String>,
+    state: HashMap<String, PulseWidth>,
+}
+
+impl ConjunctionModule {
+    fn new(name: &str, targets: Vec<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            targets,
+            state: HashMap::new(),
+        }
+    }
+
+    fn add_source(&mut self, source: String) {
+        self.state.insert(source, PulseWidth::Low);
+    }
+
+    fn handle_pulse(&mut self, pulse: Pulse) -> Vec<Pulse> {
+        self.state.insert(pulse.source, pulse.width);
+        let all_high = !self.state.values().any(|p| *p == PulseWidth::Low);
+        let output = match all_high {
+            true => PulseWidth::Low,
+            false => PulseWidth::High,
+        };
+        self.targets
+            .iter()
+            .map(|t| Pulse {
+                width: output,
+                source: self.name.clone(),
+                target: t.clone(),
+            })
+            .collect()
+    }
+}
+
+/// Splits a line of the form `%a -> b, c` into the module name, including its type
+/// prefix, and its targets
+fn parse_module(line: Line<'_>) -> Result<(&str, Vec<String>), ParseError> {
+    let (name, targets) = line.split_once(" -> ")?;
+    let targets = targets.split(", ").map(|s| s.to_string()).collect();
+    Ok((name, targets))
+}
+
+#[derive(Clone)]
+struct Network {
+    flip_flops: HashMap<String, FlipFlopModule>,
+    conjunctions: HashMap<String, ConjunctionModule>,
+    broadcast_targets: Vec<String>,
+}
+
+impl Network {
+    /// `module` and every module that sends pulses to it, however indirectly
+    fn upstream(&self, module: &str) -> HashSet<String> {
+        let mut sources: HashMap<&str, Vec<&str>> = HashMap::new();
+        let modules = self
+            .flip_flops
+            .values()
+            .map(|m| (&m.name, &m.targets))
+            .chain(self.conjunctions.values().map(|m| (&m.name, &m.targets)));
+        for (name, targets) in modules {
+            for target in targets {
+                sources.entry(target).or_default().push(name);
+            }
+        }
+        let graph = graph::from_fn(|module: &&str| {
+            sources
+                .get(module)
+                .into_iter()
+                .flatten()
+                .map(|&source| (source, 1))
+                .collect()
+        });
+        graph::bfs(&graph, [module])
+            .nodes()
+            .iter()
+            .map(|module| module.to_string())
+            .collect()
+    }
+
+    /// Only the `modules` of the network, which behave just as in the whole network if
+    /// every module that sends to them is among them
+    fn restricted_to(&self, modules: &HashSet<String>) -> Network {
+        Network {
+            flip_flops: self
+                .flip_flops
+                .iter()
+                .filter(|(name, _)| modules.contains(*name))
+                .map(|(name, module)| (name.clone(), module.clone()))
+                .collect(),
+            conjunctions: self
+                .conjunctions
+                .iter()
+                .filter(|(name, _)| modules.contains(*name))
+                .map(|(name, module)| (name.clone(), module.clone()))
+                .collect(),
+            broadcast_targets: self
+                .broadcast_targets
+                .iter()
+                .filter(|target| modules.contains(*target))
+                .cloned()
+                .collect(),
+        }
+    }
+
+    /// What the modules remember between presses, which decides everything that
+    /// happens on later presses. The maps are only ever updated in place, so they keep
+    /// iterating in the same order.
+    fn state(&self) -> (Vec<bool>, Vec<PulseWidth>) {
+        let flip_flops = self
+            .flip_flops
+            .values()
+            .map(|module| matches!(module.state, FlipFlipState::On))
+            .collect();
+        let memories = self
+            .conjunctions
+            .values()
+            .flat_map(|module| module.state.values().copied())
+            .collect();
+        (flip_flops, memories)
+    }
+}
+
+/// What happened during a single push of the button
+struct Press {
+    num_low: usize,
+    num_high: usize,
+    /// Sources of the high pulses sent to the watched conjunction
+    high_inputs: Vec<String>,
+}
+
+fn simulate(network: &mut Network, cycle_num: usize, watched: Option<&str>) -> Press {
+    let mut pulses: VecDeque<Pulse> = VecDeque::from(
+        network
+            .broadcast_targets
+            .iter()
+            .map(|t| Pulse {
+                width: PulseWidth::Low,
+                source: "broadcaster".to_string(),
+                target: t.clone(),
+            })
+            .collect::<Vec<Pulse>>(),
+    );
+
+    // The button sends a low pulse to the broadcaster
+    let mut press = Press {
+        num_low: 1,
+        num_high: 0,
+        high_inputs: Vec::new(),
+    };
+
+    while let Some(pulse) = pulses.pop_front() {
+        match pulse.width {
+            PulseWidth::Low => press.num_low += 1,
+            PulseWidth::High => press.num_high += 1,
+        }
+        if Some(pulse.target.as_str()) == watched && pulse.width == PulseWidth::High {
+            debug!(
+                "{} input high at cycle {} from pulse {:?}",
+                pulse.target, cycle_num, pulse
+            );
+            press.high_inputs.push(pulse.source.clone());
+        }
+        if let Some(flip_flop) = network.flip_flops.get_mut(&pulse.target) {
+            let new_pulses = flip_flop.handle_pulse(pulse.width);
+            pulses.extend(new_pulses);
+        } else if let Some(conjunction) = network.conjunctions.get_mut(&pulse.target) {
+            let new_pulses = conjunction.handle_pulse(pulse);
+            pulses.extend(new_pulses);
+        }
+    }
+    press
+}
+
+fn parse_network(input: &str) -> Result<Network> {
+    let mut flip_flops: HashMap<String, FlipFlopModule> = HashMap::new();
+    let mut conjunctions: HashMap<String, ConjunctionModule> = HashMap::new();
+
+    let mut broadcast_targets: Vec<String> = Vec::new();
+
+    let mut source_target_pairs: Vec<(String, String)> = Vec::new();
+
+    for line in parse::lines(input) {
+        let (name, targets) = parse_module(line)?;
+        if let Some(name) = name.strip_prefix('%') {
+            let module = FlipFlopModule::new(name, targets);
+            for target in module.targets.iter() {
+                source_target_pairs.push((module.name.clone(), target.clone()));
+            }
+            flip_flops.insert(module.name.clone(), module);
+        } else if let Some(name) = name.strip_prefix('&') {
+            let module = ConjunctionModule::new(name, targets);
+            for target in module.targets.iter() {
+                source_target_pairs.push((module.name.clone(), target.clone()));
+            }
+            conjunctions.insert(module.name.clone(), module);
+        } else if name == "broadcaster" {
+            broadcast_targets.extend(targets);
+        } else {
+            return Err(line
+                .invalid(name, "a '%' or '&' module, or the broadcaster")
+                .into());
+        }
+    }
+    debug!("broadcast_targets: {:?}", broadcast_targets);
+
+    for (source, target) in source_target_pairs {
+        if let Some(conjunction) = conjunctions.get_mut(&target) {
+            conjunction.add_source(source);
+        }
+    }
+
+    Ok(Network {
+        flip_flops,
+        conjunctions,
+        broadcast_targets,
+    })
+}
+
+pub fn part1(input: &str) -> Result<usize> {
+    let mut network = parse_network(input)?;
+
+    let mut num_low = 0;
+    let mut num_high = 0;
+    for i in 0..1000 {
+        let press = simulate(&mut network, i, None);
+        num_low += press.num_low;
+        num_high += press.num_high;
+    }
+
+    Ok(num_low * num_high)
+}
+
+/// The presses, counted from 0, at which `input` of `conjunction` sends it a high pulse,
+/// found by pressing the button on the modules upstream of the input until they repeat
+/// their state
+fn find_high_presses(network: &Network, conjunction: &str, input: &str) -> cycle::Occurrences {
+    let counter = network.restricted_to(&network.upstream(input));
+    // The modules, and how many times the button has been pressed
+    let press = |(network, presses): &mut (Network, usize)| {
+        let press = simulate(network, *presses, Some(conjunction));
+        *presses += 1;
+        press.high_inputs.iter().any(|source| source == input)
+    };
+    let found = cycle::find_cycle(
+        (counter.clone(), 0),
+        |state| {
+            press(state);
+        },
+        |(network, _)| network.state(),
+        cycle::Mode::Hash,
+    );
+
+    let mut state = (counter, 0);
+    let mut times = Vec::new();
+    while state.1 < found.start + found.period {
+        if press(&mut state) {
+            times.push(state.1 - 1);
+        }
+    }
+    cycle::Occurrences {
+        times,
+        cycle: found,
+    }
+}
+
+/// `rx` is fed by the conjunction `hb`, or the configured one, which sends it a low pulse
+/// once all its inputs have sent it a high pulse during the same press. Each input is
+/// driven by its own part of the network, which runs in a loop, so the presses at which
+/// the inputs go high line up by the Chinese remainder theorem. The loops need not start
+/// right away, nor have coprime lengths.
+pub fn part2(input: &str) -> Result<usize> {
+    let network = parse_network(input)?;
+    let name: String = CONJUNCTION.get()?;
+    let Some(conjunction) = network.conjunctions.get(&name) else {
+        bail!("the network has no conjunction module {}", name);
+    };
+    if conjunction.state.is_empty() {
+        bail!("nothing sends pulses to {}", name);
+    }
+    let inputs: Vec<cycle::Occurrences> = conjunction
+        .state
+        .keys()
+        .map(|input| find_high_presses(&network, &name, input))
+        .collect();
+    for input in &inputs {
+        debug!("loop of {:?}", input.cycle);
+    }
+
+    // The presses are counted from 0
+    cycle::first_common(&inputs)
+        .map(|press| press + 1)
+        .ok_or_else(|| anyhow!("the inputs of {} never all go high together", name))
+}
+
+pub fn day() -> Day {
+    Day {
+        number: 20,
+        dir: env!("CARGO_MANIFEST_DIR"),
+        part1: Some(|input| Ok(part1(input)?.to_string())),
+        part2: Some(|input| Ok(part2(input)?.to_string())),
+        generate: Some(gen::generate),
+        render: None,
+        dot: Some(dot::export),
+        parse: Some(|input| {
+            parse_network(input)?;
+            Ok(())
+        }),
+        params: &[CONJUNCTION],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("example.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(EXAMPLE).unwrap(), 11687500);
+    }
+
+    #[test]
+    fn part2_periods_with_common_factors() {
+        // `ia` goes high every second press and `ib` every fourth
+        let input =
+            "broadcaster -> a, b\n%a -> ia\n&ia -> hb\n%b -> c\n%c -> ib\n&ib -> hb\n&hb -> rx";
+        assert_eq!(part2(input).unwrap(), 4);
+    }
+}
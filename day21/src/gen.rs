@@ -0,0 +1,56 @@
+//! Random gardens, shaped like the real ones so that part 2 applies.
+use common::gen::StdRng;
+use rand::Rng;
+
+/// A garden of 131 by 131 tiles with the start in the middle, where `size` is the
+/// percentage of rocks, at most 40.
+///
+/// As in the real gardens, the middle row and column, the border and a diamond around
+/// the start are free of rocks, so the shortest paths into the other copies of the
+/// garden go straight.
+pub fn generate(rng: &mut StdRng, size: usize) -> String {
+    let density = size.min(40) as f64 / 100.0;
+    let mut out = String::new();
+    for y in 0..131i64 {
+        for x in 0..131i64 {
+            let from_center = (x - 65).abs() + (y - 65).abs();
+            let clear = x == 65
+                || y == 65
+                || x == 0
+                || x == 130
+                || y == 0
+                || y == 130
+                || (63..=66).contains(&from_center);
+            out.push(match (x, y) {
+                (65, 65) => 'S',
+                _ if !clear && rng.gen_bool(density) => '#',
+                _ => '.',
+            });
+        }
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::gen::rng;
+
+    #[test]
+    fn empty_garden() {
+        let input = generate(&mut rng(0), 0);
+        // Every plot within an odd number of steps
+        assert_eq!(crate::part1(&input).unwrap(), 65 * 65);
+        assert_eq!(crate::part2(&input).unwrap(), 26501366 * 26501366);
+    }
+
+    #[test]
+    fn generated_inputs_are_solved() {
+        for seed in 0..3 {
+            let input = generate(&mut rng(seed), 20);
+            assert!(crate::part1(&input).unwrap() <= 65 * 65);
+            assert!(crate::part2(&input).unwrap() <= 26501366 * 26501366);
+        }
+    }
+}
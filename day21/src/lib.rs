@@ -0,0 +1,360 @@
+pub mod gen;
+pub mod reference;
+pub mod render;
+
+use anyhow::{bail, Result};
+use common::config::Param;
+use common::geom::Pos;
+use common::graph;
+use common::grid::Grid;
+use common::parse::ParseError;
+use common::Day;
+use log::{debug, trace};
+use std::{
+    cmp::Ordering::{Equal, Greater, Less},
+    collections::HashMap,
+};
+
+const STEPS: Param = Param::new(21, "steps", "64", "Steps the elf takes in part 1");
+const INFINITE_STEPS: Param = Param::new(
+    21,
+    "infinite_steps",
+    "26501365",
+    "Steps the elf takes in the infinite garden of part 2",
+);
+
+struct Garden {
+    /// Whether each tile is a garden plot, as opposed to a rock
+    plots: Grid<bool>,
+    start_pos: (usize, usize),
+}
+
+impl Garden {
+    fn from_string(input: &str) -> Result<Garden> {
+        let tiles = Grid::parse(input, |c| "#.S".contains(c).then_some(c))?;
+        let start_pos = tiles
+            .find(&'S')
+            .ok_or_else(|| ParseError::incomplete("start position"))?;
+
+        Ok(Garden {
+            plots: tiles.map(|&c| c != '#'),
+            start_pos,
+        })
+    }
+}
+
+/// Walking distance from the starting point to every plot, `usize::MAX` if unreachable
+fn find_distances_to_points(plots: &Grid<bool>, starting_point: (usize, usize)) -> Grid<usize> {
+    let mut distances = Grid::filled(plots.width(), plots.height(), usize::MAX);
+    if !plots[starting_point] {
+        return distances;
+    }
+    let walks = graph::from_fn(|&pos| {
+        plots
+            .neighbours4(pos)
+            .filter(|&next| plots[next])
+            .map(|next| (next, 1))
+            .collect()
+    });
+    let paths = graph::bfs(&walks, [starting_point]);
+    for (&pos, distance) in paths.iter() {
+        distances[pos] = distance;
+    }
+    trace!(
+        "Longest distance for {:?} is {}",
+        starting_point,
+        paths
+            .iter()
+            .map(|(_, distance)| distance)
+            .max()
+            .unwrap_or(0)
+    );
+
+    distances
+}
+
+/// The tiles at a manhattan distance of d from the origin, as one tile for each of the
+/// axes and each of the quadrants, with how many tiles it stands for. The tiles of a
+/// quadrant all have the same corner closest to the origin, at the same distance, so one
+/// of them is enough.
+fn get_tiles_at_distance(d: i64) -> Vec<(Pos, usize)> {
+    if d == 0 {
+        return vec![(Pos { x: 0, y: 0 }, 1)];
+    }
+    let mut out = Vec::new();
+    for (x, y) in [(d, 0), (-d, 0), (0, d), (0, -d)] {
+        out.push((Pos { x, y }, 1));
+    }
+    if d > 1 {
+        for (x, y) in [(1, d - 1), (-1, d - 1), (1, 1 - d), (-1, 1 - d)] {
+            out.push((Pos { x, y }, d as usize - 1));
+        }
+    }
+
+    out
+}
+
+/// We shouldn't be using a euclidean circle; but a cirlce in the manhattan distance
+/// That actually makes it easier. We just need to iterate over all the pairs of
+/// points With |x|+|y| = (something). By symmetry we just pick the points with
+/// positive x,y and then complete the rest.
+fn count_num_lattice_points_manhattan(d: f64) -> (usize, usize, Vec<(Pos, usize)>) {
+    let mut num_odd = 0;
+    let mut num_even = 0;
+    let boundary_width: i64 = 2;
+    let r = d.ceil() as i64;
+    let r_inner = r - boundary_width;
+    let r_outer = r + boundary_width;
+
+    for y in -r_inner..=r_inner {
+        let x_length = r_inner - y.abs();
+
+        let total_points = 2 * x_length + 1;
+        let (odd, even) = if (y + x_length) % 2 != 0 {
+            ((total_points + 1) / 2, total_points / 2)
+        } else {
+            (total_points / 2, (total_points + 1) / 2)
+        };
+        num_odd += odd as usize;
+        num_even += even as usize;
+    }
+
+    let mut overlap_points = Vec::new();
+    for r in r_inner + 1..=r_outer {
+        overlap_points.extend(get_tiles_at_distance(r))
+    }
+
+    (num_odd, num_even, overlap_points)
+}
+/// For a tile of `size` by `size`, find the coordinates of the corner closest to the
+/// origin
+fn closest_corner(tile_pos: Pos, size: i64) -> (Pos, Pos) {
+    let corner = |tile: i64| match tile.cmp(&0) {
+        Equal => size / 2,
+        Less => size - 1,
+        Greater => 0,
+    };
+    let (x, y) = (corner(tile_pos.x), corner(tile_pos.y));
+    (
+        Pos { x, y },
+        Pos {
+            x: x + size * tile_pos.x,
+            y: y + size * tile_pos.y,
+        },
+    )
+}
+
+fn num_reachable_from(
+    corner: Pos,
+    distance_left: usize,
+    corner_to_distances: &HashMap<(i64, i64), Grid<usize>>,
+) -> usize {
+    corner_to_distances[&(corner.x, corner.y)]
+        .iter()
+        .filter(|(_, &dist)| dist <= distance_left && dist % 2 == distance_left % 2)
+        .count()
+}
+
+/// Number of plots that can be reached in exactly `num_steps` steps within the garden.
+/// Plots can be reached in exactly n steps if they can be reached in fewer steps with
+/// the same parity, by walking back and forth.
+fn num_reachable_in_garden(garden: &Garden, num_steps: usize) -> usize {
+    find_distances_to_points(&garden.plots, garden.start_pos)
+        .iter()
+        .filter(|(_, &dist)| dist <= num_steps && dist % 2 == num_steps % 2)
+        .count()
+}
+
+pub fn part1(input: &str) -> Result<usize> {
+    let garden = Garden::from_string(input)?;
+
+    Ok(num_reachable_in_garden(&garden, STEPS.get()?))
+}
+
+/// Number of plots that can be reached in exactly `num_steps` steps when the garden
+/// repeats infinitely in every direction. Only works for gardens shaped like the real
+/// ones: square, of an odd size with the start in the middle, with `num_steps` half the
+/// size more than a multiple of it, and with rocks sparse enough that the shortest path
+/// into every other copy enters it at the nearest corner or edge.
+fn num_reachable_in_infinite_garden(garden: &Garden, num_steps: usize) -> usize {
+    let plots = &garden.plots;
+    let size = plots.width() as i64;
+    let center = Pos::new(size / 2, size / 2);
+    let sides = [0, size / 2, size - 1];
+    let corner_to_distances = sides
+        .iter()
+        .flat_map(|&x| sides.iter().map(move |&y| (x, y)))
+        .map(|corner| {
+            (
+                corner,
+                find_distances_to_points(plots, (corner.0 as usize, corner.1 as usize)),
+            )
+        })
+        .collect::<HashMap<_, _>>();
+
+    for (i, row) in plots.rows().enumerate() {
+        if row.iter().all(|&is_plot| is_plot) {
+            debug!("row i={} has no rocks", i);
+        }
+    }
+    for (j, mut col) in plots.columns().enumerate() {
+        if col.all(|&is_plot| is_plot) {
+            debug!("col j={} has no rocks", j);
+        }
+    }
+    debug!("Start pos {:?}", garden.start_pos);
+
+    // let (num_plots_in_odd, num_plots_in_even) = num_odd_even_plots(&table_mat);
+    // Far enough to reach every plot of the tile
+    let (odd, even) = (size as usize + 2, size as usize + 1);
+    let num_plots_in_odd = num_reachable_from(center, odd, &corner_to_distances);
+    let num_plots_in_even = num_reachable_from(center, even, &corner_to_distances);
+    debug!(
+        "Num odd: {}, num even: {}",
+        num_plots_in_odd, num_plots_in_even
+    );
+
+    let mut distance_cache: HashMap<(Pos, usize), usize> = HashMap::new();
+
+    let radius_tiles = ((num_steps as f64) / size as f64).max(0.0);
+
+    let mut num_reachable = 0;
+
+    let (num_odd_tiles, num_even_tiles, boundary_points) =
+        count_num_lattice_points_manhattan(radius_tiles - 0.5);
+
+    if num_steps.is_multiple_of(2) {
+        num_reachable += num_even_tiles * num_plots_in_even + num_odd_tiles * num_plots_in_odd;
+    } else {
+        num_reachable += num_odd_tiles * num_plots_in_even + num_even_tiles * num_plots_in_odd;
+    }
+    for (tile, num_tiles) in boundary_points {
+        let (corner_mod, corner) = closest_corner(tile, size);
+        // Absolute distance to origin ignoring obstacles
+        let d = corner.manhattan(center) as usize;
+        if d > num_steps {
+            continue;
+        }
+        let distance_remaining = num_steps - d;
+        let num_reachable_from_tile = *distance_cache
+            .entry((corner_mod, distance_remaining))
+            .or_insert_with(|| {
+                num_reachable_from(corner_mod, distance_remaining, &corner_to_distances)
+            });
+        num_reachable += num_reachable_from_tile * num_tiles;
+    }
+    debug!("Num steps: {}, Num reachable: {}", num_steps, num_reachable);
+
+    num_reachable
+}
+
+/// Number of plots that can be reached in exactly `num_steps` steps in the infinite
+/// garden, for gardens and step counts that [`num_reachable_in_infinite_garden`] handles,
+/// and an error for the others rather than a wrong answer
+fn reachable_in_infinite_garden(input: &str, num_steps: usize) -> Result<usize> {
+    let garden = Garden::from_string(input)?;
+    let plots = &garden.plots;
+    let (width, height) = (plots.width(), plots.height());
+    if width != height || width % 2 == 0 {
+        bail!(
+            "expected a square garden of an odd size, got {}x{}",
+            width,
+            height
+        );
+    }
+    let (size, middle) = (width, width / 2);
+    if garden.start_pos != (middle, middle) {
+        bail!("expected the start in the middle of the garden");
+    }
+    if num_steps % size != middle {
+        bail!(
+            "expected a number of steps {} more than a multiple of {}, got {}",
+            middle,
+            size,
+            num_steps
+        );
+    }
+    let clear_row = |y| plots.row(y).iter().all(|&is_plot| is_plot);
+    let clear_column = |x| plots.column(x).all(|&is_plot| is_plot);
+    if ![0, middle, size - 1]
+        .into_iter()
+        .all(|i| clear_row(i) && clear_column(i))
+    {
+        bail!("expected no rocks in the middle row and column and on the border");
+    }
+
+    Ok(num_reachable_in_infinite_garden(&garden, num_steps))
+}
+
+pub fn part2(input: &str) -> Result<usize> {
+    reachable_in_infinite_garden(input, INFINITE_STEPS.get()?)
+}
+
+pub fn day() -> Day {
+    Day {
+        number: 21,
+        dir: env!("CARGO_MANIFEST_DIR"),
+        part1: Some(|input| Ok(part1(input)?.to_string())),
+        part2: Some(|input| Ok(part2(input)?.to_string())),
+        generate: Some(gen::generate),
+        render: Some(render::render),
+        dot: None,
+        parse: Some(|input| {
+            Garden::from_string(input)?;
+            Ok(())
+        }),
+        params: &[STEPS, INFINITE_STEPS],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("example.txt");
+
+    #[test]
+    fn reachable_in_6_steps() {
+        let garden = Garden::from_string(EXAMPLE).unwrap();
+        assert_eq!(num_reachable_in_garden(&garden, 6), 16);
+    }
+
+    #[test]
+    fn gardens_of_other_sizes() {
+        let row = |middle: char| format!("{}{}{}\n", ".".repeat(5), middle, ".".repeat(5));
+        let input = format!("{}{}{}", row('.').repeat(5), row('S'), row('.').repeat(5));
+        let garden = Garden::from_string(&input).unwrap();
+        // Without rocks every plot of the right parity within reach counts
+        assert_eq!(
+            num_reachable_in_infinite_garden(&garden, 5 + 2 * 11),
+            28 * 28
+        );
+        assert!(part2(&input[12..]).is_err());
+        assert!(reachable_in_infinite_garden(&input, 5 + 2 * 11).is_ok());
+        assert!(reachable_in_infinite_garden(&input, 6).is_err());
+    }
+
+    #[test]
+    fn tiny_gardens() {
+        // Without rocks every plot of the right parity within reach counts
+        assert_eq!(reachable_in_infinite_garden("S", 1).unwrap(), 4);
+        assert_eq!(
+            reachable_in_infinite_garden("S", 26501365).unwrap(),
+            26501366 * 26501366
+        );
+        let garden = "...\n.S.\n...\n";
+        assert_eq!(reachable_in_infinite_garden(garden, 4).unwrap(), 25);
+        assert_eq!(
+            reachable_in_infinite_garden(garden, 26501365).unwrap(),
+            26501366 * 26501366
+        );
+    }
+
+    #[test]
+    fn example_is_not_shaped_like_the_real_gardens() {
+        // Its middle row and column have rocks, so the answers would be wrong
+        for num_steps in [6, 10, 16, 50, 100] {
+            assert!(reachable_in_infinite_garden(EXAMPLE, num_steps).is_err());
+        }
+    }
+}
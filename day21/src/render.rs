@@ -0,0 +1,23 @@
+//! The garden after the steps of part 1: the plots the elf can end on in green, those
+//! it passes on the way in pale green, and the rocks in dark grey.
+use crate::{find_distances_to_points, Garden, STEPS};
+use anyhow::Result;
+use common::render::{Drawing, Rgb};
+
+pub fn render(input: &str) -> Result<Drawing> {
+    let garden = Garden::from_string(input)?;
+    let steps: usize = STEPS.get()?;
+    let distances = find_distances_to_points(&garden.plots, garden.start_pos);
+    let mut drawing = Drawing::grid(garden.plots.width(), garden.plots.height(), Rgb::WHITE);
+    for (pos, &distance) in distances.iter() {
+        let color = match (garden.plots[pos], distance) {
+            (false, _) => Rgb(70, 70, 70),
+            (true, d) if d <= steps && d % 2 == steps % 2 => Rgb::GREEN,
+            (true, d) if d <= steps => Rgb(190, 230, 190),
+            (true, _) => continue,
+        };
+        drawing.cell(pos, color);
+    }
+    drawing.cell(garden.start_pos, Rgb::RED);
+    Ok(drawing)
+}
@@ -0,0 +1,58 @@
+//! Random snapshots of falling bricks.
+use common::gen::StdRng;
+use rand::Rng;
+
+/// `size` bricks of up to four cubes over a 10 by 10 area, in a random order.
+///
+/// Every brick is dropped above all bricks below it, with some gap, so no two bricks
+/// overlap and the bricks still have to settle.
+pub fn generate(rng: &mut StdRng, size: usize) -> String {
+    let mut heights = [[0usize; 10]; 10];
+    let mut lines = Vec::new();
+    for _ in 0..size {
+        let length = rng.gen_range(0..4);
+        let mut end = [0; 3];
+        end[rng.gen_range(0..3)] = length;
+        let x = rng.gen_range(0..10 - end[0]);
+        let y = rng.gen_range(0..10 - end[1]);
+        let top = (x..=x + end[0])
+            .flat_map(|x| (y..=y + end[1]).map(move |y| (x, y)))
+            .map(|(x, y)| heights[x][y])
+            .max()
+            .unwrap();
+        let z = top + 1 + rng.gen_range(0..3);
+        for row in &mut heights[x..=x + end[0]] {
+            for height in &mut row[y..=y + end[1]] {
+                *height = z + end[2];
+            }
+        }
+        lines.push(format!(
+            "{},{},{}~{},{},{}",
+            x,
+            y,
+            z,
+            x + end[0],
+            y + end[1],
+            z + end[2]
+        ));
+    }
+    common::gen::shuffled_lines(rng, lines)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::gen::rng;
+
+    #[test]
+    fn generated_inputs_are_solved() {
+        for seed in 0..20 {
+            let input = generate(&mut rng(seed), 100);
+            // The top brick can always be disintegrated
+            let part1 = crate::part1(&input).unwrap();
+            assert!((1..=100).contains(&part1));
+            // Only the other bricks can make bricks fall
+            assert!(crate::part2(&input).unwrap() <= (100 - part1) * 99);
+        }
+    }
+}
@@ -0,0 +1,259 @@
+pub mod gen;
+
+use std::collections::{HashMap, HashSet};
+use std::fmt::Debug;
+
+use anyhow::Result;
+use common::geom::{Pos3, UPos};
+use common::parallel;
+use common::parse::{self, Line, ParseError};
+use common::Day;
+use log::trace;
+
+/// We need to first load all the bricks from the file, and make a struct for them Then
+/// we need to give each brick an id. Then we make a hashmap mapping each (x,y)
+/// coordinate that has any bricks above it to a list of brick ids
+///
+/// To solve the first part of the problem we just need to find the list of bricks that
+/// do not have any bricks _above_ them So we need to make a simple comparison function
+/// for bricks that tells whether a brick is above or below it.
+///
+/// Then we iterate over all the bricks, use the hashmap to find potential bricks that
+/// could be aboce them. If there are none then we add one to the counter.
+
+#[derive(Clone)]
+struct Brick {
+    pos1: Pos3<usize>,
+    pos2: Pos3<usize>,
+}
+
+impl Debug for Brick {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}~{:?}", self.pos1, self.pos2)
+    }
+}
+
+fn parse_pos3(line: Line, s: &str) -> Result<Pos3<usize>, ParseError> {
+    let mut split_comma = s.split(',');
+    let mut next = || match split_comma.next() {
+        Some(c) => line.parse(c, "a coordinate"),
+        None => Err(line.invalid(s, "three coordinates")),
+    };
+    Ok(Pos3::new(next()?, next()?, next()?))
+}
+
+/// The position of a block as seen from above
+fn project(pos: &Pos3<usize>) -> UPos {
+    UPos::new(pos.x, pos.y)
+}
+
+impl Brick {
+    /// Parses a brick of the form `1,0,1~1,2,1`, whose first end is the lowest
+    fn from_line(line: Line) -> Result<Self, ParseError> {
+        let (end1, end2) = line.split_once("~")?;
+        let pos1 = parse_pos3(line, end1)?;
+        let pos2 = parse_pos3(line, end2)?;
+        if pos1.x > pos2.x || pos1.y > pos2.y || pos1.z > pos2.z {
+            return Err(line.invalid(end2, "an end not before the start of the brick"));
+        }
+        Ok(Self { pos1, pos2 })
+    }
+
+    fn blocks(&self) -> Vec<Pos3<usize>> {
+        let mut out = Vec::new();
+        for x in self.pos1.x..=self.pos2.x {
+            for y in self.pos1.y..=self.pos2.y {
+                for z in self.pos1.z..=self.pos2.z {
+                    out.push(Pos3::new(x, y, z));
+                }
+            }
+        }
+        out
+    }
+
+    fn move_down(&mut self, z: usize) {
+        let height = self.pos2.z - self.pos1.z;
+        self.pos1.z = z;
+        self.pos2.z = z + height;
+    }
+}
+
+fn find_new_z(brick: &Brick, brick_map: &HashMap<UPos, Vec<usize>>, bricks: &[Brick]) -> usize {
+    brick
+        .blocks()
+        .iter()
+        .flat_map(|pos3| brick_map.get(&project(pos3)).unwrap())
+        .map(|&brick_index| bricks[brick_index].pos2.z + 1)
+        .filter(|&z| z <= brick.pos1.z)
+        .max()
+        .unwrap_or(1)
+}
+
+fn find_num_falling_bricks(
+    brick_index: usize,
+    supports: &[HashSet<usize>],
+    supported_by: &[HashSet<usize>],
+) -> usize {
+    let mut falling_bricks = HashSet::new();
+    falling_bricks.insert(brick_index);
+
+    let mut queue = vec![brick_index];
+    while let Some(i) = queue.pop() {
+        for j in &supports[i] {
+            if supported_by[*j].iter().all(|k| falling_bricks.contains(k)) {
+                falling_bricks.insert(*j);
+                queue.push(*j);
+            }
+        }
+    }
+    falling_bricks.len() - 1
+}
+
+/// For every brick, the bricks directly on top of it and the bricks directly below it,
+/// once all bricks have settled
+struct Supports {
+    supports: Vec<HashSet<usize>>,
+    supported_by: Vec<HashSet<usize>>,
+}
+
+fn parse_bricks(input: &str) -> Result<Vec<Brick>, ParseError> {
+    parse::lines(input).map(Brick::from_line).collect()
+}
+
+fn settle(input: &str) -> Result<Supports, ParseError> {
+    let mut bricks = parse_bricks(input)?;
+    let mut brick_map: HashMap<UPos, Vec<usize>> = HashMap::new();
+    for (i, brick) in bricks.iter().enumerate() {
+        brick
+            .blocks()
+            .iter()
+            .map(project)
+            .for_each(|pos| brick_map.entry(pos).or_default().push(i));
+    }
+    let mut updates = Vec::new();
+    loop {
+        for (i, brick) in bricks.iter().enumerate() {
+            let new_z = find_new_z(brick, &brick_map, &bricks);
+            if new_z != brick.pos1.z {
+                updates.push((i, new_z));
+            }
+        }
+        if updates.is_empty() {
+            break;
+        }
+
+        trace!("Updating {} bricks", updates.len());
+        for (i, new_z) in updates.drain(..) {
+            bricks[i].move_down(new_z);
+        }
+    }
+
+    let mut supports: Vec<HashSet<usize>> = vec![HashSet::new(); bricks.len()];
+    let mut supported_by: Vec<HashSet<usize>> = vec![HashSet::new(); bricks.len()];
+    for (i, brick) in bricks.iter().enumerate() {
+        for j in brick
+            .blocks()
+            .iter()
+            .flat_map(|pos3| brick_map.get(&project(pos3)).unwrap())
+            .filter(|&&j| j != i)
+        {
+            let other_brick = &bricks[*j];
+            if other_brick.pos1.z == brick.pos2.z + 1 {
+                supports[i].insert(*j);
+                supported_by[*j].insert(i);
+            }
+        }
+    }
+
+    Ok(Supports {
+        supports,
+        supported_by,
+    })
+}
+
+/// A brick can be disintegrated if every brick on top of it rests on another brick too
+pub fn part1(input: &str) -> Result<usize> {
+    let Supports {
+        supports,
+        supported_by,
+    } = settle(input)?;
+
+    let num_disintegratable = supports
+        .iter()
+        .filter(|supported_inds| supported_inds.iter().all(|&j| supported_by[j].len() > 1))
+        .count();
+
+    Ok(num_disintegratable)
+}
+
+pub fn part2(input: &str) -> Result<usize> {
+    let Supports {
+        supports,
+        supported_by,
+    } = settle(input)?;
+
+    let bricks: Vec<usize> = (0..supports.len()).collect();
+    let num_will_fall = parallel::map(&bricks, |&i| {
+        find_num_falling_bricks(i, &supports, &supported_by)
+    })
+    .iter()
+    .sum();
+
+    Ok(num_will_fall)
+}
+
+pub fn day() -> Day {
+    Day {
+        number: 22,
+        dir: env!("CARGO_MANIFEST_DIR"),
+        part1: Some(|input| Ok(part1(input)?.to_string())),
+        part2: Some(|input| Ok(part2(input)?.to_string())),
+        generate: Some(gen::generate),
+        render: None,
+        dot: None,
+        parse: Some(|input| {
+            parse_bricks(input)?;
+            Ok(())
+        }),
+        params: &[],
+    }
+}
+
+// I think we actually need to compute the final configuration, and then compute which
+// brick supports which brick.
+// To compute this we can do the following. For each brick, check how much empty space
+// is below it using the projection hashmap. This is the maximum z-coordinate of the
+// bricks below it. (Or zero).
+// Then we move the brick down by that amount.
+// We repeat the procedure until all bricks are in their resting position.
+
+// For part two: we have to use the two 'supports' and 'supported_by' to recursively
+// Find the bricks that will fall. And this really can be recursive; we can use a hashmap
+// If a bricks falls, and it supports another brick uniquely, that one will also fall.
+
+// No it's not recursive. But we start with a hashset of bricks that will fall. Then for
+// each brick on top of that one, we check if it's supported only by bricks in the set,
+// if so it also falls and is added to the set. We maintain a queue of bricks to check.
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("example.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(EXAMPLE).unwrap(), 5);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(EXAMPLE).unwrap(), 7);
+    }
+
+    #[test]
+    fn threads_give_the_same_answer() {
+        let input = gen::generate(&mut common::gen::rng(0), 20);
+        parallel::assert_thread_independent(part2, &input);
+    }
+}
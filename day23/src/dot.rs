@@ -0,0 +1,71 @@
+//! The forks of part 2 and the trails between them, labelled with their lengths.
+//! Highlighting marks the longest hike from the start to the exit.
+use crate::{find_forks, longest_hike_over, parse_hiking_map};
+use anyhow::Result;
+use common::dot::{Attributes, Dot};
+use std::collections::HashMap;
+
+pub fn export(input: &str, highlight: bool) -> Result<Dot> {
+    let forks = find_forks(&parse_hiking_map(input)?, false)?;
+
+    // The longest trail between each pair of forks the hike goes through, which is the
+    // one it takes
+    let mut hike: HashMap<(usize, usize), usize> = HashMap::new();
+    if highlight {
+        let (_, nodes) = longest_hike_over(&forks)?;
+        for pair in nodes.windows(2) {
+            let (from, to) = (pair[0].min(pair[1]), pair[0].max(pair[1]));
+            let length = forks.edges[from]
+                .iter()
+                .filter(|&&(fork, _)| fork == to)
+                .map(|&(_, length)| length)
+                .max();
+            if let Some(length) = length {
+                hike.insert((from, to), length);
+            }
+        }
+    }
+
+    let mut dot = Dot::undirected("day23");
+    for (id, tile) in forks.tiles.iter().enumerate() {
+        let attributes = Attributes::new().label(format!("{},{}", tile.x, tile.y));
+        let attributes = match id {
+            _ if id == 0 || id == forks.exit => attributes.shape("doublecircle"),
+            _ if hike.keys().any(|&(from, to)| from == id || to == id) => attributes.color("red"),
+            _ => attributes,
+        };
+        dot.node(id, attributes);
+    }
+    for (from, trails) in forks.edges.iter().enumerate() {
+        // Trails are found from both ends
+        for &(to, length) in trails.iter().filter(|&&(to, _)| from < to) {
+            let attributes = Attributes::new().label(length);
+            let attributes = match hike.get(&(from, to)) {
+                Some(&longest) if longest == length => attributes.highlight(),
+                _ => attributes,
+            };
+            dot.edge(from, to, attributes);
+        }
+    }
+    Ok(dot)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hike_lengths_add_up() {
+        let input = include_str!("example.txt");
+        let dot = export(input, true).unwrap().to_string();
+        let hiked: usize = dot
+            .lines()
+            .filter(|line| line.contains("penwidth"))
+            .map(|line| {
+                let label = line.split("label=\"").nth(1).unwrap();
+                label[..label.find('"').unwrap()].parse::<usize>().unwrap()
+            })
+            .sum();
+        assert_eq!(hiked, crate::part2(input).unwrap());
+    }
+}
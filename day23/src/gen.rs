@@ -0,0 +1,77 @@
+//! Random hiking maps: straight trails between the junctions of a lattice.
+use common::gen::StdRng;
+use common::grid::Grid;
+use rand::Rng;
+
+/// A map whose junctions form a lattice of `size / 4` by `size / 4`, at least 2 and at
+/// most 6, which is as large as the real maps.
+///
+/// Junctions are 4 to 8 tiles apart, and the trails between them are kept at random, but
+/// always along a staircase from the start to the exit. Like in the real maps, trails
+/// leave and enter junctions down slopes that point right or down.
+pub fn generate(rng: &mut StdRng, size: usize) -> String {
+    let n = (size / 4).clamp(2, 6);
+    let mut positions = || {
+        let mut at = vec![1];
+        for _ in 1..n {
+            at.push(at.last().unwrap() + rng.gen_range(4..=8));
+        }
+        at
+    };
+    let (xs, ys) = (positions(), positions());
+    let (width, height) = (xs[n - 1] + 2, ys[n - 1] + 2);
+    let mut map = Grid::filled(width, height, '#');
+    map[(1, 0)] = '.';
+    map[(width - 2, height - 1)] = '.';
+
+    // Whether the trail right of and below every junction is kept
+    let mut right = vec![vec![false; n]; n];
+    let mut down = vec![vec![false; n]; n];
+    let (mut i, mut j) = (0, 0);
+    while (i, j) != (n - 1, n - 1) {
+        if j == n - 1 || (i < n - 1 && rng.gen_bool(0.5)) {
+            right[i][j] = true;
+            i += 1;
+        } else {
+            down[i][j] = true;
+            j += 1;
+        }
+    }
+
+    for j in 0..n {
+        for i in 0..n {
+            map[(xs[i], ys[j])] = '.';
+            if i + 1 < n && (right[i][j] || rng.gen_bool(0.8)) {
+                for x in xs[i] + 1..xs[i + 1] {
+                    map[(x, ys[j])] = '.';
+                }
+                map[(xs[i] + 1, ys[j])] = '>';
+                map[(xs[i + 1] - 1, ys[j])] = '>';
+            }
+            if j + 1 < n && (down[i][j] || rng.gen_bool(0.8)) {
+                for y in ys[j] + 1..ys[j + 1] {
+                    map[(xs[i], y)] = '.';
+                }
+                map[(xs[i], ys[j] + 1)] = 'v';
+                map[(xs[i], ys[j + 1] - 1)] = 'v';
+            }
+        }
+    }
+    map.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::gen::rng;
+
+    #[test]
+    fn generated_inputs_are_solved() {
+        for seed in 0..10 {
+            let input = generate(&mut rng(seed), 16);
+            let part1 = crate::part1(&input).unwrap();
+            assert!(part1 > 0);
+            assert!(crate::part2(&input).unwrap() >= part1);
+        }
+    }
+}
@@ -0,0 +1,285 @@
+pub mod dot;
+pub mod gen;
+pub mod render;
+
+use std::{collections::HashMap, fmt::Debug};
+
+use anyhow::{anyhow, bail, Result};
+use common::geom::Direction;
+use common::graph;
+use common::grid::Grid;
+use common::instrument::Search;
+use common::Day;
+use log::{debug, trace};
+
+// First thing we just have to build that graph Let's just make an enum with
+// posibilities and parse based on that
+// Then we just make a Vec<Vec<Tile>>.
+
+// We then make a function that starts at a tile and then
+// finds all the reachable slopes (or exit) form there.
+// I guess the start and exit tile are special
+
+#[derive(Copy, Clone, PartialEq, Hash, Eq)]
+enum TileType {
+    Start,
+    Exit,
+    Path,
+    Forest,
+    SlopeUp,
+    SlopeDown,
+    SlopeLeft,
+    SlopeRight,
+}
+
+impl TileType {
+    fn from_char(c: char) -> Option<Self> {
+        match c {
+            '.' => Some(Self::Path),
+            '#' => Some(Self::Forest),
+            '^' => Some(Self::SlopeUp),
+            'v' => Some(Self::SlopeDown),
+            '<' => Some(Self::SlopeLeft),
+            '>' => Some(Self::SlopeRight),
+            _ => None,
+        }
+    }
+
+    /// The direction a slope leads down to
+    fn slope(self) -> Option<Direction> {
+        match self {
+            Self::SlopeUp => Some(Direction::Up),
+            Self::SlopeDown => Some(Direction::Down),
+            Self::SlopeLeft => Some(Direction::Left),
+            Self::SlopeRight => Some(Direction::Right),
+            _ => None,
+        }
+    }
+}
+
+impl Debug for TileType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let c = match self {
+            TileType::Start => 'S',
+            TileType::Exit => 'E',
+            TileType::Path => '.',
+            TileType::Forest => '#',
+            TileType::SlopeUp => '^',
+            TileType::SlopeDown => 'v',
+            TileType::SlopeLeft => '<',
+            TileType::SlopeRight => '>',
+        };
+        write!(f, "{}", c)
+    }
+}
+
+#[derive(Hash, Clone, PartialEq, Eq)]
+
+struct Tile {
+    x: usize,
+    y: usize,
+    tile_type: TileType,
+}
+
+impl Debug for Tile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({:?},{},{})", self.tile_type, self.x, self.y)
+    }
+}
+
+impl Tile {
+    fn new(x: usize, y: usize, tile_type: TileType) -> Self {
+        Self { x, y, tile_type }
+    }
+
+    fn step(&self, hiking_map: &Grid<TileType>, direction: Direction) -> Option<Tile> {
+        let (x, y) = hiking_map.step((self.x, self.y), direction)?;
+        Some(Tile::new(x, y, hiking_map[(x, y)]))
+    }
+
+    /// We need to have a second method that ignores the slope condition, because that's
+    /// needed when determining whether something is a fork
+    fn neighbors(&self, hiking_map: &Grid<TileType>, check_slope: bool) -> Vec<Tile> {
+        Direction::ALL
+            .iter()
+            .filter_map(|&direction| {
+                let tile = self.step(hiking_map, direction)?;
+                let uphill = tile.tile_type.slope() == Some(direction.reverse());
+                match tile.tile_type {
+                    TileType::Forest => None,
+                    _ if uphill && check_slope => None,
+                    _ => Some(tile),
+                }
+            })
+            .collect()
+    }
+
+    fn is_fork(&self, hiking_map: &Grid<TileType>) -> bool {
+        self.neighbors(hiking_map, false).len() > 2
+            || self.tile_type == TileType::Start
+            || self.tile_type == TileType::Exit
+    }
+}
+
+/// The shortest walks from a fork to the tiles that can be reached from it without
+/// passing another fork
+fn walks_from_fork(
+    input_tile: &Tile,
+    hiking_map: &Grid<TileType>,
+    check_slope: bool,
+) -> graph::Paths<Tile> {
+    // The walk stops at every fork but the one it starts from
+    let trails = graph::from_fn(|tile: &Tile| {
+        if tile != input_tile && tile.is_fork(hiking_map) {
+            return Vec::new();
+        }
+        tile.neighbors(hiking_map, check_slope)
+            .into_iter()
+            .map(|next| (next, 1))
+            .collect()
+    });
+    graph::bfs(&trails, [input_tile.clone()])
+}
+
+/// Finds distances to all forks reachable from this tile without passing another fork.
+/// The input tile must be a fork itself
+fn find_nodes(
+    input_tile: Tile,
+    hiking_map: &Grid<TileType>,
+    check_slope: bool,
+) -> Vec<(Tile, usize)> {
+    walks_from_fork(&input_tile, hiking_map, check_slope)
+        .iter()
+        .filter(|(tile, _)| **tile != input_tile && tile.is_fork(hiking_map))
+        .map(|(tile, d)| (tile.clone(), d))
+        .collect()
+}
+
+/// The map with its start and exit tiles marked
+fn parse_hiking_map(input: &str) -> Result<Grid<TileType>> {
+    let mut hiking_map = Grid::parse(input, TileType::from_char)?;
+    let num_rows = hiking_map.height();
+    let num_cols = hiking_map.width();
+    if num_cols < 3 || num_rows < 2 {
+        bail!(
+            "the map is {}x{}, too small for a start and an exit",
+            num_cols,
+            num_rows
+        );
+    }
+    hiking_map[(1, 0)] = TileType::Start;
+    hiking_map[(num_cols - 2, num_rows - 1)] = TileType::Exit;
+    Ok(hiking_map)
+}
+
+/// The forks, numbered from the start in the order they are found, and the trails
+/// between them
+struct Forks {
+    tiles: Vec<Tile>,
+    /// The `(fork, length)` of the trails from every fork
+    edges: Vec<Vec<(usize, usize)>>,
+    exit: usize,
+}
+
+/// With `check_slope` set, slopes can only be walked downhill.
+fn find_forks(hiking_map: &Grid<TileType>, check_slope: bool) -> Result<Forks> {
+    let num_rows = hiking_map.height();
+    let num_cols = hiking_map.width();
+    let start_tile = Tile::new(1, 0, TileType::Start);
+    let exit_tile = Tile::new(num_cols - 2, num_rows - 1, TileType::Exit);
+
+    let mut forks = vec![start_tile.clone()];
+    let mut fork_ids = HashMap::from([(start_tile, 0)]);
+    let mut edges: Vec<Vec<(usize, usize)>> = Vec::new();
+    while edges.len() < forks.len() {
+        let tile = forks[edges.len()].clone();
+        let connections = find_nodes(tile.clone(), hiking_map, check_slope);
+        trace!("{:?} has connections {:?}", tile, connections);
+        let mut fork_edges = Vec::new();
+        for (other_tile, d) in connections {
+            let id = *fork_ids.entry(other_tile.clone()).or_insert_with(|| {
+                forks.push(other_tile);
+                forks.len() - 1
+            });
+            fork_edges.push((id, d));
+        }
+        edges.push(fork_edges);
+    }
+    debug!("{} forks", forks.len());
+    if forks.len() > 64 {
+        bail!("{} forks are too many to search every hike", forks.len());
+    }
+
+    let Some(&exit) = fork_ids.get(&exit_tile) else {
+        bail!("the exit cannot be reached");
+    };
+    Ok(Forks {
+        tiles: forks,
+        edges,
+        exit,
+    })
+}
+
+/// Length of the longest hike from start to exit that never steps on the same tile twice.
+/// With `check_slope` set, slopes can only be walked downhill.
+fn longest_hike(input: &str, check_slope: bool) -> Result<usize> {
+    let forks = find_forks(&parse_hiking_map(input)?, check_slope)?;
+    Ok(longest_hike_over(&forks)?.0)
+}
+
+/// The longest hike over the forks, with its length and the forks along it
+fn longest_hike_over(forks: &Forks) -> Result<(usize, Vec<usize>)> {
+    let search = Search::new("longest hike");
+    let hike = graph::longest_path(&forks.edges, 0, forks.exit, &search)?
+        .ok_or_else(|| anyhow!("the exit cannot be reached"))?;
+    search.finish();
+    Ok(hike)
+}
+
+pub fn part1(input: &str) -> Result<usize> {
+    longest_hike(input, true)
+}
+
+pub fn part2(input: &str) -> Result<usize> {
+    longest_hike(input, false)
+}
+
+pub fn day() -> Day {
+    Day {
+        number: 23,
+        dir: env!("CARGO_MANIFEST_DIR"),
+        part1: Some(|input| Ok(part1(input)?.to_string())),
+        part2: Some(|input| Ok(part2(input)?.to_string())),
+        generate: Some(gen::generate),
+        render: Some(render::render),
+        dot: Some(dot::export),
+        parse: Some(|input| {
+            parse_hiking_map(input)?;
+            Ok(())
+        }),
+        params: &[],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("example.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(EXAMPLE).unwrap(), 94);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(EXAMPLE).unwrap(), 154);
+    }
+
+    #[test]
+    fn tiny_map_is_an_error() {
+        assert!(part1("#").is_err());
+        assert!(part1("#.#").is_err());
+    }
+}
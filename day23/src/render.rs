@@ -0,0 +1,39 @@
+//! The trails through the forest, with the longest hike of part 1 in red over that of
+//! part 2 in blue, and the forks where the hikes can choose their way marked.
+use crate::{find_forks, longest_hike_over, parse_hiking_map, walks_from_fork, TileType};
+use anyhow::Result;
+use common::render::{Drawing, Rgb};
+
+pub fn render(input: &str) -> Result<Drawing> {
+    let hiking_map = parse_hiking_map(input)?;
+    let mut drawing = Drawing::grid(hiking_map.width(), hiking_map.height(), Rgb::WHITE);
+    for (pos, tile) in hiking_map.iter() {
+        let color = match tile {
+            TileType::Forest => Rgb(40, 100, 50),
+            TileType::Path => continue,
+            TileType::Start | TileType::Exit => Rgb::YELLOW,
+            _ => Rgb(200, 170, 120),
+        };
+        drawing.cell(pos, color);
+    }
+
+    for (check_slope, color, width) in [(false, Rgb::BLUE, 0.7), (true, Rgb::RED, 0.3)] {
+        let forks = find_forks(&hiking_map, check_slope)?;
+        let (_, hike) = longest_hike_over(&forks)?;
+        // Between forks the hike takes the only trail there is
+        let mut tiles = vec![forks.tiles[0].clone()];
+        for pair in hike.windows(2) {
+            let (from, to) = (&forks.tiles[pair[0]], &forks.tiles[pair[1]]);
+            let trail = walks_from_fork(from, &hiking_map, check_slope)
+                .path(to)
+                .expect("forks are connected along their trails");
+            tiles.extend(trail.into_iter().skip(1));
+        }
+        let points = tiles
+            .iter()
+            .map(|tile| Drawing::centre((tile.x, tile.y)))
+            .collect();
+        drawing.path(points, color, width);
+    }
+    Ok(drawing)
+}
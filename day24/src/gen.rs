@@ -0,0 +1,57 @@
+//! Random hailstones that a planted rock trajectory hits one by one.
+use common::gen::StdRng;
+use rand::Rng;
+
+/// `size` hailstones, at least three. See [`hailstones`].
+pub fn generate(rng: &mut StdRng, size: usize) -> String {
+    hailstones(rng, size).0
+}
+
+/// Hailstones and the answer to part 2.
+///
+/// The rock starts around the test area of part 1, at a speed of at most 300 in x and y
+/// so that part 2 finds it. Every hailstone is hit at a different time, and is placed so
+/// that it is where the rock is at that time.
+pub fn hailstones(rng: &mut StdRng, size: usize) -> (String, i128) {
+    let rock: [i64; 3] =
+        std::array::from_fn(|_| rng.gen_range(250_000_000_000_000..350_000_000_000_000));
+    let rock_velocity: [i64; 3] = std::array::from_fn(|_| rng.gen_range(-300..=300));
+
+    let mut out = String::new();
+    let mut times = std::collections::HashSet::new();
+    while times.len() < size.max(3) {
+        let time = rng.gen_range(1_000_000_000..100_000_000_000);
+        if !times.insert(time) {
+            continue;
+        }
+        // Different from the rock in every direction, so every hailstone gets hit once
+        let velocity: [i64; 3] = std::array::from_fn(|i| loop {
+            let v = rng.gen_range(-500..=500);
+            if v != 0 && v != rock_velocity[i] {
+                break v;
+            }
+        });
+        let position: [i64; 3] =
+            std::array::from_fn(|i| rock[i] + (rock_velocity[i] - velocity[i]) * time);
+        out.push_str(&format!(
+            "{}, {}, {} @ {}, {}, {}\n",
+            position[0], position[1], position[2], velocity[0], velocity[1], velocity[2]
+        ));
+    }
+    (out, rock.iter().map(|&x| x as i128).sum())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::gen::rng;
+
+    #[test]
+    fn planted_rock() {
+        for seed in 0..3 {
+            let (input, part2) = hailstones(&mut rng(seed), 10);
+            assert!(crate::part1(&input).unwrap() <= 10 * 9 / 2);
+            assert_eq!(crate::part2(&input).unwrap(), part2);
+        }
+    }
+}
@@ -0,0 +1,254 @@
+pub mod gen;
+
+use std::fmt::Debug;
+
+use common::geom::Pos3;
+
+use anyhow::{anyhow, bail, Result};
+use common::config::Param;
+use common::parse::{self, Line, ParseError};
+use common::Day;
+
+const MIN_POS: Param = Param::new(
+    24,
+    "min_pos",
+    "200000000000000",
+    "Lowest x and y of the test area of part 1",
+);
+const MAX_POS: Param = Param::new(
+    24,
+    "max_pos",
+    "400000000000000",
+    "Highest x and y of the test area of part 1",
+);
+
+/// Largest rock velocity component tried in part 2
+const MAX_ROCK_SPEED: i128 = 1000;
+
+struct Hailstone3 {
+    position: Pos3,
+    velocity: Pos3,
+}
+
+impl Hailstone3 {
+    /// Parses a hailstone of the form `19, 13, 30 @ -2,  1, -2`
+    fn from_line(line: Line) -> Result<Self, ParseError> {
+        let (position, velocity) = line.split_once(" @ ")?;
+        Ok(Self {
+            position: parse_pos3(line, position, "a position")?,
+            velocity: parse_pos3(line, velocity, "a velocity")?,
+        })
+    }
+
+    fn project(&self) -> Hailstone2 {
+        Hailstone2 {
+            position: self.position.xy(),
+            velocity: self.velocity.xy(),
+        }
+    }
+}
+
+impl Debug for Hailstone3 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?} @ {:?}", self.position, self.velocity)
+    }
+}
+
+struct Hailstone2 {
+    position: (i64, i64),
+    velocity: (i64, i64),
+}
+impl Debug for Hailstone2 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?} @ {:?}", self.position, self.velocity)
+    }
+}
+
+fn intersection_point(stone1: &Hailstone2, stone2: &Hailstone2) -> Option<(f64, f64)> {
+    let (x1, y1) = stone1.position;
+    let (x2, y2) = stone2.position;
+    let (vx1, vy1) = stone1.velocity;
+    let (vx2, vy2) = stone2.velocity;
+
+    let vel_cross = (vx1 * vy2 - vx2 * vy1) as f64;
+
+    if vel_cross == 0.0 {
+        // parallel
+        return None;
+    }
+
+    // Intersection means that (x1+vx1*t, y1+vy1*t) == (x2+vx2*s, y2+vy2*s)
+    let t = (vx2 * y1 - vx2 * y2 - vy2 * x1 + vy2 * x2) as f64 / vel_cross;
+    let s = -(vx1 * y2 - vx1 * y1 - vy1 * x2 + vy1 * x1) as f64 / vel_cross;
+
+    if t < 0.0 || s < 0.0 {
+        return None;
+    }
+
+    let x = x1 as f64 + vx1 as f64 * t;
+    let y = y1 as f64 + vy1 as f64 * t;
+
+    Some((x, y))
+}
+
+fn count_intersections(hailstones: &[Hailstone2], min_pos: i64, max_pos: i64) -> usize {
+    let mut num_intersect = 0;
+    for (i, hailstone) in hailstones.iter().enumerate() {
+        for other in &hailstones[i + 1..] {
+            if let Some((x, y)) = intersection_point(hailstone, other) {
+                if x >= min_pos as f64
+                    && x <= max_pos as f64
+                    && y >= min_pos as f64
+                    && y <= max_pos as f64
+                {
+                    num_intersect += 1;
+                }
+            }
+        }
+    }
+    num_intersect
+}
+
+/// Finds the rock position if the rock moves at `velocity`, by intersecting the paths of
+/// the hailstones in the frame of the rock. All hits have to happen at integer times.
+fn rock_position(hailstones: &[Hailstone3], velocity: (i128, i128)) -> Option<(i128, i128, i128)> {
+    let relative = |stone: &Hailstone3| {
+        (
+            (stone.position.x as i128, stone.position.y as i128),
+            (
+                stone.velocity.x as i128 - velocity.0,
+                stone.velocity.y as i128 - velocity.1,
+            ),
+        )
+    };
+    let ((x0, y0), (ax, ay)) = relative(&hailstones[0]);
+    let ((x1, y1), (bx, by)) = relative(&hailstones[1]);
+    let cross = ax * by - ay * bx;
+    if cross == 0 {
+        return None;
+    }
+    let (dx, dy) = (x1 - x0, y1 - y0);
+    let t0_num = dx * by - dy * bx;
+    if t0_num % cross != 0 {
+        return None;
+    }
+    let t0 = t0_num / cross;
+    let (px, py) = (x0 + ax * t0, y0 + ay * t0);
+
+    // Every hailstone has to pass through (px, py) in the rock frame
+    let mut times = Vec::with_capacity(hailstones.len());
+    for stone in hailstones {
+        let ((x, y), (vx, vy)) = relative(stone);
+        let t = if vx != 0 {
+            (px - x) / vx
+        } else if vy != 0 {
+            (py - y) / vy
+        } else {
+            0
+        };
+        if t < 0 || x + vx * t != px || y + vy * t != py {
+            return None;
+        }
+        times.push(t);
+    }
+
+    // The z velocity follows from two hailstones hit at different times
+    let hit_z =
+        |i: usize| hailstones[i].position.z as i128 + hailstones[i].velocity.z as i128 * times[i];
+    let j = (1..hailstones.len()).find(|&j| times[j] != times[0])?;
+    let vz_num = hit_z(j) - hit_z(0);
+    if vz_num % (times[j] - times[0]) != 0 {
+        return None;
+    }
+    let vz = vz_num / (times[j] - times[0]);
+    let pz = hit_z(0) - vz * times[0];
+    (0..hailstones.len())
+        .all(|i| pz + vz * times[i] == hit_z(i))
+        .then_some((px, py, pz))
+}
+
+/// Parses three comma separated coordinates, which may be padded with spaces
+fn parse_pos3(line: Line, s: &str, expected: &str) -> Result<Pos3, ParseError> {
+    let mut coordinates = s.split(',').map(str::trim);
+    let mut next = || match coordinates.next() {
+        Some(c) => line.parse(c, "an integer coordinate"),
+        None => Err(line.invalid(s, format!("{} with three coordinates", expected))),
+    };
+    let pos = Pos3::new(next()?, next()?, next()?);
+    if coordinates.next().is_some() {
+        return Err(line.invalid(s, format!("{} with three coordinates", expected)));
+    }
+    Ok(pos)
+}
+
+fn parse_hailstones(input: &str) -> Result<Vec<Hailstone3>, ParseError> {
+    parse::lines(input).map(Hailstone3::from_line).collect()
+}
+
+pub fn part1(input: &str) -> Result<usize> {
+    let hailstones = parse_hailstones(input)?
+        .iter()
+        .map(Hailstone3::project)
+        .collect::<Vec<_>>();
+    let (min_pos, max_pos) = (MIN_POS.get()?, MAX_POS.get()?);
+    if min_pos > max_pos {
+        bail!("the test area from {} to {} is empty", min_pos, max_pos);
+    }
+    Ok(count_intersections(&hailstones, min_pos, max_pos))
+}
+
+/// Tries every rock velocity up to `MAX_ROCK_SPEED` in x and y and solves for the rest
+/// exactly.
+pub fn part2(input: &str) -> Result<i128> {
+    let hailstones = parse_hailstones(input)?;
+    if hailstones.len() < 3 {
+        return Err(anyhow!("need at least three hailstones"));
+    }
+    for vx in -MAX_ROCK_SPEED..=MAX_ROCK_SPEED {
+        for vy in -MAX_ROCK_SPEED..=MAX_ROCK_SPEED {
+            if let Some((px, py, pz)) = rock_position(&hailstones, (vx, vy)) {
+                return Ok(px + py + pz);
+            }
+        }
+    }
+    Err(anyhow!("no rock trajectory hits every hailstone"))
+}
+
+pub fn day() -> Day {
+    Day {
+        number: 24,
+        dir: env!("CARGO_MANIFEST_DIR"),
+        part1: Some(|input| Ok(part1(input)?.to_string())),
+        part2: Some(|input| Ok(part2(input)?.to_string())),
+        generate: Some(gen::generate),
+        render: None,
+        dot: None,
+        parse: Some(|input| {
+            parse_hailstones(input)?;
+            Ok(())
+        }),
+        params: &[MIN_POS, MAX_POS],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("example.txt");
+
+    #[test]
+    fn intersections_in_test_area() {
+        let hailstones = parse_hailstones(EXAMPLE)
+            .unwrap()
+            .iter()
+            .map(Hailstone3::project)
+            .collect::<Vec<_>>();
+        assert_eq!(count_intersections(&hailstones, 7, 27), 2);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(EXAMPLE).unwrap(), 47);
+    }
+}
@@ -0,0 +1,64 @@
+//! The wiring diagram, one node per component. Highlighting marks the three wires to cut
+//! and fills the two groups they separate with their own colour.
+use crate::{find_cut, Graph};
+use anyhow::Result;
+use common::dot::{Attributes, Dot};
+use std::collections::HashSet;
+
+pub fn export(input: &str, highlight: bool) -> Result<Dot> {
+    let graph = Graph::from_str(input)?;
+    let cut = match highlight {
+        true => find_cut(&graph)?,
+        false => HashSet::new(),
+    };
+    let mut groups = vec![0; graph.num_nodes()];
+    if highlight {
+        for (group, nodes) in graph.components(&cut).iter().enumerate() {
+            for &node in nodes {
+                groups[node] = group + 1;
+            }
+        }
+    }
+
+    let mut dot = Dot::undirected("day25");
+    for (node, name) in graph.edge_names.iter().enumerate() {
+        let attributes = match groups[node] {
+            1 => Attributes::new().fill("lightblue"),
+            2 => Attributes::new().fill("palegreen"),
+            _ => Attributes::new(),
+        };
+        dot.node(name, attributes);
+    }
+    for &(a, b) in &graph.edges {
+        let attributes = match cut.contains(&(a, b)) {
+            true => Attributes::new().highlight(),
+            false => Attributes::new(),
+        };
+        dot.edge(&graph.edge_names[a], &graph.edge_names[b], attributes);
+    }
+    Ok(dot)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cut_wires() {
+        let dot = export(include_str!("example.txt"), true)
+            .unwrap()
+            .to_string();
+        let cut: Vec<&str> = dot
+            .lines()
+            .filter(|line| line.contains("penwidth"))
+            .map(|line| line.trim())
+            .collect();
+        assert_eq!(cut.len(), 3);
+        for (a, b) in [("hfx", "pzl"), ("bvb", "cmg"), ("nvd", "jqt")] {
+            let wire = |a, b| format!("\"{}\" -- \"{}\"", a, b);
+            assert!(cut
+                .iter()
+                .any(|line| line.starts_with(&wire(a, b)) || line.starts_with(&wire(b, a))));
+        }
+    }
+}
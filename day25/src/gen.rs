@@ -0,0 +1,75 @@
+//! Random wiring diagrams: two clusters of components joined by three wires.
+use common::gen::{names, StdRng};
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::collections::BTreeMap;
+
+/// A diagram of `size` components, at least 10. See [`diagram`].
+pub fn generate(rng: &mut StdRng, size: usize) -> String {
+    diagram(rng, size).0
+}
+
+/// A diagram and the answer to part 1.
+///
+/// Each cluster is wired as a ring in which every component is also wired to the one
+/// after the next, plus some random wires. Cutting a cluster in two takes at least four
+/// wires then, so the three wires between the clusters are the only cut of three.
+pub fn diagram(rng: &mut StdRng, size: usize) -> (String, usize) {
+    let size = size.max(10);
+    let first = rng.gen_range(5..=size - 5);
+    let mut nodes: Vec<usize> = (0..size).collect();
+    nodes.shuffle(rng);
+    let clusters = [&nodes[..first], &nodes[first..]];
+
+    let mut wires = Vec::new();
+    for cluster in clusters {
+        let n = cluster.len();
+        for i in 0..n {
+            wires.push((cluster[i], cluster[(i + 1) % n]));
+            wires.push((cluster[i], cluster[(i + 2) % n]));
+        }
+        for _ in 0..n / 2 {
+            let (a, b) = (rng.gen_range(0..n), rng.gen_range(0..n));
+            let wire = (cluster[a], cluster[b]);
+            if a != b && !wires.contains(&wire) && !wires.contains(&(wire.1, wire.0)) {
+                wires.push(wire);
+            }
+        }
+    }
+    for (a, b) in clusters[0]
+        .choose_multiple(rng, 3)
+        .zip(clusters[1].choose_multiple(rng, 3))
+    {
+        wires.push((*a, *b));
+    }
+
+    // Every wire is listed once, on the line of either of its components
+    let names = names(rng, size, 3);
+    let mut lines: BTreeMap<usize, Vec<&str>> = BTreeMap::new();
+    for (a, b) in wires {
+        let (a, b) = if rng.gen_bool(0.5) { (a, b) } else { (b, a) };
+        lines.entry(a).or_default().push(&names[b]);
+    }
+    let lines = lines
+        .into_iter()
+        .map(|(a, others)| format!("{}: {}", names[a], others.join(" ")))
+        .collect();
+    (
+        common::gen::shuffled_lines(rng, lines),
+        first * (size - first),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::gen::rng;
+
+    #[test]
+    fn planted_cut() {
+        for seed in 0..10 {
+            let (input, part1) = diagram(&mut rng(seed), 40);
+            assert_eq!(crate::part1(&input).unwrap(), part1);
+        }
+    }
+}
@@ -0,0 +1,234 @@
+pub mod dot;
+pub mod gen;
+pub mod reference;
+
+use anyhow::{anyhow, Result};
+use common::graph;
+use common::instrument::{Search, TimedOut};
+use common::parallel;
+use common::parse::{self, Line, ParseError};
+use common::Day;
+use log::{debug, trace};
+use std::collections::{HashMap, HashSet};
+
+struct Graph {
+    nodes: Vec<Vec<usize>>,
+    edges: Vec<(usize, usize)>,
+    edge_names: Vec<String>,
+}
+
+impl Graph {
+    fn _from_str_vecs(input: Vec<(&str, Vec<&str>)>) -> Self {
+        let mut edge_names_inv = HashMap::<String, usize>::new();
+        let mut edges = Vec::<(usize, usize)>::new();
+        let mut edge_names = Vec::<String>::new();
+        let mut counter = 0;
+        let mut nodes = Vec::<Vec<usize>>::new();
+
+        for (node_src, nodes_tgt) in input {
+            for node in (vec![node_src])
+                .into_iter()
+                .chain(nodes_tgt.iter().cloned())
+            {
+                if !edge_names_inv.contains_key(node) {
+                    edge_names_inv.insert(node.to_string(), counter);
+                    edge_names.push(node.to_string());
+                    nodes.push(Vec::new());
+                    counter += 1;
+                }
+            }
+            for node_tgt in nodes_tgt {
+                let src = edge_names_inv.get(node_src).unwrap();
+                let tgt = edge_names_inv.get(node_tgt).unwrap();
+                nodes[*src].push(*tgt);
+                nodes[*tgt].push(*src);
+                edges.push(order_edge(*src, *tgt));
+            }
+        }
+
+        Self {
+            nodes,
+            edges,
+            edge_names,
+        }
+    }
+
+    fn from_str(input: &str) -> Result<Self, ParseError> {
+        let edges = parse::lines(input)
+            .map(_edges_from_str)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self::_from_str_vecs(edges))
+    }
+
+    fn num_nodes(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// The product of the sizes of the two groups the graph falls apart in without the
+    /// `forbidden_edges`, or 0 if it does not fall apart in two
+    fn is_connected(&self, forbidden_edges: &HashSet<(usize, usize)>) -> usize {
+        match self.components(forbidden_edges).as_slice() {
+            [a, b] => a.len() * b.len(),
+            _ => 0,
+        }
+    }
+
+    /// The groups of nodes that stay connected without the `forbidden_edges`
+    fn components(&self, forbidden_edges: &HashSet<(usize, usize)>) -> Vec<Vec<usize>> {
+        let wires = graph::from_fn(|&node: &usize| {
+            self.nodes[node]
+                .iter()
+                .filter(|&&target| !forbidden_edges.contains(&order_edge(node, target)))
+                .map(|&target| (target, 1))
+                .collect()
+        });
+        graph::connected_components(&wires, 0..self.num_nodes())
+    }
+
+    /// Gives all the edges involved in shortests paths from the source node
+    fn edges_in_shortest_distancs(&self, source: usize) -> Vec<(usize, usize)> {
+        let wires = graph::from_fn(|&node: &usize| {
+            self.nodes[node].iter().map(|&target| (target, 1)).collect()
+        });
+        let paths = graph::bfs(&wires, [source]);
+
+        let mut edges = Vec::new();
+        for (&node, distance) in paths.iter() {
+            for &target in &self.nodes[node] {
+                if paths.cost(&target) == Some(distance + 1) {
+                    edges.push(order_edge(node, target));
+                }
+            }
+        }
+        edges
+    }
+}
+
+fn _edges_from_str(line: Line<'_>) -> Result<(&str, Vec<&str>), ParseError> {
+    let (node, targets) = line.split_once(": ")?;
+    Ok((node, targets.split(' ').collect::<Vec<_>>()))
+}
+
+fn order_edge(a: usize, b: usize) -> (usize, usize) {
+    if a < b {
+        (a, b)
+    } else {
+        (b, a)
+    }
+}
+
+// fn compute_distances(graph: &HashMap<usize, Vec<usize>>, node: usize) -> Vec<usize> {
+//     let mut distances = vec![usize::MAX; graph.len()];
+//     let mut queue = vec![(node, 0)];
+//     while let Some((node, dist)) = queue.pop() {
+//         if dist >= distances[node] {
+//             continue;
+//         }
+//         distances[node] = dist;
+//         for target in graph.get(&node).unwrap() {
+//             queue.push((*target, dist + 1));
+//         }
+//     }
+//     distances
+// }
+
+// fn compute_distance_matrix(graph: &HashMap<usize, Vec<usize>>) -> Vec<Vec<usize>> {
+//     let mut distances = vec![vec![usize::MAX; graph.len()]; graph.len()];
+//     for (i, node) in graph.keys().enumerate() {
+//         let node_distances = compute_distances(graph, *node);
+//         println!("Progress: {}/{}", i, graph.len());
+//         for (target, dist) in node_distances.iter().enumerate() {
+//             distances[*node][target] = *dist;
+//         }
+//     }
+//     distances
+// }
+
+/// The three wires to cut to split the machine in two. The wires that most shortest paths
+/// go through are tried first, as every path between the two groups crosses one of them.
+fn find_cut(graph: &Graph) -> Result<HashSet<(usize, usize)>> {
+    let mut edge_count: HashMap<(usize, usize), usize> = HashMap::new();
+
+    // The shortest paths from every node, and then every triple of wires from the busiest
+    // on, are the states
+    let search = Search::new("three-wire cut");
+    let nodes: Vec<usize> = (0..graph.num_nodes()).collect();
+    let edges_from_nodes = parallel::map(&nodes, |&node| {
+        let edges = graph.edges_in_shortest_distancs(node);
+        // Each of these takes a while
+        search.expand()?;
+        search.check()?;
+        Ok::<_, TimedOut>(edges)
+    });
+    for edges_from_node in edges_from_nodes {
+        let edges_from_node = edges_from_node?;
+        for edge in edges_from_node {
+            *edge_count.entry(edge).or_insert(0) += 1;
+        }
+    }
+    let mut betweennes: Vec<(usize, usize)> = Vec::new();
+    for (index, edge) in graph.edges.iter().enumerate() {
+        betweennes.push((index, *edge_count.get(edge).unwrap()));
+    }
+    betweennes.sort_by(|(_, x), (_, y)| y.cmp(x));
+    trace!("{:?}", betweennes);
+    for (i, (edge1, _)) in betweennes.iter().enumerate() {
+        for (j, (edge2, _)) in betweennes.iter().enumerate().skip(i) {
+            for (edge3, _) in betweennes.iter().skip(j) {
+                search.expand()?;
+                let mut forbidden_edges = HashSet::new();
+                forbidden_edges.insert(graph.edges[*edge1]);
+                forbidden_edges.insert(graph.edges[*edge2]);
+                forbidden_edges.insert(graph.edges[*edge3]);
+                let connected = graph.is_connected(&forbidden_edges);
+                if connected > 0 {
+                    debug!("Connected: {}", connected);
+                    search.finish();
+                    return Ok(forbidden_edges);
+                }
+            }
+        }
+    }
+
+    Err(anyhow!("no 3-edge cut found"))
+}
+
+pub fn part1(input: &str) -> Result<usize> {
+    let graph = Graph::from_str(input)?;
+    Ok(graph.is_connected(&find_cut(&graph)?))
+}
+
+pub fn day() -> Day {
+    Day {
+        number: 25,
+        dir: env!("CARGO_MANIFEST_DIR"),
+        part1: Some(|input| Ok(part1(input)?.to_string())),
+        part2: None,
+        generate: Some(gen::generate),
+        render: None,
+        dot: Some(dot::export),
+        parse: Some(|input| {
+            Graph::from_str(input)?;
+            Ok(())
+        }),
+        params: &[],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("example.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(EXAMPLE).unwrap(), 54);
+    }
+
+    #[test]
+    fn threads_give_the_same_answer() {
+        let input = gen::generate(&mut common::gen::rng(0), 12);
+        parallel::assert_thread_independent(part1, &input);
+    }
+}
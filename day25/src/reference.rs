@@ -0,0 +1,73 @@
+//! A naive solution, which tries every three wires instead of only the busiest ones.
+use anyhow::{anyhow, Result};
+use std::collections::HashMap;
+
+/// The size of the group of components connected to the first one, without the
+/// `cut` wires
+fn group_size(num_components: usize, wires: &[(usize, usize)], cut: [usize; 3]) -> usize {
+    let mut seen = vec![false; num_components];
+    seen[0] = true;
+    let mut stack = vec![0];
+    while let Some(component) = stack.pop() {
+        for (i, &(a, b)) in wires.iter().enumerate() {
+            let other = match component {
+                _ if cut.contains(&i) => continue,
+                c if c == a => b,
+                c if c == b => a,
+                _ => continue,
+            };
+            if !seen[other] {
+                seen[other] = true;
+                stack.push(other);
+            }
+        }
+    }
+    seen.iter().filter(|&&seen| seen).count()
+}
+
+pub fn part1(input: &str) -> Result<usize> {
+    let mut ids = HashMap::new();
+    let mut wires = Vec::new();
+    for line in input.lines() {
+        let (from, tos) = line
+            .split_once(": ")
+            .ok_or_else(|| anyhow!("invalid line {:?}", line))?;
+        for to in tos.split(' ') {
+            let mut id = |name| {
+                let next = ids.len();
+                *ids.entry(name).or_insert(next)
+            };
+            wires.push((id(from), id(to)));
+        }
+    }
+
+    let n = wires.len();
+    for i in 0..n {
+        for j in i + 1..n {
+            for k in j + 1..n {
+                let size = group_size(ids.len(), &wires, [i, j, k]);
+                if size < ids.len() {
+                    return Ok(size * (ids.len() - size));
+                }
+            }
+        }
+    }
+    Err(anyhow!("no 3-wire cut found"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::differential::assert_agree;
+
+    #[test]
+    fn part1_agrees() {
+        assert_agree(
+            |input| Ok(crate::part1(input)?.to_string()),
+            |input| Ok(part1(input)?.to_string()),
+            crate::gen::generate,
+            10..=16,
+            0..10,
+        );
+    }
+}
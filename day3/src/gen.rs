@@ -0,0 +1,43 @@
+//! Random engine schematics of numbers and symbols.
+use common::gen::StdRng;
+use rand::Rng;
+
+const SYMBOLS: &[u8] = b"*#+$/@=%&-";
+
+/// A square schematic of `size` lines, with a gear symbol `*` among the others.
+pub fn generate(rng: &mut StdRng, size: usize) -> String {
+    let width = size.max(1);
+    let mut out = String::new();
+    for _ in 0..size {
+        let mut line = String::new();
+        while line.len() < width {
+            match rng.gen_range(0..10) {
+                0 => line.push(SYMBOLS[rng.gen_range(0..SYMBOLS.len())] as char),
+                1..=3 => line.push_str(&rng.gen_range(1..1000).to_string()),
+                _ => {}
+            }
+            // Numbers and symbols are kept apart, so that numbers never run together
+            line.push('.');
+        }
+        line.truncate(width);
+        out.push_str(&line);
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::gen::rng;
+
+    #[test]
+    fn generated_inputs_are_solved() {
+        for seed in 0..20 {
+            let input = generate(&mut rng(seed), 30);
+            assert_eq!(input.lines().count(), 30);
+            crate::part1(&input).unwrap();
+            crate::part2(&input).unwrap();
+        }
+    }
+}
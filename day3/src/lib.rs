@@ -0,0 +1,191 @@
+pub mod gen;
+
+use anyhow::Result;
+use common::parse::{self, Line, ParseError};
+use common::Day;
+use log::trace;
+use regex::Regex;
+use std::collections::HashMap;
+
+fn process_line1(
+    line_prev: Option<&str>,
+    line: Line,
+    line_next: Option<&str>,
+    re_number: &Regex,
+    re_symbol: &Regex,
+) -> Result<u32, ParseError> {
+    let line_now = line.text;
+    let mut sum = 0;
+
+    for cap in re_number.find_iter(line_now) {
+        let num: u32 = line.parse(cap.as_str(), "a part number")?;
+        let start = cap.start().saturating_sub(1);
+        let end = std::cmp::min(cap.end() + 1, line_now.len());
+        let is_part_number = [line_prev, Some(line_now), line_next]
+            .into_iter()
+            .flatten()
+            .any(|line| re_symbol.is_match(&line[start..end]));
+        if is_part_number {
+            sum += num;
+        }
+    }
+    Ok(sum)
+}
+
+fn record_gear(
+    line_number: usize,
+    line_start: usize,
+    num: u32,
+    line_slice: &str,
+    gear_map: &mut HashMap<(usize, usize), Vec<u32>>,
+) {
+    if let Some(index) = line_slice.find('*') {
+        let vec = gear_map
+            .entry((line_number, line_start + index))
+            .or_default();
+        vec.push(num);
+        trace!("({},{}): {:?}", line_number, line_start + index, vec);
+    }
+}
+fn process_line2(
+    line_prev: Option<&str>,
+    line: Line,
+    line_next: Option<&str>,
+    re_number: &Regex,
+    line_number: usize,
+    gear_map: &mut HashMap<(usize, usize), Vec<u32>>,
+) -> Result<(), ParseError> {
+    let line_now = line.text;
+    for cap in re_number.find_iter(line_now) {
+        let num: u32 = line.parse(cap.as_str(), "a part number")?;
+        let start = cap.start().saturating_sub(1);
+        let end = std::cmp::min(cap.end() + 1, line_now.len());
+        if let Some(line_prev) = line_prev {
+            record_gear(
+                line_number - 1,
+                start,
+                num,
+                &line_prev[start..end],
+                gear_map,
+            );
+        }
+        record_gear(line_number, start, num, &line_now[start..end], gear_map);
+
+        if let Some(line_next) = line_next {
+            record_gear(
+                line_number + 1,
+                start,
+                num,
+                &line_next[start..end],
+                gear_map,
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Splits the schematic into lines, which all need to have the same length. Numbers are
+/// looked up in the lines above and below by byte offset, so only ASCII will do.
+fn parse_schematic(input: &str) -> Result<Vec<Line<'_>>, ParseError> {
+    let lines: Vec<Line> = parse::lines(input).collect();
+    for line in &lines {
+        if let Some((i, c)) = line.text.char_indices().find(|(_, c)| !c.is_ascii()) {
+            let token = &line.text[i..i + c.len_utf8()];
+            return Err(line.invalid(token, "an ASCII character"));
+        }
+    }
+    let width = lines.first().map_or(0, |line| line.text.len());
+    if let Some(line) = lines.iter().find(|line| line.text.len() != width) {
+        return Err(line.missing(format!("{} characters", width)));
+    }
+
+    Ok(lines)
+}
+
+/// Iterates over the lines of the schematic together with the lines above and below
+fn with_neighbours<'a>(
+    lines: &'a [Line<'a>],
+) -> impl Iterator<Item = (usize, Option<&'a str>, Line<'a>, Option<&'a str>)> {
+    lines.iter().enumerate().map(|(i, line)| {
+        let line_prev = i.checked_sub(1).map(|j| lines[j].text);
+        let line_next = lines.get(i + 1).map(|line| line.text);
+        (i, line_prev, *line, line_next)
+    })
+}
+
+pub fn part1(input: &str) -> Result<u32> {
+    let re_number: Regex = Regex::new(r"\d+").unwrap();
+    let re_symbol: Regex = Regex::new(r"[^\w\s\d.]").unwrap();
+
+    let lines = parse_schematic(input)?;
+    let mut sum = 0;
+    for (_, line_prev, line_now, line_next) in with_neighbours(&lines) {
+        sum += process_line1(line_prev, line_now, line_next, &re_number, &re_symbol)?;
+    }
+
+    Ok(sum)
+}
+
+pub fn part2(input: &str) -> Result<u32> {
+    let re_number: Regex = Regex::new(r"\d+").unwrap();
+
+    let lines = parse_schematic(input)?;
+    let gear_map = &mut HashMap::new();
+    for (line_number, line_prev, line_now, line_next) in with_neighbours(&lines) {
+        process_line2(
+            line_prev,
+            line_now,
+            line_next,
+            &re_number,
+            line_number,
+            gear_map,
+        )?;
+    }
+
+    let mut sum = 0;
+    for nums in gear_map.clone().into_values() {
+        if nums.len() == 2 {
+            sum += nums[0] * nums[1];
+        }
+    }
+
+    Ok(sum)
+}
+
+pub fn day() -> Day {
+    Day {
+        number: 3,
+        dir: env!("CARGO_MANIFEST_DIR"),
+        part1: Some(|input| Ok(part1(input)?.to_string())),
+        part2: Some(|input| Ok(part2(input)?.to_string())),
+        generate: Some(gen::generate),
+        render: None,
+        dot: None,
+        parse: None,
+        params: &[],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("example.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(EXAMPLE).unwrap(), 4361);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(EXAMPLE).unwrap(), 467835);
+    }
+
+    #[test]
+    fn non_ascii_is_an_error() {
+        assert!(part1("\u{fffd}1").is_err());
+        assert!(part2("\u{fffd}1").is_err());
+    }
+}
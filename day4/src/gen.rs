@@ -0,0 +1,58 @@
+//! Random scratchcards.
+use common::gen::StdRng;
+use rand::seq::{index, SliceRandom};
+use rand::Rng;
+
+/// `size` cards of 10 winning numbers and 25 numbers we have, out of 1 to 99.
+///
+/// Most cards have no matches, so that the number of copies in part 2 stays small, and
+/// cards never win copies of cards past the end of the table.
+pub fn generate(rng: &mut StdRng, size: usize) -> String {
+    let mut out = String::new();
+    for card in 1..=size {
+        let max_matches = (size - card).min(5);
+        let num_matches = match rng.gen_bool(0.7) {
+            true => 0,
+            false => rng.gen_range(0..=max_matches),
+        };
+        // The first 10 numbers win, and we have `num_matches` of them
+        let numbers: Vec<usize> = index::sample(rng, 99, 35 - num_matches)
+            .into_iter()
+            .map(|i| i + 1)
+            .collect();
+        let winning = &numbers[..10];
+        let mut ours = numbers[10..].to_vec();
+        ours.extend(&winning[..num_matches]);
+        ours.shuffle(rng);
+
+        let format = |numbers: &[usize]| {
+            numbers
+                .iter()
+                .map(|n| format!("{:2}", n))
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+        out.push_str(&format!(
+            "Card {:3}: {} | {}\n",
+            card,
+            format(winning),
+            format(&ours)
+        ));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::gen::rng;
+
+    #[test]
+    fn generated_inputs_are_solved() {
+        for seed in 0..20 {
+            let input = generate(&mut rng(seed), 100);
+            crate::part1(&input).unwrap();
+            assert!(crate::part2(&input).unwrap() >= 100);
+        }
+    }
+}
@@ -0,0 +1,153 @@
+pub mod gen;
+
+use anyhow::{anyhow, Result};
+use common::parse::{self, Line, ParseError};
+use common::Day;
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+
+fn extract_numbers_to_set(
+    line: Line,
+    input: &str,
+    re_number: &Regex,
+) -> Result<HashSet<u32>, ParseError> {
+    re_number
+        .find_iter(input)
+        .map(|cap| line.parse(cap.as_str(), "a number"))
+        .collect()
+}
+
+struct Card {
+    number: usize,
+    winning_numbers: HashSet<u32>,
+    our_numbers: HashSet<u32>,
+}
+
+impl Card {
+    fn from_line(line: Line, re_number: &Regex) -> Result<Self, ParseError> {
+        let (card, numbers) = line.split_once(":")?;
+        let (winning, ours) = numbers
+            .split_once('|')
+            .ok_or_else(|| line.missing("\"|\""))?;
+        let number = card
+            .strip_prefix("Card")
+            .ok_or_else(|| line.invalid(card, "\"Card <number>\""))?
+            .trim_start();
+
+        Ok(Self {
+            number: line.parse(number, "a card number")?,
+            winning_numbers: extract_numbers_to_set(line, winning, re_number)?,
+            our_numbers: extract_numbers_to_set(line, ours, re_number)?,
+        })
+    }
+
+    fn num_matches(&self) -> usize {
+        self.winning_numbers.intersection(&self.our_numbers).count()
+    }
+}
+
+fn process_line1(line: Line, re_number: &Regex) -> Result<u32> {
+    let intersection_num = Card::from_line(line, re_number)?.num_matches() as u32;
+
+    match intersection_num {
+        0 => Ok(0),
+        x => 2u32
+            .checked_pow(x - 1)
+            .ok_or_else(|| anyhow!("card with {} matches is worth too many points", x)),
+    }
+}
+
+fn process_line2(
+    line: Line,
+    re_number: &Regex,
+    count_hashmap: &mut HashMap<usize, u32>,
+) -> Result<u32> {
+    let card = Card::from_line(line, re_number)?;
+    let intersection_num = card.num_matches();
+    let card_number = card.number;
+
+    let self_count = *count_hashmap.entry(card_number).or_insert(1u32);
+    for i in card_number + 1..card_number + 1 + intersection_num {
+        let entry = count_hashmap.entry(i).or_insert(1u32);
+        *entry = entry
+            .checked_add(self_count)
+            .ok_or_else(|| anyhow!("too many copies of card {}", i))?;
+    }
+
+    Ok(self_count)
+}
+
+pub fn part1(input: &str) -> Result<u32> {
+    let re_number: Regex = Regex::new(r"\d+").unwrap();
+
+    let mut sum: u32 = 0;
+    for line in parse::lines(input) {
+        sum = sum
+            .checked_add(process_line1(line, &re_number)?)
+            .ok_or_else(|| anyhow!("too many points"))?;
+    }
+
+    Ok(sum)
+}
+
+pub fn part2(input: &str) -> Result<u32> {
+    let re_number: Regex = Regex::new(r"\d+").unwrap();
+    let mut count_hashmap = HashMap::new();
+
+    let mut sum: u32 = 0;
+    for line in parse::lines(input) {
+        let val = process_line2(line, &re_number, &mut count_hashmap)?;
+        sum = sum
+            .checked_add(val)
+            .ok_or_else(|| anyhow!("too many scratchcards"))?;
+    }
+
+    Ok(sum)
+}
+
+pub fn day() -> Day {
+    Day {
+        number: 4,
+        dir: env!("CARGO_MANIFEST_DIR"),
+        part1: Some(|input| Ok(part1(input)?.to_string())),
+        part2: Some(|input| Ok(part2(input)?.to_string())),
+        generate: Some(gen::generate),
+        render: None,
+        dot: None,
+        parse: None,
+        params: &[],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("example.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(EXAMPLE).unwrap(), 13);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(EXAMPLE).unwrap(), 30);
+    }
+
+    #[test]
+    fn too_many_points() {
+        let numbers: Vec<String> = (1..=33).map(|n| n.to_string()).collect();
+        let numbers = numbers.join(" ");
+        assert!(part1(&format!("Card 1: {} | {}", numbers, numbers)).is_err());
+    }
+
+    #[test]
+    fn too_many_scratchcards() {
+        // Every card wins a copy of the next two, which more than doubles the copies
+        let input: String = (1..=60)
+            .map(|i| format!("Card {}: 1 2 | 1 2\n", i))
+            .collect();
+        assert!(part2(&input).is_err());
+    }
+}
@@ -0,0 +1,82 @@
+//! Random almanacs: seed ranges and seven maps between the 32-bit categories.
+use common::gen::StdRng;
+use rand::seq::{index, SliceRandom};
+use rand::Rng;
+
+const CATEGORIES: [&str; 8] = [
+    "seed",
+    "soil",
+    "fertilizer",
+    "water",
+    "light",
+    "temperature",
+    "humidity",
+    "location",
+];
+
+/// Numbers are below 2^32, as in the real almanacs
+const LIMIT: usize = 1 << 32;
+
+/// Cuts `0..limit` into `size` ranges and moves them around, then leaves some of them
+/// out so that they map to themselves. No two sources overlap, and no two targets.
+fn map_entries(rng: &mut StdRng, size: usize, limit: usize) -> Vec<String> {
+    let mut cuts: Vec<usize> = index::sample(rng, limit - 1, size.max(1))
+        .into_iter()
+        .map(|cut| cut + 1)
+        .collect();
+    cuts.push(0);
+    cuts.push(limit);
+    cuts.sort();
+    let mut ranges: Vec<(usize, usize)> = cuts.windows(2).map(|w| (w[0], w[1] - w[0])).collect();
+    // The ranges are laid out again in a random order
+    ranges.shuffle(rng);
+    let mut tgt = 0;
+    let mut entries = Vec::new();
+    for (src, len) in ranges {
+        if rng.gen_bool(0.8) {
+            entries.push(format!("{} {} {}", tgt, src, len));
+        }
+        tgt += len;
+    }
+    entries
+}
+
+/// An almanac with `size` entries per map, and a seed range for every five entries.
+pub fn generate(rng: &mut StdRng, size: usize) -> String {
+    almanac(rng, size, LIMIT)
+}
+
+/// An almanac of numbers below `limit`, where seed ranges cover up to a fortieth of
+/// the numbers each.
+pub fn almanac(rng: &mut StdRng, size: usize, limit: usize) -> String {
+    let seeds: Vec<String> = (0..1 + size / 5)
+        .map(|_| {
+            let len = rng.gen_range(1..=(limit / 40).max(1));
+            format!("{} {}", rng.gen_range(0..=limit - len), len)
+        })
+        .collect();
+    let mut out = format!("seeds: {}\n", seeds.join(" "));
+    for names in CATEGORIES.windows(2) {
+        out.push_str(&format!("\n{}-to-{} map:\n", names[0], names[1]));
+        for entry in map_entries(rng, size, limit) {
+            out.push_str(&entry);
+            out.push('\n');
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::gen::rng;
+
+    #[test]
+    fn generated_inputs_are_solved() {
+        for seed in 0..20 {
+            let input = generate(&mut rng(seed), 20);
+            assert!(crate::part1(&input).unwrap() < LIMIT);
+            assert!(crate::part2(&input).unwrap() < LIMIT);
+        }
+    }
+}
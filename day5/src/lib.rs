@@ -0,0 +1,171 @@
+pub mod gen;
+pub mod reference;
+
+use anyhow::{anyhow, Result};
+use common::interval::{Interval, IntervalSet};
+use common::parse::{self, Line, ParseError};
+use common::Day;
+use log::{debug, trace};
+
+#[derive(Debug)]
+struct MapEntry {
+    tgt: usize,
+    src: usize,
+    len: usize,
+}
+
+impl MapEntry {
+    fn source(&self) -> Interval {
+        Interval::with_len(self.src as i64, self.len as i64)
+    }
+
+    fn offset(&self) -> i64 {
+        self.tgt as i64 - self.src as i64
+    }
+}
+
+fn apply_map(input: usize, map: &[MapEntry]) -> usize {
+    for entry in map {
+        if entry.source().contains(input as i64) {
+            return input - entry.src + entry.tgt;
+        }
+    }
+
+    input
+}
+
+/// Moves the numbers covered by every entry of the map, leaving the others in place
+fn apply_map_to_ranges(input: &IntervalSet, map: &[MapEntry]) -> IntervalSet {
+    let mut output = IntervalSet::new();
+    let mut unmapped = input.clone();
+    for entry in map {
+        let source = IntervalSet::from(entry.source());
+        output = output.union(&unmapped.intersection(&source).shift(entry.offset()));
+        unmapped = unmapped.difference(&source);
+        trace!(
+            "entry = {:?}, unmapped = {:?}, output = {:?}",
+            entry,
+            unmapped,
+            output
+        );
+    }
+
+    output.union(&unmapped)
+}
+
+fn extract_map_entry(line: Line) -> Result<MapEntry, ParseError> {
+    let mut iter = line.text.split_whitespace();
+    let mut next_number = |expected: &str| match iter.next() {
+        Some(token) => line.parse::<usize>(token, expected),
+        None => Err(line.missing(expected)),
+    };
+    let destination_start = next_number("a destination range start")?;
+    let source_start = next_number("a source range start")?;
+    let size = next_number("a range length")?;
+
+    Ok(MapEntry {
+        tgt: destination_start,
+        src: source_start,
+        len: size,
+    })
+}
+
+/// Parses the seed numbers on the first line and the list of maps below it
+fn parse_almanac(input: &str) -> Result<(Vec<usize>, Vec<Vec<MapEntry>>), ParseError> {
+    let mut lines = parse::lines(input);
+    let first_line = lines
+        .next()
+        .ok_or_else(|| ParseError::incomplete("seeds"))?;
+    let (_, seeds) = first_line.split_once("seeds:")?;
+    let seeds = seeds
+        .split_whitespace()
+        .map(|seed| first_line.parse(seed, "a seed number"))
+        .collect::<Result<_, _>>()?;
+
+    let mut map_vecs: Vec<Vec<MapEntry>> = Vec::new();
+
+    for line in lines {
+        if line.text.trim().is_empty() {
+            continue;
+        } else if line.text.ends_with("map:") {
+            map_vecs.push(Vec::new());
+        } else {
+            let entry = extract_map_entry(line)?;
+            map_vecs
+                .last_mut()
+                .ok_or_else(|| line.invalid(line.text, "a map header"))?
+                .push(entry);
+        }
+    }
+
+    Ok((seeds, map_vecs))
+}
+
+pub fn part1(input: &str) -> Result<usize> {
+    let (mut seeds, map_vecs) = parse_almanac(input)?;
+
+    for maps in &map_vecs {
+        seeds = seeds.iter().map(|x| apply_map(*x, maps)).collect();
+        trace!("{:?}", seeds);
+    }
+
+    seeds.into_iter().min().ok_or_else(|| anyhow!("no seeds"))
+}
+
+pub fn part2(input: &str) -> Result<usize> {
+    let (seeds, map_vecs) = parse_almanac(input)?;
+
+    let mut ranges: IntervalSet = seeds
+        .chunks(2)
+        .map(|chunk| match chunk {
+            [start, len] => Ok(Interval::with_len(*start as i64, *len as i64)),
+            _ => Err(anyhow!("seed {} has no range length", chunk[0])),
+        })
+        .collect::<Result<_>>()?;
+    debug!("{:?}", ranges);
+
+    for maps in &map_vecs {
+        ranges = apply_map_to_ranges(&ranges, maps);
+        trace!("{:?}", ranges)
+    }
+
+    let min = ranges.min();
+    debug!("min = {:?}", min);
+
+    min.map(|min| min as usize)
+        .ok_or_else(|| anyhow!("no seeds"))
+}
+
+pub fn day() -> Day {
+    Day {
+        number: 5,
+        dir: env!("CARGO_MANIFEST_DIR"),
+        part1: Some(|input| Ok(part1(input)?.to_string())),
+        part2: Some(|input| Ok(part2(input)?.to_string())),
+        generate: Some(gen::generate),
+        render: None,
+        dot: None,
+        parse: Some(|input| {
+            parse_almanac(input)?;
+            Ok(())
+        }),
+        params: &[],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("example.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(EXAMPLE).unwrap(), 35);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(EXAMPLE).unwrap(), 46);
+    }
+}
@@ -0,0 +1,95 @@
+//! A naive solution, which looks up every seed on its own instead of splitting ranges.
+use anyhow::{anyhow, Result};
+
+/// A map, as a list of `(destination, source, length)`
+type Map = Vec<[usize; 3]>;
+
+/// The seeds and the maps
+fn parse(input: &str) -> Result<(Vec<usize>, Vec<Map>)> {
+    let mut blocks = input.split("\n\n");
+    let seeds = blocks
+        .next()
+        .and_then(|line| line.strip_prefix("seeds:"))
+        .ok_or_else(|| anyhow!("no seeds"))?
+        .split_whitespace()
+        .map(|seed| seed.parse())
+        .collect::<Result<_, _>>()?;
+    let maps = blocks
+        .map(|block| {
+            block
+                .lines()
+                .skip(1)
+                .map(|line| {
+                    let numbers: Vec<usize> = line
+                        .split_whitespace()
+                        .map(|n| n.parse())
+                        .collect::<Result<_, _>>()?;
+                    numbers
+                        .try_into()
+                        .map_err(|_| anyhow!("expected three numbers in {:?}", line))
+                })
+                .collect::<Result<_>>()
+        })
+        .collect::<Result<_>>()?;
+    Ok((seeds, maps))
+}
+
+fn location(seed: usize, maps: &[Vec<[usize; 3]>]) -> usize {
+    maps.iter().fold(seed, |number, map| {
+        map.iter()
+            .find(|[_, src, len]| (*src..src + len).contains(&number))
+            .map_or(number, |[tgt, src, _]| number - src + tgt)
+    })
+}
+
+pub fn part1(input: &str) -> Result<usize> {
+    let (seeds, maps) = parse(input)?;
+    seeds
+        .iter()
+        .map(|&seed| location(seed, &maps))
+        .min()
+        .ok_or_else(|| anyhow!("no seeds"))
+}
+
+/// Only feasible for small ranges of seeds
+pub fn part2(input: &str) -> Result<usize> {
+    let (seeds, maps) = parse(input)?;
+    seeds
+        .chunks(2)
+        .flat_map(|range| range[0]..range[0] + range[1])
+        .map(|seed| location(seed, &maps))
+        .min()
+        .ok_or_else(|| anyhow!("no seeds"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::differential::assert_agree;
+
+    fn small_almanac(rng: &mut common::gen::StdRng, size: usize) -> String {
+        crate::gen::almanac(rng, size, 1000)
+    }
+
+    #[test]
+    fn part1_agrees() {
+        assert_agree(
+            |input| Ok(crate::part1(input)?.to_string()),
+            |input| Ok(part1(input)?.to_string()),
+            small_almanac,
+            1..=10,
+            0..30,
+        );
+    }
+
+    #[test]
+    fn part2_agrees() {
+        assert_agree(
+            |input| Ok(crate::part2(input)?.to_string()),
+            |input| Ok(part2(input)?.to_string()),
+            small_almanac,
+            1..=10,
+            0..30,
+        );
+    }
+}
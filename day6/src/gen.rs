@@ -0,0 +1,49 @@
+//! Random boat race records.
+use common::gen::StdRng;
+use rand::Rng;
+
+/// `size` races, at most four so that the single race of part 2 stays within the
+/// precision of the solution. Every record can be beaten, also in part 2.
+pub fn generate(rng: &mut StdRng, size: usize) -> String {
+    let num_races = size.clamp(1, 4);
+    loop {
+        let times: Vec<u64> = (0..num_races).map(|_| rng.gen_range(7..100)).collect();
+        let distances: Vec<u64> = times
+            .iter()
+            .map(|&time| rng.gen_range(time * time / 8..time * time / 4))
+            .collect();
+        let join = |numbers: &[u64]| {
+            numbers
+                .iter()
+                .map(|n| format!("{:>5}", n))
+                .collect::<String>()
+        };
+        let concat = |numbers: &[u64]| -> u64 {
+            numbers
+                .iter()
+                .map(|n| n.to_string())
+                .collect::<String>()
+                .parse()
+                .unwrap()
+        };
+        let (time, distance) = (concat(&times), concat(&distances));
+        if distance < time * time / 4 {
+            return format!("Time:    {}\nDistance:{}\n", join(&times), join(&distances));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::gen::rng;
+
+    #[test]
+    fn generated_inputs_are_solved() {
+        for seed in 0..20 {
+            let input = generate(&mut rng(seed), 4);
+            assert!(crate::part1(&input).unwrap() > 0);
+            assert!(crate::part2(&input).unwrap() > 0);
+        }
+    }
+}
@@ -0,0 +1,133 @@
+pub mod gen;
+pub mod reference;
+
+use anyhow::{anyhow, Result};
+use common::math::quadratic_below_zero;
+use common::parse::{self, Line, ParseError};
+use common::Day;
+use log::debug;
+
+/// Parses the numbers after the `label:` at the start of the line
+fn parse_numbers(line: Option<Line>, label: &str) -> Result<Vec<u64>, ParseError> {
+    let line = line.ok_or_else(|| ParseError::incomplete(format!("{} line", label)))?;
+    let (start, numbers) = line.split_once(":")?;
+    if start != label {
+        return Err(line.invalid(start, format!("{:?}", label)));
+    }
+
+    numbers
+        .split_whitespace()
+        .map(|number| line.parse(number, "a number"))
+        .collect()
+}
+
+fn parse_races(input: &str) -> Result<(Vec<u64>, Vec<u64>)> {
+    let mut lines = parse::lines(input);
+    let times = parse_numbers(lines.next(), "Time")?;
+    let distances = parse_numbers(lines.next(), "Distance")?;
+    if times.len() != distances.len() {
+        return Err(anyhow!(
+            "{} race times but {} distances",
+            times.len(),
+            distances.len()
+        ));
+    }
+
+    debug!("{:?}", times);
+    debug!("{:?}", distances);
+
+    Ok((times, distances))
+}
+
+pub fn part1(input: &str) -> Result<u64> {
+    let (times, distances) = parse_races(input)?;
+
+    times
+        .iter()
+        .zip(distances.iter())
+        .map(|(race_time, distance)| compute_c_range(*race_time, *distance))
+        .product()
+}
+
+/// In part 2 the spaces between the numbers are bad kerning, so there is a single race
+pub fn part2(input: &str) -> Result<u64> {
+    let (times, distances) = parse_races(&input.replace(' ', ""))?;
+    match (times.as_slice(), distances.as_slice()) {
+        ([race_time], [distance]) => compute_c_range(*race_time, *distance),
+        _ => Err(anyhow!("expected a single race")),
+    }
+}
+
+pub fn day() -> Day {
+    Day {
+        number: 6,
+        dir: env!("CARGO_MANIFEST_DIR"),
+        part1: Some(|input| Ok(part1(input)?.to_string())),
+        part2: Some(|input| Ok(part2(input)?.to_string())),
+        generate: Some(gen::generate),
+        render: None,
+        dot: None,
+        parse: None,
+        params: &[],
+    }
+}
+
+/// The number of ways to hold the button for a whole number of milliseconds `c` and beat
+/// the record: the boat travels `c * (race_time - c)`, so these are the integers with
+/// `c^2 - race_time * c + distance < 0`. Solved exactly, as the times in part 2 are too
+/// large for `f64` to tell a tie from a win. Fails for times and distances beyond `i64`.
+fn compute_c_range(race_time: u64, distance: u64) -> Result<u64> {
+    let (Ok(b), Ok(c)) = (i64::try_from(race_time), i64::try_from(distance)) else {
+        return Err(anyhow!(
+            "the race of {} ms with a record of {} mm is too long",
+            race_time,
+            distance
+        ));
+    };
+    let Some(range) = quadratic_below_zero(1, -b, c) else {
+        return Ok(0);
+    };
+    debug!("lower: {}, upper: {}", range.start(), range.end());
+
+    Ok((range.end() - range.start() + 1) as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("example.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(EXAMPLE).unwrap(), 288);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(EXAMPLE).unwrap(), 71503);
+    }
+
+    #[test]
+    fn ties_do_not_win() {
+        // Holding for 10^9 or 10^9 + 1 ms exactly ties the record
+        assert_eq!(
+            compute_c_range(2_000_000_001, 1_000_000_001_000_000_000).unwrap(),
+            0
+        );
+        assert_eq!(
+            compute_c_range(2_000_000_001, 1_000_000_000_999_999_999).unwrap(),
+            2
+        );
+    }
+
+    #[test]
+    fn races_beyond_i64_are_an_error() {
+        assert!(part1("Time: 9223372036854775808\nDistance: 1").is_err());
+        assert!(part2("Time: 1\nDistance: 9223372036854775808").is_err());
+        assert_eq!(
+            part1("Time: 9223372036854775807\nDistance: 0").unwrap(),
+            9223372036854775806
+        );
+    }
+}
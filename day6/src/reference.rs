@@ -0,0 +1,63 @@
+//! A naive solution, which tries every time to hold the button instead of solving the
+//! quadratic inequality in floating point.
+use anyhow::{anyhow, Result};
+
+fn numbers(line: Option<&str>) -> Result<Vec<u64>> {
+    let (_, numbers) = line
+        .and_then(|line| line.split_once(':'))
+        .ok_or_else(|| anyhow!("expected a line of numbers"))?;
+    Ok(numbers
+        .split_whitespace()
+        .map(|n| n.parse())
+        .collect::<Result<_, _>>()?)
+}
+
+fn ways_to_win(time: u64, record: u64) -> u64 {
+    (0..=time)
+        .filter(|hold| hold * (time - hold) > record)
+        .count() as u64
+}
+
+pub fn part1(input: &str) -> Result<u64> {
+    let mut lines = input.lines();
+    let times = numbers(lines.next())?;
+    let records = numbers(lines.next())?;
+    Ok(times
+        .iter()
+        .zip(&records)
+        .map(|(&time, &record)| ways_to_win(time, record))
+        .product())
+}
+
+/// Only feasible for short races
+pub fn part2(input: &str) -> Result<u64> {
+    part1(&input.replace(' ', ""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::differential::assert_agree;
+
+    #[test]
+    fn part1_agrees() {
+        assert_agree(
+            |input| Ok(crate::part1(input)?.to_string()),
+            |input| Ok(part1(input)?.to_string()),
+            crate::gen::generate,
+            1..=4,
+            0..50,
+        );
+    }
+
+    #[test]
+    fn part2_agrees() {
+        assert_agree(
+            |input| Ok(crate::part2(input)?.to_string()),
+            |input| Ok(part2(input)?.to_string()),
+            crate::gen::generate,
+            1..=2,
+            0..50,
+        );
+    }
+}
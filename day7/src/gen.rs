@@ -0,0 +1,40 @@
+//! Random Camel Cards hands and bids.
+use common::gen::StdRng;
+use rand::Rng;
+use std::collections::HashSet;
+
+const CARDS: &[u8] = b"23456789TJQKA";
+
+/// `size` distinct hands, since equal hands would have no well-defined order.
+pub fn generate(rng: &mut StdRng, size: usize) -> String {
+    assert!(size <= 100_000, "too many hands");
+    let mut seen = HashSet::new();
+    let mut out = String::new();
+    while seen.len() < size {
+        // Drawing from few distinct cards makes the stronger hand types more common
+        let num_kinds = rng.gen_range(1..=5);
+        let start = rng.gen_range(0..=CARDS.len() - num_kinds);
+        let hand: String = (0..5)
+            .map(|_| CARDS[start + rng.gen_range(0..num_kinds)] as char)
+            .collect();
+        if seen.insert(hand.clone()) {
+            out.push_str(&format!("{} {}\n", hand, rng.gen_range(1..=1000)));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::gen::rng;
+
+    #[test]
+    fn generated_inputs_are_solved() {
+        for seed in 0..20 {
+            let input = generate(&mut rng(seed), 200);
+            crate::part1(&input).unwrap();
+            crate::part2(&input).unwrap();
+        }
+    }
+}
@@ -0,0 +1,240 @@
+pub mod gen;
+
+use anyhow::Result;
+use common::parse::{self, Line, ParseError};
+use common::Day;
+use core::fmt;
+use log::trace;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+// A card is a set of cards of the same value, e.g. AA or 666
+#[derive(PartialEq, Eq, Clone, Copy)]
+struct CardSet {
+    num: u32,
+    value: usize,
+}
+
+impl PartialOrd for CardSet {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        let num_order = self.num.partial_cmp(&other.num);
+        if num_order == Some(Ordering::Equal) {
+            self.value.partial_cmp(&other.value)
+        } else {
+            num_order
+        }
+    }
+}
+
+impl fmt::Display for CardSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let char = value_to_char(self.value);
+        let val = char.to_string().repeat(self.num as usize);
+        write!(f, "{}", val)
+    }
+}
+
+// A Hand is an ordered vector of CardSet's.
+#[derive(PartialEq, Eq)]
+struct Hand {
+    hand_str: String,
+    cards: Vec<CardSet>,
+    jokers: bool,
+}
+
+#[derive(PartialEq, Eq, Ord, PartialOrd)]
+enum HandType {
+    Five,
+    Four,
+    FullHouse,
+    Three,
+    TwoPair,
+    Pair,
+    High,
+}
+
+impl PartialOrd for Hand {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        let type_order = other.hand_type().partial_cmp(&self.hand_type());
+        if type_order == Some(Ordering::Equal) {
+            // self.cards.partial_cmp(&other.cards)
+            for (c1, c2) in self.hand_str.chars().zip(other.hand_str.chars()) {
+                let v1 = char_to_value(c1, self.jokers);
+                let v2 = char_to_value(c2, other.jokers);
+                let order = v1.partial_cmp(&v2);
+                if order != Some(Ordering::Equal) {
+                    return order;
+                }
+            }
+            Some(Ordering::Equal)
+        } else {
+            type_order
+        }
+    }
+}
+
+impl fmt::Display for Hand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut cards_str = String::new();
+        cards_str.push_str(&self.hand_str);
+        cards_str.push_str(" | ");
+        for card in &self.cards {
+            cards_str.push_str(&card.to_string());
+            cards_str.push(' ');
+        }
+        write!(f, "{}", cards_str)
+    }
+}
+
+impl Hand {
+    /// With `jokers` set, J cards are the weakest cards but count towards the best set.
+    pub fn new(hand_str: &str, jokers: bool) -> Self {
+        let mut hand_count: HashMap<usize, u32> = hand_str
+            .chars()
+            .map(|c| char_to_value(c, jokers))
+            .fold(HashMap::new(), |mut acc, x| {
+                *acc.entry(x).or_insert(0) += 1;
+                acc
+            });
+        let num_jokers = hand_count.remove(&1).unwrap_or(0);
+        let mut cards: Vec<CardSet> = hand_count
+            .iter()
+            .map(|(k, v)| CardSet { num: *v, value: *k })
+            .collect();
+        cards.sort_by(|a, b| b.partial_cmp(a).unwrap());
+        if !cards.is_empty() {
+            cards[0].num += num_jokers;
+        }
+
+        Self {
+            hand_str: hand_str.to_string(),
+            cards,
+            jokers,
+        }
+    }
+    pub fn hand_type(&self) -> HandType {
+        let num_sets = self.cards.len();
+        let best_num = match self.cards.first() {
+            Some(first_card) => first_card.num,
+            None => 5,
+        };
+
+        // let best_num = self.cards.get(0).num;
+        match (num_sets, best_num) {
+            (0, 5) => HandType::Five,
+            (1, 5) => HandType::Five,
+            (2, 4) => HandType::Four,
+            (3, 3) => HandType::Three,
+            (4, 2) => HandType::Pair,
+            (5, 1) => HandType::High,
+            (3, 2) => HandType::TwoPair,
+            (2, 3) => HandType::FullHouse,
+            _ => panic!(
+                "Unknown hand type {}. num_sets={}, best_num={}",
+                self, num_sets, best_num
+            ),
+        }
+    }
+}
+
+fn char_to_value(c: char, jokers: bool) -> usize {
+    match c {
+        'T' => 10,
+        'J' if jokers => 1,
+        'J' => 11,
+        'Q' => 12,
+        'K' => 13,
+        'A' => 14,
+        _ => c.to_digit(10).unwrap() as usize,
+    }
+}
+
+fn value_to_char(v: usize) -> char {
+    match v {
+        10 => 'T',
+        1 | 11 => 'J',
+        12 => 'Q',
+        13 => 'K',
+        14 => 'A',
+        _ => std::char::from_digit(v as u32, 10).unwrap(),
+    }
+}
+
+struct Bet {
+    hand: Hand,
+    bet: u32,
+}
+
+impl Bet {
+    pub fn from_line(line: Line, jokers: bool) -> Result<Self, ParseError> {
+        let (hand_str, bet) = line.split_once(" ")?;
+        let is_card = |c: char| "23456789TJQKA".contains(c);
+        if hand_str.chars().count() != 5 || !hand_str.chars().all(is_card) {
+            return Err(line.invalid(hand_str, "a hand of five cards"));
+        }
+
+        Ok(Self {
+            hand: Hand::new(hand_str, jokers),
+            bet: line.parse(bet.trim(), "a bid")?,
+        })
+    }
+}
+
+/// The bids times the ranks, which outgrow a `u32` for large enough bids
+fn total_winnings(input: &str, jokers: bool) -> Result<u64, ParseError> {
+    let mut bets: Vec<Bet> = parse::lines(input)
+        .map(|line| Bet::from_line(line, jokers))
+        .collect::<Result<_, _>>()?;
+    bets.sort_by(|bet1, bet2| bet1.hand.partial_cmp(&bet2.hand).unwrap());
+    let mut sum = 0;
+    for (rank, bet) in bets.iter().enumerate() {
+        trace!("{}\t{} * {}", bet.hand, bet.bet, rank + 1);
+        sum += (rank as u64 + 1) * bet.bet as u64;
+    }
+
+    Ok(sum)
+}
+
+pub fn part1(input: &str) -> Result<u64> {
+    Ok(total_winnings(input, false)?)
+}
+
+pub fn part2(input: &str) -> Result<u64> {
+    Ok(total_winnings(input, true)?)
+}
+
+pub fn day() -> Day {
+    Day {
+        number: 7,
+        dir: env!("CARGO_MANIFEST_DIR"),
+        part1: Some(|input| Ok(part1(input)?.to_string())),
+        part2: Some(|input| Ok(part2(input)?.to_string())),
+        generate: Some(gen::generate),
+        render: None,
+        dot: None,
+        parse: None,
+        params: &[],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("example.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(EXAMPLE).unwrap(), 6440);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(EXAMPLE).unwrap(), 5905);
+    }
+
+    #[test]
+    fn large_bids() {
+        assert_eq!(part1("AAAAA 4294967295\n22345 1").unwrap(), 8589934591);
+    }
+}
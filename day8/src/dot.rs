@@ -0,0 +1,72 @@
+//! The network, with the start nodes of the ghosts in green and the end nodes in red.
+//! Highlighting marks the loop every ghost ends up walking around.
+use crate::{check_network, find_end_times, parse_network};
+use anyhow::Result;
+use common::dot::{Attributes, Dot};
+use std::collections::HashSet;
+
+pub fn export(input: &str, highlight: bool) -> Result<Dot> {
+    let (instructions, network) = parse_network(input)?;
+    check_network(&network)?;
+
+    // The steps `(node, instruction)` that are part of a loop
+    let mut looping = HashSet::new();
+    if highlight {
+        for start in network.keys().filter(|node| node.ends_with('A')) {
+            let loop_start = find_end_times(&network, instructions, start).cycle.start;
+            let mut position = start.as_str();
+            for (step, instruction) in instructions.chars().cycle().enumerate() {
+                if looping.contains(&(position, step % instructions.len())) {
+                    break;
+                }
+                if step >= loop_start {
+                    looping.insert((position, step % instructions.len()));
+                }
+                let (left, right) = &network[position];
+                position = if instruction == 'L' { left } else { right };
+            }
+        }
+    }
+    let looping_moves: HashSet<(&str, char)> = looping
+        .iter()
+        .map(|&(node, i)| (node, instructions.as_bytes()[i] as char))
+        .collect();
+
+    let mut nodes: Vec<&String> = network.keys().collect();
+    nodes.sort();
+    let mut dot = Dot::directed("day8");
+    for node in nodes {
+        let attributes = match node.as_bytes().last() {
+            Some(b'A') => Attributes::new().fill("palegreen"),
+            Some(b'Z') => Attributes::new().fill("salmon"),
+            _ => Attributes::new(),
+        };
+        dot.node(node, attributes);
+        let (left, right) = &network[node];
+        for (instruction, next) in [('L', left), ('R', right)] {
+            let attributes = Attributes::new().label(instruction);
+            let attributes = match looping_moves.contains(&(node.as_str(), instruction)) {
+                true => attributes.highlight(),
+                false => attributes,
+            };
+            dot.edge(node, next, attributes);
+        }
+    }
+    Ok(dot)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ghost_loops() {
+        let input = "LR\n\n11A = (11B, XXX)\n11B = (XXX, 11Z)\n11Z = (11B, XXX)\nXXX = (XXX, XXX)";
+        let dot = export(input, true).unwrap().to_string();
+        assert!(dot.contains("\"11A\" -> \"11B\" [label=\"L\"];"));
+        assert!(dot.contains("\"11B\" -> \"11Z\" [label=\"R\", color=\"red\", penwidth=\"3\"];"));
+        assert!(dot.contains("\"11Z\" -> \"11B\" [label=\"L\", color=\"red\", penwidth=\"3\"];"));
+        assert!(dot.contains("\"11Z\" [style=\"filled\", fillcolor=\"salmon\"];"));
+        assert_eq!(dot.matches("red").count(), 2);
+    }
+}
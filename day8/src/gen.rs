@@ -0,0 +1,119 @@
+//! Random desert maps, built so that the ghosts of part 2 meet after a known number of
+//! steps.
+use common::gen::StdRng;
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+const PRIMES: [usize; 13] = [3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43];
+
+/// `size` is the number of instructions, rounded up to a prime. See [`network`].
+pub fn generate(rng: &mut StdRng, size: usize) -> String {
+    network(rng, size, 6).0
+}
+
+/// Names of three capital letters, which end in neither A nor Z
+fn node_names(rng: &mut StdRng, count: usize) -> Vec<String> {
+    let letters: Vec<char> = ('A'..='Z').collect();
+    let mut names: Vec<String> = letters
+        .iter()
+        .flat_map(|&a| letters.iter().map(move |&b| (a, b)))
+        .flat_map(|(a, b)| ('B'..='Y').map(move |c| format!("{}{}{}", a, b, c)))
+        .collect();
+    assert!(count <= names.len(), "too many nodes");
+    names.shuffle(rng);
+    names.truncate(count);
+    names
+}
+
+/// A network with up to `max_ghosts` ghosts, and the answers to both parts.
+///
+/// The number of instructions `n` is a prime, and every ghost walks a cycle of `n * p`
+/// steps through its end node, for a different prime `p`. The end node takes the place
+/// of the start node in the cycle, so the ghost first reaches it after `n * p` steps,
+/// and then every `n * p` steps. The first ghost starts at `AAA` and ends at `ZZZ`.
+/// Every node of a cycle is always entered at the same instruction, and its other
+/// branch leads to a random node of the same cycle.
+pub fn network(rng: &mut StdRng, size: usize, max_ghosts: usize) -> (String, usize, usize) {
+    let num_instructions = *[2, 3]
+        .iter()
+        .chain(&PRIMES)
+        .chain(&[47, 53, 59, 61, 67, 71, 73, 79, 83, 89, 97])
+        .find(|&&prime| prime >= size)
+        .unwrap_or(&97);
+    let instructions: Vec<char> = (0..num_instructions)
+        .map(|_| if rng.gen_bool(0.5) { 'L' } else { 'R' })
+        .collect();
+
+    let mut primes: Vec<usize> = PRIMES
+        .iter()
+        .copied()
+        .filter(|&p| p != num_instructions)
+        .collect();
+    primes.shuffle(rng);
+    let num_ghosts = rng.gen_range(1..=max_ghosts);
+    primes.truncate(num_ghosts);
+    // Fewer ghosts keep the network within the number of names
+    while primes.len() > 1 && primes.iter().sum::<usize>() * num_instructions > 15_000 {
+        primes.pop();
+    }
+
+    let mut names = node_names(rng, primes.iter().sum::<usize>() * num_instructions).into_iter();
+    // Start and end nodes are told apart by the first two letters of their names
+    let mut prefixes: Vec<String> = ('A'..='Z')
+        .flat_map(|a| ('A'..='Z').map(move |b| format!("{}{}", a, b)))
+        .filter(|prefix| prefix != "AA" && prefix != "ZZ")
+        .collect();
+    prefixes.shuffle(rng);
+    let mut prefixes = prefixes.into_iter();
+    let mut lines = Vec::new();
+    for (ghost, &prime) in primes.iter().enumerate() {
+        let length = prime * num_instructions;
+        // The cycle, where the end node stands in for the start node
+        let mut cycle: Vec<String> = (0..length).map(|_| names.next().unwrap()).collect();
+        let (start, end) = match ghost {
+            0 => ("AAA".to_string(), "ZZZ".to_string()),
+            _ => {
+                let prefix = prefixes.next().unwrap();
+                (format!("{}A", prefix), format!("{}Z", prefix))
+            }
+        };
+        cycle[0] = end.clone();
+        for (i, node) in cycle.iter().enumerate() {
+            let next = &cycle[(i + 1) % length];
+            let other = &cycle[rng.gen_range(0..length)];
+            let (left, right) = match instructions[i % num_instructions] {
+                'L' => (next, other),
+                _ => (other, next),
+            };
+            lines.push(format!("{} = ({}, {})", node, left, right));
+            if i == 0 {
+                lines.push(format!("{} = ({}, {})", start, left, right));
+            }
+        }
+    }
+    lines.shuffle(rng);
+
+    let part1 = primes[0] * num_instructions;
+    let part2 = primes.iter().product::<usize>() * num_instructions;
+    let instructions: String = instructions.into_iter().collect();
+    (
+        format!("{}\n\n{}\n", instructions, lines.join("\n")),
+        part1,
+        part2,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::gen::rng;
+
+    #[test]
+    fn planted_answers() {
+        for seed in 0..20 {
+            let (input, part1, part2) = network(&mut rng(seed), 20, 6);
+            assert_eq!(crate::part1(&input).unwrap(), part1);
+            assert_eq!(crate::part2(&input).unwrap(), part2 as i64);
+        }
+    }
+}
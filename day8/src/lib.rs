@@ -0,0 +1,216 @@
+pub mod dot;
+pub mod gen;
+pub mod reference;
+
+use anyhow::{anyhow, Result};
+use common::cycle;
+use common::parse::{self, Line, ParseError};
+use common::Day;
+use log::debug;
+use std::collections::HashMap;
+
+/// Parses a node of the form `AAA = (BBB, CCC)`
+fn parse_line(line: Line) -> Result<(String, (String, String)), ParseError> {
+    let (key, value) = line.split_once(" = ")?;
+    let pair = value
+        .strip_prefix('(')
+        .and_then(|pair| pair.strip_suffix(')'))
+        .ok_or_else(|| line.invalid(value, "\"(<left>, <right>)\""))?;
+    let (value1, value2) = pair
+        .split_once(", ")
+        .ok_or_else(|| line.invalid(pair, "\"<left>, <right>\""))?;
+    Ok((key.to_string(), (value1.to_string(), value2.to_string())))
+}
+
+/// The steps at which a ghost walking from `start` is at an end node, found by walking
+/// until the same node comes up at the same instruction again. Every node has to be in
+/// the network.
+fn find_end_times<'a>(
+    network: &'a Network,
+    instructions: &str,
+    start: &'a str,
+) -> cycle::Occurrences {
+    let instructions = instructions.as_bytes();
+    // Where the ghost is, and how many steps it has taken
+    let step = |(position, steps): &mut (&'a str, usize)| {
+        let (left, right) = &network[*position];
+        *position = match instructions[*steps % instructions.len()] {
+            b'L' => left,
+            _ => right,
+        };
+        *steps += 1;
+    };
+    let key = |&(position, steps): &(&'a str, usize)| (position, steps % instructions.len());
+    let found = cycle::find_cycle((start, 0), step, key, cycle::Mode::Hash);
+
+    let mut ghost = (start, 0);
+    let mut times = Vec::new();
+    while ghost.1 < found.start + found.period {
+        if ghost.0.ends_with('Z') {
+            times.push(ghost.1);
+        }
+        step(&mut ghost);
+    }
+    cycle::Occurrences {
+        times,
+        cycle: found,
+    }
+}
+
+type Network = HashMap<String, (String, String)>;
+
+/// Fails if a node leads to a node that is not in the network
+fn check_network(network: &Network) -> Result<()> {
+    match network
+        .values()
+        .flat_map(|(left, right)| [left, right])
+        .find(|node| !network.contains_key(*node))
+    {
+        Some(unknown) => Err(anyhow!("unknown node {}", unknown)),
+        None => Ok(()),
+    }
+}
+
+fn parse_network(input: &str) -> Result<(&str, Network)> {
+    let mut lines = parse::lines(input);
+
+    let first_line = lines
+        .next()
+        .ok_or_else(|| ParseError::incomplete("instructions"))?;
+    let instructions = first_line.text;
+    if instructions.is_empty() {
+        return Err(first_line.missing("'L' or 'R' instructions").into());
+    }
+    if let Some((i, c)) = instructions
+        .char_indices()
+        .find(|&(_, c)| c != 'L' && c != 'R')
+    {
+        return Err(first_line
+            .invalid(&instructions[i..i + c.len_utf8()], "'L' or 'R'")
+            .into());
+    }
+    debug!("{}", instructions);
+
+    let mut network: Network = HashMap::new();
+
+    lines.next();
+    for line in lines {
+        let (key, value) = parse_line(line)?;
+        network.insert(key, value);
+    }
+
+    Ok((instructions, network))
+}
+
+pub fn part1(input: &str) -> Result<usize> {
+    let (instructions, network) = parse_network(input)?;
+
+    let mut position = "AAA";
+    for (i, instr) in instructions.chars().cycle().enumerate() {
+        if position == "ZZZ" {
+            return Ok(i);
+        }
+        let (left, right) = network
+            .get(position)
+            .ok_or_else(|| anyhow!("unknown node {}", position))?;
+        position = match instr {
+            'L' => left,
+            'R' => right,
+            _ => position,
+        };
+    }
+
+    Err(anyhow!("no instructions"))
+}
+
+/// Every ghost ends up walking in a loop, so the steps at which it is at an end node
+/// repeat with the length of its loop, and those of all ghosts line up by the Chinese
+/// remainder theorem. The loops need not start at the same step, nor have coprime
+/// lengths.
+pub fn part2(input: &str) -> Result<i64> {
+    let (instructions, network) = parse_network(input)?;
+
+    let start_positions: Vec<&String> = network.keys().filter(|s| s.ends_with('A')).collect();
+    debug!("{:?}", start_positions);
+    if start_positions.is_empty() {
+        return Err(anyhow!("no start nodes"));
+    }
+    check_network(&network)?;
+    let ghosts: Vec<cycle::Occurrences> = start_positions
+        .iter()
+        .map(|start| find_end_times(&network, instructions, start))
+        .collect();
+    for ghost in &ghosts {
+        debug!("loop of {:?}", ghost.cycle);
+    }
+
+    cycle::first_common(&ghosts)
+        .map(|step| step as i64)
+        .ok_or_else(|| anyhow!("the ghosts are never all at end nodes"))
+}
+
+pub fn day() -> Day {
+    Day {
+        number: 8,
+        dir: env!("CARGO_MANIFEST_DIR"),
+        part1: Some(|input| Ok(part1(input)?.to_string())),
+        part2: Some(|input| Ok(part2(input)?.to_string())),
+        generate: Some(gen::generate),
+        render: None,
+        dot: Some(dot::export),
+        parse: Some(|input| {
+            parse_network(input)?;
+            Ok(())
+        }),
+        params: &[],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("example.txt");
+    const EXAMPLE2: &str = include_str!("example2.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(EXAMPLE).unwrap(), 2);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(EXAMPLE2).unwrap(), 6);
+    }
+
+    #[test]
+    fn part2_loops_with_offsets() {
+        // The first ghost is at 11Z after 2, 5, 8, ... steps, the second one at 22Z
+        // after 1, 3, 5, ... steps
+        let input = "L\n\n\
+            11A = (11B, XXX)\n\
+            11B = (11Z, XXX)\n\
+            11Z = (11C, XXX)\n\
+            11C = (11B, XXX)\n\
+            22A = (22Z, XXX)\n\
+            22Z = (22B, XXX)\n\
+            22B = (22Z, XXX)\n\
+            XXX = (XXX, XXX)\n";
+        assert_eq!(part2(input).unwrap(), 5);
+        assert_eq!(reference::part2(input).unwrap(), 5);
+    }
+
+    #[test]
+    fn non_ascii_instruction_is_an_error() {
+        assert!(part1("\u{fffd}").is_err());
+    }
+
+    #[test]
+    fn no_instructions_is_an_error() {
+        let err = part2("\n\nAAA = (AAA, AAA)\n").unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<ParseError>(),
+            Some(ParseError::Missing { line: 1, .. })
+        ));
+    }
+}
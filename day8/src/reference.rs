@@ -0,0 +1,57 @@
+//! A naive solution to part 2, which walks all ghosts in lockstep instead of assuming
+//! that each one runs in a cycle through a single end node.
+use anyhow::{anyhow, Result};
+use std::collections::HashMap;
+
+pub fn part2(input: &str) -> Result<usize> {
+    let (instructions, nodes) = input
+        .split_once("\n\n")
+        .ok_or_else(|| anyhow!("expected instructions and nodes"))?;
+    let network: HashMap<&str, (&str, &str)> = nodes
+        .lines()
+        .map(|line| {
+            let (node, targets) = line.split_once(" = ")?;
+            let (left, right) = targets
+                .strip_prefix('(')?
+                .strip_suffix(')')?
+                .split_once(", ")?;
+            Some((node, (left, right)))
+        })
+        .collect::<Option<_>>()
+        .ok_or_else(|| anyhow!("invalid node"))?;
+
+    let mut positions: Vec<&str> = network
+        .keys()
+        .copied()
+        .filter(|node| node.ends_with('A'))
+        .collect();
+    for (steps, instruction) in instructions.trim().chars().cycle().enumerate() {
+        if positions.iter().all(|node| node.ends_with('Z')) {
+            return Ok(steps);
+        }
+        for position in &mut positions {
+            let (left, right) = network
+                .get(position)
+                .ok_or_else(|| anyhow!("unknown node {}", position))?;
+            *position = if instruction == 'L' { left } else { right };
+        }
+    }
+    Err(anyhow!("no instructions"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::differential::assert_agree;
+
+    #[test]
+    fn part2_agrees() {
+        assert_agree(
+            |input| Ok(crate::part2(input)?.to_string()),
+            |input| Ok(part2(input)?.to_string()),
+            |rng, size| crate::gen::network(rng, size, 2).0,
+            1..=7,
+            0..20,
+        );
+    }
+}
@@ -0,0 +1,51 @@
+//! Random sequences of polynomial values.
+use common::gen::StdRng;
+use rand::Rng;
+
+/// `size` sequences of 21 values, each given by a polynomial of degree at most 6.
+pub fn generate(rng: &mut StdRng, size: usize) -> String {
+    let mut out = String::new();
+    for _ in 0..size {
+        // Start from the constant differences, and sum them up `degree` times
+        let degree = rng.gen_range(0..=6);
+        let mut values = [rng.gen_range(-5..=5); 21];
+        for _ in 0..degree {
+            let mut value = rng.gen_range(-10..=10);
+            for difference in values.iter_mut() {
+                (value, *difference) = (value + *difference, value);
+            }
+        }
+        let line: Vec<String> = values.iter().map(|value: &i32| value.to_string()).collect();
+        out.push_str(&line.join(" "));
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::gen::rng;
+
+    #[test]
+    fn generated_inputs_are_solved() {
+        for seed in 0..20 {
+            let input = generate(&mut rng(seed), 50);
+            crate::part1(&input).unwrap();
+            crate::part2(&input).unwrap();
+        }
+    }
+
+    #[test]
+    fn constant_sequences() {
+        let input = generate(&mut rng(0), 200);
+        let constant = input.lines().find(|line| {
+            let values: Vec<&str> = line.split(' ').collect();
+            values.iter().all(|value| *value == values[0])
+        });
+        let line = constant.expect("some sequences are constant");
+        let value: i32 = line.split(' ').next().unwrap().parse().unwrap();
+        assert_eq!(crate::part1(line).unwrap(), value);
+        assert_eq!(crate::part2(line).unwrap(), value);
+    }
+}
@@ -0,0 +1,128 @@
+pub mod gen;
+
+use anyhow::{anyhow, Result};
+use common::parse::{self, Line, ParseError};
+use common::Day;
+
+fn extract_line(line: Line) -> Result<Vec<i32>, ParseError> {
+    let mut nums = Vec::new();
+    for s in line.text.split_whitespace() {
+        nums.push(line.parse(s, "a number")?);
+    }
+    if nums.is_empty() {
+        return Err(line.missing("a number"));
+    }
+    Ok(nums)
+}
+
+/// The differences between consecutive values, and whether they are all zero. `None`
+/// if a difference does not fit.
+fn diff(input: Vec<i32>) -> Option<(Vec<i32>, bool)> {
+    let mut diff: Vec<i32> = Vec::new();
+    let mut all_zero: bool = true;
+    for i in 0..input.len() - 1 {
+        let new_val = input[i + 1].checked_sub(input[i])?;
+        if new_val != 0 {
+            all_zero = false;
+        }
+        diff.push(new_val);
+    }
+    Some((diff, all_zero))
+}
+
+/// Extrapolates the sequence one step forward, which is the sum of the last values of
+/// all the difference sequences
+fn make_predict_forward(input: &[i32]) -> Option<i32> {
+    let mut val = input.to_vec();
+    let mut prediction: i32 = 0;
+    while val.iter().any(|&x| x != 0) {
+        prediction = prediction.checked_add(val[val.len() - 1])?;
+        val = diff(val)?.0;
+    }
+    Some(prediction)
+}
+
+/// Extrapolates the sequence one step backwards
+fn make_predict(input: &[i32]) -> Option<i32> {
+    let mut all_zero = input.iter().filter(|&x| *x != 0).count() == 0;
+    // let mut tower: Vec<Vec<i32>> = vec![input.to_vec()];
+    let mut val = input.to_vec();
+    let mut first_vals = vec![input[0]];
+    while !all_zero {
+        let (new_diff, new_all_zero) = diff(val)?;
+        // A single value has no differences, and stays the same going back
+        let Some(&first) = new_diff.first() else {
+            break;
+        };
+        first_vals.push(first);
+        val = new_diff;
+        all_zero = new_all_zero;
+    }
+    first_vals
+        .iter()
+        .rev()
+        .try_fold(0i32, |acc, x| x.checked_sub(acc))
+}
+
+/// The sum of the predictions, which fails when the numbers grow too large
+fn sum_predictions(input: &str, predict: fn(&[i32]) -> Option<i32>) -> Result<i32> {
+    let input_vecs: Vec<Vec<i32>> = parse::lines(input)
+        .map(extract_line)
+        .collect::<Result<_, _>>()?;
+
+    input_vecs
+        .iter()
+        .try_fold(0i32, |sum, x| sum.checked_add(predict(x)?))
+        .ok_or_else(|| anyhow!("the numbers grow too large to extrapolate"))
+}
+
+pub fn part1(input: &str) -> Result<i32> {
+    sum_predictions(input, make_predict_forward)
+}
+
+pub fn part2(input: &str) -> Result<i32> {
+    sum_predictions(input, make_predict)
+}
+
+pub fn day() -> Day {
+    Day {
+        number: 9,
+        dir: env!("CARGO_MANIFEST_DIR"),
+        part1: Some(|input| Ok(part1(input)?.to_string())),
+        part2: Some(|input| Ok(part2(input)?.to_string())),
+        generate: Some(gen::generate),
+        render: None,
+        dot: None,
+        parse: None,
+        params: &[],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("example.txt");
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(EXAMPLE).unwrap(), 114);
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(EXAMPLE).unwrap(), 2);
+    }
+
+    #[test]
+    fn single_values() {
+        assert_eq!(part1("7\n-3").unwrap(), 4);
+        assert_eq!(part2("7\n-3").unwrap(), 4);
+    }
+
+    #[test]
+    fn overflow_is_an_error() {
+        assert!(part1("7 872663013 1").is_err());
+        assert!(part2("7 872663013 1").is_err());
+    }
+}